@@ -0,0 +1,22 @@
+import React, { ReactElement } from "react";
+import { Ability } from "../game/gameState.d";
+import translate from "../game/lang";
+import Counter from "./Counter";
+import StyledText from "./StyledText";
+import { usePlayerState } from "./useHooks";
+
+/// Shows how many more times you can use a limited ability of your role
+export default function AbilityChargesCounter(props: Readonly<{
+    ability: Ability
+}>): ReactElement | null {
+    const charges = usePlayerState(
+        playerState => playerState.abilityCharges[props.ability],
+        ["yourAbilityCharges"]
+    );
+
+    if (charges === undefined) return null;
+
+    return <Counter max={charges.total} current={charges.remaining}>
+        <StyledText>{translate("ability."+props.ability+".remaining", charges.remaining)}</StyledText>
+    </Counter>
+}
@@ -3,7 +3,7 @@ import React, { ReactElement } from "react";
 import GAME_MANAGER, { find, replaceMentions } from "..";
 import StyledText, { KeywordDataMap, PLAYER_SENDER_KEYWORD_DATA } from "./StyledText";
 import "./chatMessage.css"
-import { ChatGroup, PhaseState, PlayerIndex, Tag, Verdict } from "../game/gameState.d";
+import { ChatGroup, PhaseState, PlayerIndex, Tag, TrialSystem, Verdict } from "../game/gameState.d";
 import { Role, RoleState } from "../game/roleState.d";
 import { Grave } from "../game/graveState";
 import DOMPurify from "dompurify";
@@ -375,6 +375,8 @@ export function translateChatMessage(message: ChatMessageVariant, playerNames?:
                     playerNames[message.player], translate("role."+message.role+".name")
                 );
             }
+        case "gamePaused":
+            return translate("chatMessage.gamePaused." + message.reason);
         case "playerQuit":
             return translate("chatMessage.playerQuit",
                 playerNames[message.playerIndex]
@@ -405,9 +407,20 @@ export function translateChatMessage(message: ChatMessageVariant, playerNames?:
             }
             
         case "trialInformation":
-            return translate("chatMessage.trialInformation",
-                message.requiredVotes,
-                message.trialsLeft
+            switch (message.trialSystem) {
+                case "plurality":
+                    return translate("chatMessage.trialInformation.plurality", message.trialsLeft);
+                case "directLynch":
+                    return translate("chatMessage.trialInformation.directLynch", message.requiredVotes);
+                default:
+                    return translate("chatMessage.trialInformation",
+                        message.requiredVotes,
+                        message.trialsLeft
+                    );
+            }
+        case "nominationTied":
+            return translate("chatMessage.nominationTied",
+                playerListToString(message.playerIndices, playerNames)
             );
         case "voted":
             if (message.votee !== null) {
@@ -656,6 +669,7 @@ export function translateChatMessage(message: ChatMessageVariant, playerNames?:
         case "targetsMessage":
         case "psychicFailed":
         case "phaseFastForwarded":
+        case "gameResumed":
         case "mayorCantWhisper":
         case "youAttackedSomeone":
         case "youWereAttacked":
@@ -733,7 +747,11 @@ export type ChatMessageVariant = {
 {
     type: "trialInformation", 
     requiredVotes: number, 
-    trialsLeft: number
+    trialsLeft: number,
+    trialSystem: TrialSystem
+} | {
+    type: "nominationTied",
+    playerIndices: PlayerIndex[]
 } | {
     type: "voted", 
     voter: PlayerIndex, 
@@ -759,6 +777,11 @@ export type ChatMessageVariant = {
     targets: PlayerIndex[]
 } | {
     type: "phaseFastForwarded"
+} | {
+    type: "gamePaused",
+    reason: "host" | "lostConnection"
+} | {
+    type: "gameResumed"
 } |
 // Role-specific
 {
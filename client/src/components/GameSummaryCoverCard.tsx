@@ -0,0 +1,140 @@
+import React, { ReactElement } from "react";
+import translate from "../game/lang";
+import { GameSummary, NightSummary, PlayerIndex, PlayerSummary, TrialSummary } from "../game/gameState.d";
+import StyledText from "./StyledText";
+import GraveComponent from "./grave";
+import { useGameState } from "./useHooks";
+import { CopyButton } from "./ClipboardButtons";
+import "./gameSummaryCoverCard.css";
+
+export default function GameSummaryCoverCard(): ReactElement {
+    const summary = useGameState(
+        gameState => gameState.gameSummary,
+        ["gameSummary"]
+    )!;
+    const playerNames = useGameState(
+        gameState => gameState.players.map(player => player.toString()),
+        ["gamePlayers"]
+    )!;
+    const nightTrace = useGameState(
+        gameState => gameState.nightTrace,
+        ["nightTrace"]
+    )!;
+
+    if (summary === null) {
+        return <div className="game-summary-cover-card">
+            <h1>{translate("menu.gameSummary.title")}</h1>
+        </div>
+    }
+
+    return <div className="game-summary-cover-card">
+        <h1>{translate("menu.gameSummary.title")}</h1>
+        <ResolutionHeader summary={summary}/>
+        <section>
+            <h2>{translate("menu.gameSummary.players")}</h2>
+            {summary.players.map(player => 
+                <PlayerRow key={player.player} player={player} playerNames={playerNames}/>
+            )}
+        </section>
+        <section>
+            <h2>{translate("menu.gameSummary.nights")}</h2>
+            {summary.nights.map(night => 
+                <NightSection key={night.dayNumber} night={night} playerNames={playerNames}/>
+            )}
+        </section>
+        <section>
+            <h2>{translate("menu.gameSummary.trials")}</h2>
+            {summary.trials.map((trial, index) => 
+                <TrialSection key={index} trial={trial} playerNames={playerNames}/>
+            )}
+        </section>
+        <section>
+            <h2>{translate("menu.graveyard.title")}</h2>
+            {summary.graves.map(grave => 
+                <GraveComponent key={grave.player} grave={grave} playerNames={playerNames}/>
+            )}
+        </section>
+        {nightTrace !== null && <section>
+            <h2>{translate("menu.gameSummary.nightTrace")}</h2>
+            <span>{translate("menu.gameSummary.nightTrace.description")}</span>
+            <CopyButton text={JSON.stringify(nightTrace, null, 2)}/>
+        </section>}
+    </div>
+}
+
+function ResolutionHeader(props: Readonly<{ summary: GameSummary }>): ReactElement {
+    if (props.summary.resolution === null) {
+        return <h2>{translate("menu.gameSummary.noWinner")}</h2>
+    }
+    return <h2><StyledText>{translate("menu.gameSummary.resolution." + props.summary.resolution)}</StyledText></h2>
+}
+
+function PlayerRow(props: Readonly<{ player: PlayerSummary, playerNames: string[] }>): ReactElement {
+    const roles = [
+        props.player.startingRole,
+        ...props.player.roleSwitches.map(roleSwitch => roleSwitch.role)
+    ].map(role => translate("role." + role + ".name"));
+
+    return <div className={"game-summary-player" + (props.player.won ? " won" : "")}>
+        <StyledText>
+            {translate("menu.gameSummary.player",
+                props.playerNames[props.player.player],
+                roles.join(" → "),
+                translate(props.player.won ? "menu.gameSummary.won" : "menu.gameSummary.lost")
+            )}
+        </StyledText>
+    </div>
+}
+
+function NightSection(props: Readonly<{ night: NightSummary, playerNames: string[] }>): ReactElement {
+    const names = (players: PlayerIndex[]) => players.map(player => props.playerNames[player]).join(", ");
+
+    return <div className="game-summary-night">
+        <h3>{translate("menu.gameSummary.night", props.night.dayNumber)}</h3>
+        {props.night.actions.map(action => 
+            <div key={action.player}>
+                <StyledText>
+                    {translate("menu.gameSummary.nightAction",
+                        props.playerNames[action.player],
+                        names(action.selection),
+                        names(action.visits.map(visit => visit.target))
+                    )}
+                </StyledText>
+            </div>
+        )}
+    </div>
+}
+
+function TrialSection(props: Readonly<{ trial: TrialSummary, playerNames: string[] }>): ReactElement {
+    return <div className="game-summary-trial">
+        <h3>
+            <StyledText>
+                {translate("menu.gameSummary.trial",
+                    props.trial.dayNumber,
+                    props.playerNames[props.trial.playerOnTrial],
+                    props.trial.nominatedBy.map(player => props.playerNames[player]).join(", ")
+                )}
+            </StyledText>
+        </h3>
+        {props.trial.verdicts.length !== 0 && <>
+            {props.trial.verdicts.map(([voter, verdict]) => 
+                <div key={voter}>
+                    <StyledText>
+                        {translate("chatMessage.judgementVerdict",
+                            props.playerNames[voter],
+                            translate("verdict." + verdict)
+                        )}
+                    </StyledText>
+                </div>
+            )}
+            <StyledText>
+                {translate("chatMessage.trialVerdict",
+                    props.playerNames[props.trial.playerOnTrial],
+                    props.trial.innocent < props.trial.guilty ? translate("verdict.guilty") : translate("verdict.innocent"),
+                    props.trial.innocent,
+                    props.trial.guilty
+                )}
+            </StyledText>
+        </>}
+    </div>
+}
@@ -15,6 +15,7 @@ import ROLES from "../resources/roles.json";
 import "../menu/game/gameScreenContent/RoleSpecificMenus/smallRoleSpecificMenu.css";
 import LargeKiraMenu from "../menu/game/gameScreenContent/RoleSpecificMenus/LargeKiraMenu";
 import Counter from "./Counter";
+import AbilityChargesCounter from "./AbilityChargesCounter";
 import "./roleSpecific.css";
 import ErosMenu from "../menu/game/gameScreenContent/RoleSpecificMenus/ErosMenu";
 import CounterfeiterMenu from "../menu/game/gameScreenContent/RoleSpecificMenus/CounterfeiterMenu";
@@ -51,21 +52,10 @@ export default function RoleSpecificSection(){
             return <JailorRoleSpecificMenu roleState={roleState}/>;
         case "medium": 
             return <MediumRoleSpecificMenu roleState={roleState}/>
-        case "doctor": {
-            return <Counter
-                max={1}
-                current={roleState.selfHealsRemaining}
-            >
-                <StyledText>{translate("role.doctor.roleDataText", roleState.selfHealsRemaining)}</StyledText>
-            </Counter>
-        }
+        case "doctor":
+            return <AbilityChargesCounter ability="selfHeal"/>
         case "bodyguard":
-            return <Counter
-                max={1}
-                current={roleState.selfShieldsRemaining}
-            >
-                <StyledText>{translate("role.bodyguard.roleDataText", roleState.selfShieldsRemaining)}</StyledText>
-            </Counter>
+            return <AbilityChargesCounter ability="selfShield"/>
         case "engineer":
             return <div className="role-information">
                 <StyledText>{translate("role.engineer.roleDataText." + roleState.trap.type)}</StyledText>
@@ -91,19 +81,11 @@ export default function RoleSpecificSection(){
                     return null
             }
         case "veteran":
-            return <Counter
-                max={3}
-                current={roleState.alertsRemaining}
-            >
-                <StyledText>{translate("role.veteran.roleDataText", roleState.alertsRemaining)}</StyledText>
-            </Counter>
+            return <AbilityChargesCounter ability="alert"/>
+        case "deputy":
+            return <AbilityChargesCounter ability="shoot"/>
         case "armorsmith":
-            return <Counter
-                max={2}
-                current={roleState.openShopsRemaining}
-            >
-                <StyledText>{translate("role.armorsmith.roleDataText", roleState.openShopsRemaining)}</StyledText>
-            </Counter>
+            return <AbilityChargesCounter ability="openShop"/>
         case "marksman": 
             return <MarksmanRoleSpecificMenu roleState={roleState} />
         case "eros":
@@ -199,12 +181,7 @@ function JailorRoleSpecificMenu(props: Readonly<{
         gameState => gameState.phaseState
     )!;
 
-    const counter = <Counter 
-        max={3} 
-        current={props.roleState.executionsRemaining}
-    >
-        <StyledText>{translate("role.jailor.roleDataText.executionsRemaining", props.roleState.executionsRemaining)}</StyledText>
-    </Counter>;
+    const counter = <AbilityChargesCounter ability="execute"/>;
     if(phaseState.type==="night") {
         return counter;
     } else if (props.roleState.jailedTargetRef === null) {
@@ -234,12 +211,7 @@ function MediumRoleSpecificMenu(props: Readonly<{
         ["gamePlayers"]
     )!;
 
-    const counter = <Counter
-        max={2}
-        current={props.roleState.seancesRemaining}
-    >
-        <StyledText>{translate("role.medium.roleDataText.hauntsRemaining", props.roleState.seancesRemaining)}</StyledText>
-    </Counter>
+    const counter = <AbilityChargesCounter ability="seance"/>
     if (props.roleState.seancedTarget === null) {
         return <>
             {counter}
@@ -0,0 +1,82 @@
+import React, { ReactElement, useState } from "react";
+import { CustomRoleSet, getAllRoles } from "../../game/roleListState.d";
+import { Role } from "../../game/roleState.d";
+import translate from "../../game/lang";
+import Icon from "../Icon";
+import StyledText from "../StyledText";
+import "./customRoleSetsSelector.css";
+
+// Must match CustomRoleSet::MAX_NAME_LENGTH on the server
+const MAX_NAME_LENGTH = 30;
+
+export default function CustomRoleSetsSelector(props: Readonly<{
+    disabled?: boolean,
+    customRoleSets: CustomRoleSet[],
+    onChange: (customRoleSets: CustomRoleSet[]) => void,
+}>): ReactElement {
+    const [newName, setNewName] = useState<string>("");
+
+    // Sets are matched to outlines by name, so they can't be renamed once they're made
+    const addSet = () => {
+        const name = newName.trim();
+        if(name.length === 0 || props.customRoleSets.some(set => set.name === name)) return;
+
+        props.onChange([...props.customRoleSets, {name, roles: []}]);
+        setNewName("");
+    }
+    const setRoles = (index: number, roles: Role[]) => {
+        const customRoleSets = [...props.customRoleSets];
+        customRoleSets[index] = {...customRoleSets[index], roles};
+        props.onChange(customRoleSets);
+    }
+
+    return <section className="custom-role-sets-selector will-menu-colors selector-section">
+        <h2>{translate("menu.lobby.customRoleSets")}</h2>
+        {props.customRoleSets.map((customRoleSet, index) =>
+            <div key={customRoleSet.name} className="custom-role-set">
+                <div>
+                    <h3>{customRoleSet.name}</h3>
+                    <button
+                        disabled={props.disabled}
+                        onClick={() => props.onChange(props.customRoleSets.filter((_, i) => i !== index))}
+                    ><Icon size="tiny">delete</Icon></button>
+                </div>
+                <div className="custom-role-set-roles">
+                    {customRoleSet.roles.map(role =>
+                        <button
+                            key={role}
+                            disabled={props.disabled}
+                            onClick={() => setRoles(index, customRoleSet.roles.filter(r => r !== role))}
+                        >
+                            <StyledText noLinks={true}>{translate("role."+role+".name")}</StyledText>
+                            <Icon size="tiny">remove</Icon>
+                        </button>
+                    )}
+                    <select
+                        disabled={props.disabled}
+                        value=""
+                        onChange={e => setRoles(index, [...customRoleSet.roles, e.target.value as Role])}
+                    >
+                        <option value="" disabled={true}>{translate("menu.lobby.customRoleSets.addRole")}</option>
+                        {getAllRoles()
+                            .filter(role => !customRoleSet.roles.includes(role))
+                            .map(role => <option key={role} value={role}>{translate("role."+role+".name")}</option>)
+                        }
+                    </select>
+                </div>
+            </div>
+        )}
+        <div className="custom-role-set-new">
+            <input
+                disabled={props.disabled}
+                type="text"
+                value={newName}
+                maxLength={MAX_NAME_LENGTH}
+                placeholder={translate("menu.lobby.customRoleSets.name")}
+                onChange={e => setNewName(e.target.value)}
+                onKeyUp={e => {if(e.key === "Enter") addSet()}}
+            />
+            <button disabled={props.disabled} onClick={addSet}><Icon size="tiny">add</Icon></button>
+        </div>
+    </section>
+}
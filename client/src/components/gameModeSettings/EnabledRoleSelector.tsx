@@ -19,7 +19,7 @@ export default function EnabledRoleSelector(props: {
     onEnableRoles: (role: Role[]) => void,
     onIncludeAll: () => void
 }): ReactElement {
-    const {enabledRoles} = useContext(GameModeContext);
+    const {enabledRoles, customRoleSets} = useContext(GameModeContext);
 
     const [roleOutlineOption, setRoleOutlineOption] = useState<RoleOutlineOption>({ type: "faction", faction: "town" });
 
@@ -59,6 +59,7 @@ export default function EnabledRoleSelector(props: {
                 <RoleOutlineOptionSelector
                     excludeAny={true}
                     disabled={props.disabled}
+                    customRoleSets={customRoleSets}
                     roleOutlineOption={roleOutlineOption}
                     onChange={setRoleOutlineOption}
                 />
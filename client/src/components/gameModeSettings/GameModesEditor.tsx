@@ -1,7 +1,7 @@
 import { ReactElement, createContext, useCallback, useState } from "react";
 import React from "react";
 import { OutlineListSelector } from "./OutlineSelector";
-import { getAllRoles, RoleList, RoleOutline } from "../../game/roleListState.d";
+import { CustomRoleSet, getAllRoles, getCustomRoleSetsFromRoleList, RoleList, RoleOutline } from "../../game/roleListState.d";
 import translate from "../../game/lang";
 import "./gameModesEditor.css";
 import PhaseTimesSelector from "./PhaseTimeSelector";
@@ -15,7 +15,8 @@ import { GameModeSelector } from "./GameModeSelector";
 const GameModeContext = createContext({
     roleList: [] as RoleList,
     phaseTimes: defaultPhaseTimes(),
-    enabledRoles: [] as Role[]
+    enabledRoles: [] as Role[],
+    customRoleSets: [] as CustomRoleSet[]
 });
 export {GameModeContext};
 
@@ -62,7 +63,7 @@ export default function GameModesEditor(): ReactElement {
         <header>
             <h1>{translate("menu.globalMenu.gameSettingsEditor")}</h1>
         </header>
-        <GameModeContext.Provider value={{roleList, phaseTimes, enabledRoles}}>
+        <GameModeContext.Provider value={{roleList, phaseTimes, enabledRoles, customRoleSets: getCustomRoleSetsFromRoleList(roleList)}}>
             <main>
                 <div>
                     <GameModeSelector 
@@ -0,0 +1,110 @@
+import React, { ReactElement } from "react";
+import { GameRules, NOMINATION_THRESHOLDS, NominationThreshold, TEAM_CHATS, TRIAL_SYSTEMS, TrialSystem } from "../../game/gameState.d";
+import translate from "../../game/lang";
+import "./gameRulesSelector.css";
+
+type NumberRule = "speedUpLivingPlayers" | "speedUpFactor" | "mayorVotingPower" | "trialsPerDay";
+
+// Must match GameRules::is_valid on the server
+const MINIMUMS: Record<NumberRule, number> = {
+    speedUpLivingPlayers: 0,
+    speedUpFactor: 1,
+    mayorVotingPower: 1,
+    trialsPerDay: 1,
+};
+const MAXIMUMS: Record<NumberRule, number> = {
+    speedUpLivingPlayers: 255,
+    speedUpFactor: 10,
+    mayorVotingPower: 10,
+    trialsPerDay: 10,
+};
+
+export default function GameRulesSelector(props: Readonly<{
+    disabled?: boolean,
+    gameRules: GameRules,
+    onChange: (gameRules: GameRules) => void,
+}>): ReactElement {
+    return <section className="game-rules-selector will-menu-colors selector-section">
+        <h2>{translate("menu.lobby.gameRules")}</h2>
+        <div className="game-rules">
+            {(Object.keys(MINIMUMS) as NumberRule[]).map(rule => 
+                <div key={rule}>
+                    <span>{translate("menu.lobby.gameRules." + rule)}</span>
+                    <input
+                        disabled={props.disabled ?? false}
+                        type="text"
+                        value={props.gameRules[rule]}
+                        onChange={(e)=>{
+                            const value = Number(e.target.value);
+                            if (!Number.isInteger(value) || value < MINIMUMS[rule] || value > MAXIMUMS[rule]) return;
+
+                            props.onChange({...props.gameRules, [rule]: value});
+                        }}
+                    />
+                </div>
+            )}
+            <div>
+                <span>{translate("menu.lobby.gameRules.nominationThreshold")}</span>
+                <select
+                    disabled={props.disabled ?? false}
+                    value={props.gameRules.nominationThreshold}
+                    onChange={(e)=>props.onChange({
+                        ...props.gameRules,
+                        nominationThreshold: e.target.value as NominationThreshold
+                    })}
+                >
+                    {NOMINATION_THRESHOLDS.map(threshold => 
+                        <option key={threshold} value={threshold}>
+                            {translate("menu.lobby.gameRules.nominationThreshold." + threshold)}
+                        </option>
+                    )}
+                </select>
+            </div>
+            <div>
+                <span>{translate("menu.lobby.gameRules.trialSystem")}</span>
+                <select
+                    disabled={props.disabled ?? false}
+                    value={props.gameRules.trialSystem}
+                    onChange={(e)=>props.onChange({
+                        ...props.gameRules,
+                        trialSystem: e.target.value as TrialSystem
+                    })}
+                >
+                    {TRIAL_SYSTEMS.map(trialSystem => 
+                        <option key={trialSystem} value={trialSystem}>
+                            {translate("menu.lobby.gameRules.trialSystem." + trialSystem)}
+                        </option>
+                    )}
+                </select>
+            </div>
+            <div>
+                <span>{translate("menu.lobby.gameRules.traceNightActions")}</span>
+                <input
+                    disabled={props.disabled ?? false}
+                    type="checkbox"
+                    checked={props.gameRules.traceNightActions}
+                    onChange={(e)=>props.onChange({
+                        ...props.gameRules,
+                        traceNightActions: e.target.checked
+                    })}
+                />
+            </div>
+            {TEAM_CHATS.map(teamChat =>
+                <div key={teamChat}>
+                    <span>{translate("menu.lobby.gameRules.teamChat", translate("chatGroup."+teamChat+".icon"), translate("menu.lobby.gameRules.teamChat."+teamChat))}</span>
+                    <input
+                        disabled={props.disabled ?? false}
+                        type="checkbox"
+                        checked={props.gameRules.teamChats.includes(teamChat)}
+                        onChange={(e)=>props.onChange({
+                            ...props.gameRules,
+                            teamChats: e.target.checked
+                                ? [...props.gameRules.teamChats, teamChat]
+                                : props.gameRules.teamChats.filter(chat => chat !== teamChat)
+                        })}
+                    />
+                </div>
+            )}
+        </div>
+    </section>
+}
@@ -2,7 +2,7 @@ import React, { useContext } from "react";
 import "./outlineSelector.css";
 import translate from "../../game/lang";
 import ROLES from "../../resources/roles.json";
-import { FACTIONS, ROLE_SETS, RoleList, RoleOutline, RoleOutlineOption, simplifyRoleOutline, translateRoleOutlineOption} from "../../game/roleListState.d";
+import { CustomRoleSet, FACTIONS, MAX_OPTION_WEIGHT, ROLE_SETS, RoleList, ROLE_MODIFIERS, RoleModifier, RoleOutline, RoleOutlineOption, simplifyRoleOutline, translateRoleModifier, translateRoleOutlineOption} from "../../game/roleListState.d";
 import { Role } from "../../game/roleState.d";
 import Icon from "../Icon";
 import { DragAndDrop } from "../DragAndDrop";
@@ -12,16 +12,30 @@ type RoleOutlineSelectorProps = {
     roleOutline: RoleOutline,
     onChange: (value: RoleOutline) => void,
     disabled?: boolean,
+    customRoleSets?: CustomRoleSet[],
 }
 
 export default class RoleOutlineSelector extends React.Component<RoleOutlineSelectorProps> {
     
+    /** Changing the roles keeps the outline's modifiers */
+    handleChange(value: RoleOutline) {
+        this.props.onChange({...value, modifiers: this.props.roleOutline.modifiers});
+    }
+    handleModifierToggle(modifier: RoleModifier) {
+        const modifiers = this.props.roleOutline.modifiers ?? [];
+        this.props.onChange({
+            ...this.props.roleOutline,
+            modifiers: modifiers.includes(modifier)
+                ? modifiers.filter(m => m !== modifier)
+                : [...modifiers, modifier].sort((a, b) => ROLE_MODIFIERS.indexOf(a) - ROLE_MODIFIERS.indexOf(b))
+        });
+    }
     handleRoleOutlineOptionChange(
         index: number,
         value: RoleOutlineOption | "any"
     ){
         if(value === "any") {
-            this.props.onChange({
+            this.handleChange({
                 type: "any",
             });
             return
@@ -29,13 +43,13 @@ export default class RoleOutlineSelector extends React.Component<RoleOutlineSele
 
         if(this.props.roleOutline.type === "roleOutlineOptions") {
             let options = [...this.props.roleOutline.options];
-            options[index] = value;
-            this.props.onChange({
+            options[index] = {...value, weight: options[index].weight};
+            this.handleChange({
                 type: "roleOutlineOptions",
                 options: options
             });
         } else {
-            this.props.onChange({
+            this.handleChange({
                 type: "roleOutlineOptions",
                 options: [value]
             });
@@ -45,7 +59,7 @@ export default class RoleOutlineSelector extends React.Component<RoleOutlineSele
         if(this.props.roleOutline.type !== "roleOutlineOptions") {return}
 
 
-        this.props.onChange({
+        this.handleChange({
             type: "roleOutlineOptions",
             options: [...this.props.roleOutline.options, {
                 type: "role",
@@ -55,11 +69,28 @@ export default class RoleOutlineSelector extends React.Component<RoleOutlineSele
         
     }
 
+    renderModifiers(): React.ReactNode {
+        const modifiers = this.props.roleOutline.modifiers ?? [];
+        return <div className="role-picker-modifiers" title={translate("menu.lobby.roleList.modifiers")}>
+            {ROLE_MODIFIERS.map(modifier => 
+                <button
+                    key={modifier}
+                    disabled={this.props.disabled}
+                    className={modifiers.includes(modifier) ? "highlighted" : undefined}
+                    title={translate("roleModifier."+modifier+".description")}
+                    onClick={() => this.handleModifierToggle(modifier)}
+                >{translateRoleModifier(modifier)}</button>
+            )}
+        </div>
+    }
+
     render(): React.ReactNode {
         if(this.props.roleOutline.type === "any") {
             return <div className="role-picker">
+                {this.renderModifiers()}
                 <RoleOutlineOptionSelector
                     disabled={this.props.disabled}
+                    customRoleSets={this.props.customRoleSets}
                     roleOutlineOption={"any"}
                     onChange={(o) => {
                         this.handleRoleOutlineOptionChange(0, o);
@@ -68,16 +99,37 @@ export default class RoleOutlineSelector extends React.Component<RoleOutlineSele
             </div>
         }else{
             return <div className="role-picker">
+                {this.renderModifiers()}
                 {this.props.roleOutline.options.map((option, index) => {
                     return (
                         <div key={index} className="role-picker-option">
                             <RoleOutlineOptionSelector
                                 disabled={this.props.disabled}
+                                customRoleSets={this.props.customRoleSets}
                                 roleOutlineOption={option}
                                 onChange={(o) => {
                                     this.handleRoleOutlineOptionChange(index, o);
                                 }}
                             />
+                            {this.props.roleOutline.options.length > 1 && <input
+                                className="role-picker-option-weight"
+                                disabled={this.props.disabled}
+                                type="text"
+                                title={translate("menu.lobby.roleList.weight")}
+                                value={option.weight ?? 1}
+                                onChange={(e) => {
+                                    const weight = Number(e.target.value);
+                                    if(!Number.isInteger(weight) || weight < 0 || weight > MAX_OPTION_WEIGHT) return;
+                                    if(this.props.roleOutline.type !== "roleOutlineOptions") return;
+
+                                    let options = [...this.props.roleOutline.options];
+                                    options[index] = {...option, weight};
+                                    this.handleChange({
+                                        type: "roleOutlineOptions",
+                                        options: options
+                                    });
+                                }}
+                            />}
                             <button
                                 disabled={this.props.disabled}
                                 onClick={() => {
@@ -85,12 +137,12 @@ export default class RoleOutlineSelector extends React.Component<RoleOutlineSele
                                     let options = [...this.props.roleOutline.options];
                                     options.splice(index, 1);
                                     if(options.length === 0) {
-                                        this.props.onChange({
+                                        this.handleChange({
                                             type: "any",
                                         });
                                         return
                                     }
-                                    this.props.onChange({
+                                    this.handleChange({
                                         type: "roleOutlineOptions",
                                         options: options
                                     });
@@ -113,7 +165,8 @@ export default class RoleOutlineSelector extends React.Component<RoleOutlineSele
 }
 
 type RoleOutlineOptionSelectorProps = {
-    disabled?: boolean
+    disabled?: boolean,
+    customRoleSets?: CustomRoleSet[],
 } & ({
     excludeAny: true
     roleOutlineOption: RoleOutlineOption,
@@ -133,9 +186,14 @@ export class RoleOutlineOptionSelector extends React.Component<RoleOutlineOption
             return translateRoleOutlineOption(roleOutlineOption);
     }
     render(): React.ReactNode {
+        // Weights are set separately, so they're left out when matching the option
+        const selected = this.props.roleOutlineOption === "any" 
+            ? "any" 
+            : {...this.props.roleOutlineOption, weight: undefined};
+
         return <select
             disabled={this.props.disabled}
-            value={JSON.stringify(this.props.roleOutlineOption)} 
+            value={JSON.stringify(selected)} 
             onChange={(e) => {
                 if(e.target.value === "any" && this.props.excludeAny !== true) {
                     this.props.onChange("any");
@@ -159,6 +217,11 @@ export class RoleOutlineOptionSelector extends React.Component<RoleOutlineOption
                         {this.translateRoleOutlineOptionOrAny({type: "roleSet", roleSet: roleSet})}
                 </option>
             })}
+            {(this.props.customRoleSets ?? []).map((customRoleSet) => {
+                return <option key={"custom " + customRoleSet.name} value={JSON.stringify({type: "customRoleSet", customRoleSet: customRoleSet})}>
+                        {this.translateRoleOutlineOptionOrAny({type: "customRoleSet", customRoleSet: customRoleSet})}
+                </option>
+            })}
             {Object.keys(ROLES).map((role) => {
                 return <option key={role} value={JSON.stringify({type: "role", role: role})}>
                         {this.translateRoleOutlineOptionOrAny({type: "role", role: role as Role})}
@@ -175,7 +238,7 @@ export function OutlineListSelector(props: {
     onRemoveOutline?: ((index: number) => void),
     setRoleList: (newRoleList: RoleList) => void,
 }) {
-    const {roleList} = useContext(GameModeContext);
+    const {roleList, customRoleSets} = useContext(GameModeContext);
 
     const simplify = () => {
         props.setRoleList(roleList.map(simplifyRoleOutline));
@@ -196,6 +259,7 @@ export function OutlineListSelector(props: {
                         {props.disabled === true || <Icon>drag_indicator</Icon>}
                         <RoleOutlineSelector
                             disabled={props.disabled}
+                            customRoleSets={customRoleSets}
                             roleOutline={outline}
                             onChange={(value: RoleOutline) => {props.onChangeRolePicker(value, index);}}
                             key={index}
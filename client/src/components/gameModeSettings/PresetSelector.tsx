@@ -0,0 +1,71 @@
+import React, { ReactElement, useState } from "react";
+import translate from "../../game/lang";
+import Icon from "../Icon";
+import { CopyButton, PasteButton } from "../ClipboardButtons";
+import "./presetSelector.css";
+
+// Must match Preset::MAX_NAME_LENGTH on the server
+const MAX_NAME_LENGTH = 30;
+
+export default function PresetSelector(props: Readonly<{
+    disabled?: boolean,
+    presets: string[],
+    presetCode: string | null,
+    onLoad: (name: string) => void,
+    onImport: (code: string) => void,
+    onExport: (name: string) => void,
+}>): ReactElement {
+    const [selectedPreset, setSelectedPreset] = useState<string>("");
+    const [exportName, setExportName] = useState<string>("");
+    const [importCode, setImportCode] = useState<string>("");
+
+    return <section className="preset-selector will-menu-colors selector-section">
+        <h2>{translate("menu.lobby.presets")}</h2>
+        {props.presets.length > 0 && <div>
+            <select
+                disabled={props.disabled}
+                value={selectedPreset}
+                onChange={e => setSelectedPreset(e.target.value)}
+            >
+                <option value="" disabled={true}>{translate("menu.lobby.presets")}</option>
+                {props.presets.map(preset => <option key={preset} value={preset}>{preset}</option>)}
+            </select>
+            <button
+                disabled={props.disabled || selectedPreset === ""}
+                onClick={() => props.onLoad(selectedPreset)}
+            >{translate("menu.lobby.presets.load")}</button>
+        </div>}
+        <div>
+            <input
+                type="text"
+                value={exportName}
+                maxLength={MAX_NAME_LENGTH}
+                placeholder={translate("menu.lobby.presets.name")}
+                onChange={e => setExportName(e.target.value)}
+            />
+            <button onClick={() => props.onExport(exportName)}>
+                <Icon size="tiny">upload</Icon> {translate("menu.lobby.presets.export")}
+            </button>
+        </div>
+        {props.presetCode !== null && <div>
+            <input type="text" readOnly={true} value={props.presetCode}/>
+            <CopyButton text={props.presetCode}/>
+        </div>}
+        <div>
+            <input
+                disabled={props.disabled}
+                type="text"
+                value={importCode}
+                placeholder={translate("menu.lobby.presets.code")}
+                onChange={e => setImportCode(e.target.value)}
+            />
+            <PasteButton disabled={props.disabled} onClipboardRead={code => props.onImport(code)}/>
+            <button
+                disabled={props.disabled || importCode.trim() === ""}
+                onClick={() => props.onImport(importCode)}
+            >
+                <Icon size="tiny">download</Icon> {translate("menu.lobby.presets.import")}
+            </button>
+        </div>
+    </section>
+}
@@ -0,0 +1,64 @@
+import React, { ReactElement } from "react";
+import { FACTIONS, RoleListAnalysis } from "../../game/roleListState.d";
+import { Role } from "../../game/roleState.d";
+import translate from "../../game/lang";
+import StyledText from "../StyledText";
+import "./roleListAnalysisDisplay.css";
+
+function percent(chance: number): string {
+    return Math.round(chance * 100) + "%";
+}
+
+export default function RoleListAnalysisDisplay(props: Readonly<{
+    disabled?: boolean,
+    analysis: RoleListAnalysis | null,
+    onAnalyze: () => void,
+}>): ReactElement {
+    const analysis = props.analysis;
+    const roleChances = analysis === null ? [] : (Object.entries(analysis.roleChances) as [Role, number][])
+        .sort(([, a], [, b]) => b - a);
+
+    return <section className="role-list-analysis will-menu-colors selector-section">
+        <h2>{translate("menu.lobby.roleListAnalysis")}</h2>
+        <button disabled={props.disabled} onClick={props.onAnalyze}>
+            {translate("menu.lobby.roleListAnalysis.analyze")}
+        </button>
+        {analysis !== null && <>
+            <p>{translate("menu.lobby.roleListAnalysis.samples", analysis.samples)}</p>
+            {analysis.impossibleOutlines.length > 0 && <p className="role-list-analysis-warning">
+                {translate("menu.lobby.roleListAnalysis.impossibleOutlines", analysis.impossibleOutlines.map(outline => outline + 1).join(", "))}
+            </p>}
+            {analysis.failureChance > 0 && <p className="role-list-analysis-warning">
+                {translate("menu.lobby.roleListAnalysis.failureChance", percent(analysis.failureChance))}
+            </p>}
+            {analysis.gameEndsInstantlyChance > 0 && <p className="role-list-analysis-warning">
+                {translate("menu.lobby.roleListAnalysis.gameEndsInstantlyChance", percent(analysis.gameEndsInstantlyChance))}
+            </p>}
+            <h3>{translate("menu.lobby.roleListAnalysis.factions")}</h3>
+            <table>
+                <tbody>
+                    {FACTIONS.map(faction => {
+                        const distribution = analysis.factionDistributions[faction] ?? [];
+                        if (distribution.slice(1).every(chance => chance === 0)) return null;
+                        return <tr key={faction}>
+                            <th><StyledText noLinks={true}>{translate(faction)}</StyledText></th>
+                            {distribution.map((chance, count) =>
+                                <td key={count} title={translate("menu.lobby.roleListAnalysis.players", count)}>
+                                    {count}: {percent(chance)}
+                                </td>
+                            )}
+                        </tr>
+                    })}
+                </tbody>
+            </table>
+            <h3>{translate("menu.lobby.roleListAnalysis.roles")}</h3>
+            <div className="role-list-analysis-roles">
+                {roleChances.map(([role, chance]) =>
+                    <span key={role}>
+                        <StyledText noLinks={true}>{translate("role."+role+".name")}</StyledText> {percent(chance)}
+                    </span>
+                )}
+            </div>
+        </>}
+    </section>
+}
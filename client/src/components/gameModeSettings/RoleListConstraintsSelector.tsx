@@ -0,0 +1,102 @@
+import React, { ReactElement } from "react";
+import { getAllRoles, RoleListConstraint } from "../../game/roleListState.d";
+import { Role } from "../../game/roleState.d";
+import translate from "../../game/lang";
+import Icon from "../Icon";
+import "./roleListConstraintsSelector.css";
+
+function RoleSelect(props: Readonly<{
+    disabled?: boolean,
+    value: Role | "",
+    placeholder?: string,
+    exclude?: Role[],
+    onChange: (role: Role) => void,
+}>): ReactElement {
+    return <select
+        disabled={props.disabled}
+        value={props.value}
+        onChange={e => props.onChange(e.target.value as Role)}
+    >
+        {props.value === "" && <option value="" disabled={true}>{props.placeholder}</option>}
+        {getAllRoles()
+            .filter(role => role === props.value || !(props.exclude ?? []).includes(role))
+            .map(role => <option key={role} value={role}>{translate("role."+role+".name")}</option>)
+        }
+    </select>
+}
+
+export default function RoleListConstraintsSelector(props: Readonly<{
+    disabled?: boolean,
+    roleListConstraints: RoleListConstraint[],
+    onChange: (roleListConstraints: RoleListConstraint[]) => void,
+}>): ReactElement {
+    const setConstraint = (index: number, constraint: RoleListConstraint) => {
+        const constraints = [...props.roleListConstraints];
+        constraints[index] = constraint;
+        props.onChange(constraints);
+    }
+
+    return <section className="role-list-constraints-selector will-menu-colors selector-section">
+        <h2>{translate("menu.lobby.roleListConstraints")}</h2>
+        {props.roleListConstraints.map((constraint, index) =>
+            <div key={index} className="role-list-constraint">
+                {constraint.type === "atMost" ? <>
+                    <span>{translate("menu.lobby.roleListConstraints.atMost")}</span>
+                    <input
+                        disabled={props.disabled}
+                        type="text"
+                        value={constraint.count}
+                        onChange={e => {
+                            const count = Number(e.target.value);
+                            if (!Number.isInteger(count) || count < 0 || count > 255) return;
+                            setConstraint(index, {...constraint, count});
+                        }}
+                    />
+                    <span>{translate("menu.lobby.roleListConstraints.atMost.of")}</span>
+                    {constraint.roles.map(role =>
+                        <button
+                            key={role}
+                            disabled={props.disabled}
+                            onClick={() => setConstraint(index, {...constraint, roles: constraint.roles.filter(r => r !== role)})}
+                        >
+                            {translate("role."+role+".name")} <Icon size="tiny">remove</Icon>
+                        </button>
+                    )}
+                    <RoleSelect
+                        disabled={props.disabled}
+                        value=""
+                        placeholder={translate("menu.lobby.customRoleSets.addRole")}
+                        exclude={constraint.roles}
+                        onChange={role => setConstraint(index, {...constraint, roles: [...constraint.roles, role]})}
+                    />
+                </> : <>
+                    <RoleSelect
+                        disabled={props.disabled}
+                        value={constraint.role}
+                        onChange={role => setConstraint(index, {...constraint, role})}
+                    />
+                    <span>{translate("menu.lobby.roleListConstraints.requires")}</span>
+                    <RoleSelect
+                        disabled={props.disabled}
+                        value={constraint.requiredRole}
+                        onChange={requiredRole => setConstraint(index, {...constraint, requiredRole})}
+                    />
+                </>}
+                <button
+                    disabled={props.disabled}
+                    onClick={() => props.onChange(props.roleListConstraints.filter((_, i) => i !== index))}
+                ><Icon size="tiny">delete</Icon></button>
+            </div>
+        )}
+        <div className="role-list-constraint-new">
+            <button
+                disabled={props.disabled}
+                onClick={() => props.onChange([...props.roleListConstraints, {type: "atMost", roles: [], count: 1}])}
+            ><Icon size="tiny">add</Icon> {translate("menu.lobby.roleListConstraints.atMost")}</button>
+            <button
+                disabled={props.disabled}
+                onClick={() => props.onChange([...props.roleListConstraints, {type: "requires", role: "mafioso", requiredRole: "godfather"}])}
+            ><Icon size="tiny">add</Icon> {translate("menu.lobby.roleListConstraints.requires")}</button>
+        </div>
+    </section>
+}
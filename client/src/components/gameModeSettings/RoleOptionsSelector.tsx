@@ -0,0 +1,129 @@
+import React, { ReactElement } from "react";
+import { getAllRoles, MAX_ROLE_OPTION_DEFENSE, RoleOptions, RoleOptionsMap } from "../../game/roleListState.d";
+import { Role } from "../../game/roleState.d";
+import translate from "../../game/lang";
+import ROLES from "../../resources/roles.json";
+import Icon from "../Icon";
+import "./roleOptionsSelector.css";
+
+/** The value a role has when the host hasn't changed it */
+function defaultRoleOption(role: Role, option: keyof RoleOptions): number | boolean | null {
+    switch(option) {
+        case "uses": return ROLES[role].uses;
+        case "defense": return ROLES[role].armor ? 1 : 0;
+        case "maximumCount": return ROLES[role].maxCount;
+        case "startsPublic": return role === "journalist" ? true : null;
+    }
+}
+
+function roleHasOption(role: Role, option: "uses" | "startsPublic"): boolean {
+    return defaultRoleOption(role, option) !== null;
+}
+
+function NumberOption(props: Readonly<{
+    disabled?: boolean,
+    role: Role,
+    option: "uses" | "defense" | "maximumCount",
+    value: number | undefined,
+    max: number,
+    min?: number,
+    onChange: (value: number | undefined) => void,
+}>): ReactElement {
+    const placeholder = defaultRoleOption(props.role, props.option);
+
+    return <label>
+        {translate("roleOptions."+props.option)}
+        <input
+            disabled={props.disabled}
+            type="text"
+            placeholder={placeholder === null ? translate("roleOptions.unlimited") : String(placeholder)}
+            value={props.value ?? ""}
+            onChange={e => {
+                if (e.target.value === "") {
+                    props.onChange(undefined);
+                    return;
+                }
+                const value = Number(e.target.value);
+                if (!Number.isInteger(value) || value < (props.min ?? 0) || value > props.max) return;
+                props.onChange(value);
+            }}
+        />
+    </label>
+}
+
+export default function RoleOptionsSelector(props: Readonly<{
+    disabled?: boolean,
+    roleOptions: RoleOptionsMap,
+    onChange: (roleOptions: RoleOptionsMap) => void,
+}>): ReactElement {
+    const roles = getAllRoles().filter(role => props.roleOptions[role] !== undefined);
+
+    const setOptions = (role: Role, options: RoleOptions | undefined) => {
+        const roleOptions = {...props.roleOptions};
+        if (options === undefined) {
+            delete roleOptions[role];
+        } else {
+            roleOptions[role] = options;
+        }
+        props.onChange(roleOptions);
+    }
+
+    return <section className="role-options-selector will-menu-colors selector-section">
+        <h2>{translate("menu.lobby.roleOptions")}</h2>
+        {roles.map(role => {
+            const options = props.roleOptions[role]!;
+
+            return <div key={role} className="role-options">
+                <span>{translate("role."+role+".name")}</span>
+                {roleHasOption(role, "uses") && <NumberOption
+                    disabled={props.disabled}
+                    role={role}
+                    option="uses"
+                    value={options.uses}
+                    max={255}
+                    onChange={uses => setOptions(role, {...options, uses})}
+                />}
+                <NumberOption
+                    disabled={props.disabled}
+                    role={role}
+                    option="defense"
+                    value={options.defense}
+                    max={MAX_ROLE_OPTION_DEFENSE}
+                    onChange={defense => setOptions(role, {...options, defense})}
+                />
+                <NumberOption
+                    disabled={props.disabled}
+                    role={role}
+                    option="maximumCount"
+                    value={options.maximumCount}
+                    min={1}
+                    max={255}
+                    onChange={maximumCount => setOptions(role, {...options, maximumCount})}
+                />
+                {roleHasOption(role, "startsPublic") && <label>
+                    {translate("roleOptions.startsPublic")}
+                    <input
+                        disabled={props.disabled}
+                        type="checkbox"
+                        checked={options.startsPublic ?? defaultRoleOption(role, "startsPublic") === true}
+                        onChange={e => setOptions(role, {...options, startsPublic: e.target.checked})}
+                    />
+                </label>}
+                <button
+                    disabled={props.disabled}
+                    onClick={() => setOptions(role, undefined)}
+                ><Icon size="tiny">delete</Icon></button>
+            </div>
+        })}
+        {props.disabled || <select
+            value=""
+            onChange={e => setOptions(e.target.value as Role, {})}
+        >
+            <option value="" disabled={true}>{translate("menu.lobby.roleOptions.addRole")}</option>
+            {getAllRoles()
+                .filter(role => !roles.includes(role))
+                .map(role => <option key={role} value={role}>{translate("role."+role+".name")}</option>)
+            }
+        </select>}
+    </section>
+}
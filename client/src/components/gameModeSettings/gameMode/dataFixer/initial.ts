@@ -1,6 +1,6 @@
 import { VersionConverter } from ".";
 import { PHASES, PhaseTimes } from "../../../../game/gameState.d";
-import { FACTIONS, Faction, ROLE_SETS, RoleList, RoleOutline, RoleOutlineOption, RoleSet, getAllRoles } from "../../../../game/roleListState.d";
+import { CustomRoleSet, FACTIONS, Faction, MAX_OPTION_WEIGHT, ROLE_SETS, RoleList, RoleOutline, RoleOutlineOption, RoleOutlineOptionRoles, RoleSet, getAllRoles } from "../../../../game/roleListState.d";
 import { Role } from "../../../../game/roleState.d";
 import { Failure, ParseResult, ParseSuccess, Success, isFailure } from "../parse";
 
@@ -187,6 +187,17 @@ function parseRoleOutlineOptionList(json: NonNullable<any>): ParseResult<RoleOut
 }
 
 function parseRoleOutlineOption(json: NonNullable<any>): ParseResult<RoleOutlineOption> {
+    const roles = parseRoleOutlineOptionRoles(json);
+    if (isFailure(roles)) return roles;
+
+    if (json.weight === undefined) return roles;
+    if (!Number.isInteger(json.weight) || json.weight < 0 || json.weight > MAX_OPTION_WEIGHT) {
+        return Failure("invalidRoleOutlineOptionWeight", json);
+    }
+    return Success({...roles.value, weight: json.weight});
+}
+
+function parseRoleOutlineOptionRoles(json: NonNullable<any>): ParseResult<RoleOutlineOptionRoles> {
     if (!Object.keys(json).includes('type')) {
         return Failure("roleOutlineOptionMissingTypeKey", json);
     }
@@ -228,6 +239,18 @@ function parseRoleOutlineOption(json: NonNullable<any>): ParseResult<RoleOutline
                 type: "faction",
                 faction: faction.value
             });
+        case "customRoleSet":
+            if (!Object.keys(json).includes('customRoleSet')) {
+                return Failure("roleOutlineOptionMissingCustomRoleSetKey", json);
+            }
+
+            const customRoleSet = parseCustomRoleSet(json.customRoleSet);
+            if (isFailure(customRoleSet)) return customRoleSet;
+
+            return Success({
+                type: "customRoleSet",
+                customRoleSet: customRoleSet.value
+            });
         default:
             return Failure("roleOutlineOptionInvalidType", json);
     }
@@ -300,4 +323,23 @@ function parseFaction(json: NonNullable<any>): ParseResult<Faction> {
         return Failure("invalidFaction", json)
     }
     return Success(json as Faction);
+}
+
+function parseCustomRoleSet(json: NonNullable<any>): ParseResult<CustomRoleSet> {
+    if (typeof json.name !== "string") {
+        return Failure("customRoleSetNameIsNotString", json);
+    }
+    if (!Array.isArray(json.roles)) {
+        return Failure("customRoleSetRolesIsNotArray", json);
+    }
+
+    const roles = json.roles.map(parseRole);
+    for (const role of roles) {
+        if (isFailure(role)) return role;
+    }
+
+    return Success({
+        name: json.name,
+        roles: roles.map((role: ParseSuccess<Role>) => role.value)
+    });
 }
\ No newline at end of file
@@ -2,6 +2,7 @@
 import { replaceMentions } from "..";
 import { Grave } from "../game/graveState";
 import translate from "../game/lang";
+import { translateRoleModifier } from "../game/roleListState.d";
 import { sanitizePlayerMessage } from "./ChatMessage";
 import StyledText from "./StyledText";
 import React, { ReactElement } from "react";
@@ -30,7 +31,10 @@ export default function GraveComponent(props: {grave: Grave, playerNames: string
         deathCauseString = translate("grave.deathCause."+props.grave.information.deathCause.type);
     }
 
-    let graveRoleString = translate(`role.${props.grave.information.role}.name`);
+    let graveRoleString = [
+        ...(props.grave.information.modifiers ?? []).map(translateRoleModifier),
+        translate(`role.${props.grave.information.role}.name`)
+    ].join(" ");
 
     let diedPhaseString = props.grave.diedPhase === "day" ? translate("day") : translate("phase.night");
 
@@ -4,9 +4,9 @@ import { DoomsayerGuess } from "../menu/game/gameScreenContent/RoleSpecificMenus
 import { KiraGuess } from "../menu/game/gameScreenContent/RoleSpecificMenus/LargeKiraMenu";
 import { OjoAction } from "../menu/game/gameScreenContent/RoleSpecificMenus/SmallOjoMenu";
 import { PuppeteerAction } from "../menu/game/gameScreenContent/RoleSpecificMenus/SmallPuppeteerMenu";
-import { PhaseType, PhaseTimes, PlayerIndex, State, Verdict, Player } from "./gameState.d";
+import { PhaseType, PhaseTimes, PlayerIndex, State, Verdict, Player, GameRules } from "./gameState.d";
 import { ToClientPacket, ToServerPacket } from "./packet";
-import { RoleList, RoleOutline } from "./roleListState.d";
+import { CustomRoleSet, RoleList, RoleListConstraint, RoleOptionsMap, RoleOutline } from "./roleListState.d";
 import { Role } from "./roleState.d";
 
 export type Server = {
@@ -72,12 +72,23 @@ export type GameManager = {
      */
     sendJoinPacket(roomCode: number): Promise<boolean>;
     sendKickPlayerPacket(playerId: number): void;
+    sendAddBotPacket(): void;
     sendSetSpectatorPacket(spectator: boolean): void;
     sendSetNamePacket(name: string): void;
     sendSendLobbyMessagePacket(text: string): void;
     sendSetLobbyNamePacket(name: string): void;
     sendStartGamePacket(): Promise<boolean>;
     sendBackToLobbyPacket(): void;
+    sendSetPausedPacket(paused: boolean): void;
+    sendSetAutoPausePacket(autoPause: boolean): void;
+    sendSetGameRulesPacket(rules: GameRules): void;
+    sendSetCustomRoleSetsPacket(customRoleSets: CustomRoleSet[]): void;
+    sendSetRoleListConstraintsPacket(roleListConstraints: RoleListConstraint[]): void;
+    sendSetRoleOptionsPacket(roleOptions: RoleOptionsMap): void;
+    sendLoadPresetPacket(name: string): void;
+    sendImportPresetPacket(code: string): void;
+    sendExportPresetPacket(name: string): void;
+    sendAnalyzeRoleListPacket(): void;
     sendSetPhaseTimePacket(phase: PhaseType, time: number): void;
     sendSetPhaseTimesPacket(phaseTimeSettings: PhaseTimes): void;
     sendSetRoleListPacket(roleListEntries: RoleList): void;
@@ -4,10 +4,10 @@ import GAME_MANAGER from "./../index";
 import messageListener from "./messageListener";
 import CONFIG from "./../resources/config.json"
 import React from "react";
-import { PhaseType, PhaseTimes, Verdict, Player, PlayerIndex } from "./gameState.d";
+import { PhaseType, PhaseTimes, Verdict, Player, PlayerIndex, GameRules } from "./gameState.d";
 import { GameManager, Server, StateListener } from "./gameManager.d";
 import { LobbyPreviewData, ToClientPacket, ToServerPacket } from "./packet";
-import { RoleOutline } from "./roleListState.d";
+import { CustomRoleSet, RoleListConstraint, RoleOptionsMap, RoleOutline } from "./roleListState.d";
 import translate from "./lang";
 import PlayMenu from "../menu/main/PlayMenu";
 import { createGameState, createLobbyState } from "./gameState";
@@ -59,6 +59,7 @@ export function createGameManager(): GameManager {
                 GAME_MANAGER.state.lobbyName = gameState.lobbyName;
                 GAME_MANAGER.state.roleList = gameState.roleList;
                 GAME_MANAGER.state.phaseTimes = gameState.phaseTimes;
+                GAME_MANAGER.state.gameRules = gameState.gameRules;
                 GAME_MANAGER.state.enabledRoles = gameState.enabledRoles;
             }
         },
@@ -78,6 +79,7 @@ export function createGameManager(): GameManager {
                 GAME_MANAGER.state.lobbyName = lobbyState.lobbyName;
                 GAME_MANAGER.state.roleList = lobbyState.roleList;
                 GAME_MANAGER.state.phaseTimes = lobbyState.phaseTimes;
+                GAME_MANAGER.state.gameRules = lobbyState.gameRules;
                 GAME_MANAGER.state.enabledRoles = lobbyState.enabledRoles;
                 GAME_MANAGER.state.host = lobbyState.players.get(lobbyState.myId!)?.host ?? false;
             }
@@ -293,6 +295,12 @@ export function createGameManager(): GameManager {
             });
         },
 
+        sendAddBotPacket() {
+            this.server.sendPacket({
+                type: "addBot"
+            });
+        },
+
         sendSetSpectatorPacket(spectator) {
             this.server.sendPacket({
                 type: "setSpectator",
@@ -346,6 +354,64 @@ export function createGameManager(): GameManager {
                 type: "backToLobby"
             });
         },
+        sendSetPausedPacket(paused: boolean) {
+            this.server.sendPacket({
+                type: paused ? "pause" : "resume"
+            });
+        },
+        sendSetAutoPausePacket(autoPause: boolean) {
+            this.server.sendPacket({
+                type: "setAutoPause",
+                autoPause
+            });
+        },
+        sendSetGameRulesPacket(rules: GameRules) {
+            this.server.sendPacket({
+                type: "setGameRules",
+                rules
+            });
+        },
+        sendSetCustomRoleSetsPacket(customRoleSets: CustomRoleSet[]) {
+            this.server.sendPacket({
+                type: "setCustomRoleSets",
+                customRoleSets
+            });
+        },
+        sendSetRoleListConstraintsPacket(roleListConstraints: RoleListConstraint[]) {
+            this.server.sendPacket({
+                type: "setRoleListConstraints",
+                roleListConstraints
+            });
+        },
+        sendSetRoleOptionsPacket(roleOptions: RoleOptionsMap) {
+            this.server.sendPacket({
+                type: "setRoleOptions",
+                roleOptions
+            });
+        },
+        sendLoadPresetPacket(name: string) {
+            this.server.sendPacket({
+                type: "loadPreset",
+                name
+            });
+        },
+        sendImportPresetPacket(code: string) {
+            this.server.sendPacket({
+                type: "importPreset",
+                code
+            });
+        },
+        sendExportPresetPacket(name: string) {
+            this.server.sendPacket({
+                type: "exportPreset",
+                name
+            });
+        },
+        sendAnalyzeRoleListPacket() {
+            this.server.sendPacket({
+                type: "analyzeRoleList"
+            });
+        },
         sendSetPhaseTimePacket(phase: PhaseType, time: number) {
             if (isValidPhaseTime(time)) {
                 this.server.sendPacket({
@@ -579,16 +645,14 @@ export function createGameManager(): GameManager {
                 }
             }
             if (gameManager.state.stateType === "game") {
-                if (!gameManager.state.ticking) return;
+                if (!gameManager.state.ticking || gameManager.state.paused) return;
 
-                const newTimeLeft = gameManager.state.timeLeftMs - timePassedMs;
+                // Count down against the deadline, so the timer doesn't drift if ticks are late
+                const newTimeLeft = Math.max(0, gameManager.state.phaseDeadline - Date.now());
                 if (Math.floor(newTimeLeft / 1000) < Math.floor(gameManager.state.timeLeftMs / 1000)) {
                     gameManager.invokeStateListeners("tick");
                 }
                 gameManager.state.timeLeftMs = newTimeLeft;
-                if (gameManager.state.timeLeftMs < 0) {
-                    gameManager.state.timeLeftMs = 0;
-                }
             }
         },
     }
@@ -1,7 +1,7 @@
 import { Grave } from "./graveState";
 import { ChatMessage } from "../components/ChatMessage";
 import { Role, RoleState } from "./roleState.d";
-import { RoleList } from "./roleListState.d";
+import { CustomRoleSet, RoleList, RoleListAnalysis, RoleListConstraint, RoleModifier, RoleOptionsMap } from "./roleListState.d";
 import { LobbyPreviewData } from "./packet";
 
 
@@ -30,13 +30,23 @@ export type LobbyState = {
     roleList: RoleList,
     enabledRoles: Role[],
     phaseTimes: PhaseTimes,
+    autoPause: boolean,
+    gameRules: GameRules,
+    customRoleSets: CustomRoleSet[],
+    roleListConstraints: RoleListConstraint[],
+    roleOptions: RoleOptionsMap,
+    // The names of the presets the server has
+    presets: string[],
+    // The last preset this client exported
+    presetCode: string | null,
+    roleListAnalysis: RoleListAnalysis | null,
 
     players: Map<LobbyClientID, LobbyClient>,
     chatMessages: ChatMessage[],
 }
 export type LobbyClient = {
     host: boolean,
-    connection: "connected" | "disconnected" | "couldReconnect",
+    connection: "connected" | "disconnected" | "couldReconnect" | "bot",
     clientType: LobbyClientType
 }
 export type LobbyClientType = {
@@ -57,7 +67,10 @@ type GameState = {
     players: Player[],
     
     phaseState: PhaseState,
+    /** When the phase ends, in milliseconds since the unix epoch */
+    phaseDeadline: number,
     timeLeftMs: number,
+    paused: boolean,
     dayNumber: number,
 
     fastForward: boolean,
@@ -65,8 +78,14 @@ type GameState = {
     roleList: RoleList,
     enabledRoles: Role[],
     phaseTimes: PhaseTimes
+    gameRules: GameRules,
+    roleOptions: RoleOptionsMap,
 
     ticking: boolean,
+    /** Sent once the game ends */
+    gameSummary: GameSummary | null,
+    /** Sent to hosts once the game ends, if they turned on tracing night actions */
+    nightTrace: TracedNight[] | null,
 
     clientState: PlayerGameState | {type: "spectator"},
     host: boolean,
@@ -80,6 +99,8 @@ export type PlayerGameState = {
     myIndex: PlayerIndex,
     
     roleState: RoleState,
+    modifiers: RoleModifier[],
+    abilityCharges: Partial<Record<Ability, Charges>>,
 
     will: string,
     notes: string,
@@ -98,6 +119,45 @@ export type PlayerGameState = {
 export type PlayerIndex = number;
 export type LobbyClientID = number;
 export type Verdict = "innocent"|"guilty"|"abstain";
+
+export type GameSummary = {
+    players: PlayerSummary[],
+    nights: NightSummary[],
+    trials: TrialSummary[],
+    graves: Grave[],
+    resolution: ResolutionState | null,
+}
+export type PlayerSummary = {
+    player: PlayerIndex,
+    startingRole: Role,
+    finalRole: Role,
+    roleSwitches: {dayNumber: number, phase: PhaseType, role: Role}[],
+    alive: boolean,
+    won: boolean,
+}
+export type NightSummary = {
+    dayNumber: number,
+    actions: {
+        player: PlayerIndex,
+        selection: PlayerIndex[],
+        visits: {target: PlayerIndex, attack: boolean}[],
+    }[],
+}
+/** How a night was resolved, priority by priority. Only meant to be read as JSON */
+export type TracedNight = {
+    dayNumber: number,
+    startingVisits: {player: PlayerIndex, visits: {target: PlayerIndex, attack: boolean}[]}[],
+    priorities: {priority: string, events: ({type: string} & Record<string, unknown>)[]}[],
+}
+export type TrialSummary = {
+    dayNumber: number,
+    playerOnTrial: PlayerIndex,
+    nominatedBy: PlayerIndex[],
+    verdicts: [PlayerIndex, Verdict][],
+    guilty: number,
+    innocent: number,
+}
+export type ResolutionState = "town" | "mafia" | "cult" | "fiends" | "death" | "politician" | "draw";
 export const PHASES = ["briefing", "obituary", "discussion", "nomination", "testimony", "judgement", "finalWords", "dusk", "night"] as const;
 export type PhaseType = (typeof PHASES)[number];
 export type PhaseState = {type: "briefing"} | {type: "dusk"} | {type: "night"} | {type: "obituary"} | {type: "discussion"} | 
@@ -117,10 +177,34 @@ export type PhaseState = {type: "briefing"} | {type: "dusk"} | {type: "night"} |
     playerOnTrial: PlayerIndex
 }
 
-export type ChatGroup = "all" | "dead" | "mafia" | "cult" | "jail" | "interview";
+export type ChatGroup = "all" | "dead" | "mafia" | "cult" | "fiends" | "lovers" | "puppeteer" | "jail" | "interview";
+export const TEAM_CHATS = ["mafia", "cult", "fiends", "puppeteer", "lovers"] as const;
+export type TeamChat = typeof TEAM_CHATS[number];
 
 export type PhaseTimes = Record<PhaseType, number>;
 
+export type GameRules = {
+    speedUpLivingPlayers: number,
+    speedUpFactor: number,
+    mayorVotingPower: number,
+    nominationThreshold: NominationThreshold,
+    trialsPerDay: number,
+    trialSystem: TrialSystem,
+    traceNightActions: boolean,
+    teamChats: TeamChat[],
+}
+export const NOMINATION_THRESHOLDS = ["majority", "half", "twoThirds"] as const;
+export type NominationThreshold = typeof NOMINATION_THRESHOLDS[number];
+export const TRIAL_SYSTEMS = ["majority", "plurality", "directLynch"] as const;
+export type TrialSystem = typeof TRIAL_SYSTEMS[number];
+
+export const ABILITIES = ["execute", "selfHeal", "selfShield", "openShop", "alert", "shoot", "seance", "retrain", "forge"] as const;
+export type Ability = typeof ABILITIES[number];
+export type Charges = {
+    remaining: number,
+    total: number
+}
+
 export type Tag = | "godfatherBackup" | "werewolfTracked" | "doused" | "rabbleRouserTarget" | "morticianTagged" | "puppeteerMarionette" | "loveLinked" | "forfeitVote";
 
 export type Player = {
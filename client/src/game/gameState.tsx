@@ -1,4 +1,4 @@
-import GameState, { LobbyClient, LobbyState, PhaseTimes, Player, LobbyClientID, PlayerGameState } from "./gameState.d"
+import GameState, { LobbyClient, LobbyState, PhaseTimes, Player, LobbyClientID, PlayerGameState, GameRules } from "./gameState.d"
 
 
 export function defaultPhaseTimes(): PhaseTimes {
@@ -15,6 +15,19 @@ export function defaultPhaseTimes(): PhaseTimes {
     }
 }
 
+export function defaultGameRules(): GameRules {
+    return {
+        speedUpLivingPlayers: 3,
+        speedUpFactor: 2,
+        mayorVotingPower: 3,
+        nominationThreshold: "majority",
+        trialsPerDay: 3,
+        trialSystem: "majority",
+        traceNightActions: false,
+        teamChats: ["mafia", "cult"],
+    }
+}
+
 export function createLobbyState(): LobbyState {
     return {
         stateType: "lobby",
@@ -26,6 +39,14 @@ export function createLobbyState(): LobbyState {
         roleList: [],
         enabledRoles: [],
         phaseTimes: defaultPhaseTimes(),
+        autoPause: false,
+        gameRules: defaultGameRules(),
+        customRoleSets: [],
+        roleListConstraints: [],
+        roleOptions: {},
+        presets: [],
+        presetCode: null,
+        roleListAnalysis: null,
 
         players: new Map<LobbyClientID, LobbyClient>(),
         chatMessages: [],
@@ -43,7 +64,9 @@ export function createGameState(): GameState {
         players: [],
         
         phaseState: {type:"briefing"},
+        phaseDeadline: 0,
         timeLeftMs: 0,
+        paused: false,
         dayNumber: 1,
 
         fastForward: false,
@@ -51,8 +74,12 @@ export function createGameState(): GameState {
         roleList: [],
         enabledRoles: [],
         phaseTimes: defaultPhaseTimes(),
+        gameRules: defaultGameRules(),
+        roleOptions: {},
 
         ticking: true,
+        gameSummary: null,
+        nightTrace: null,
 
         clientState: createPlayerGameState(),
         host: false
@@ -67,6 +94,8 @@ export function createPlayerGameState(): PlayerGameState {
         myIndex: 0,
         
         roleState: { type: "detective" },
+        modifiers: [],
+        abilityCharges: {},
 
         will: "",
         notes: "",
@@ -1,5 +1,5 @@
 import { PlayerIndex } from "./gameState.d";
-import { Faction } from "./roleListState.d";
+import { Faction, RoleModifier } from "./roleListState.d";
 import { Role } from "./roleState.d";
 
 export type Grave = {
@@ -15,6 +15,7 @@ export type GraveInformation ={
     type: "normal",
     
     role: Role,
+    modifiers: RoleModifier[],
     will: string,
     deathCause: GraveDeathCause,
     deathNotes: string[],
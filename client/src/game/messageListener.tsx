@@ -12,10 +12,12 @@ import { deleteReconnectData, saveReconnectData } from "./localStorage";
 import { WikiArticleLink } from "../components/WikiArticleLink";
 import React from "react";
 import WikiArticle from "../components/WikiArticle";
+import GameSummaryCoverCard from "../components/GameSummaryCoverCard";
 import SpectatorGameScreen from "../menu/spectator/SpectatorGameScreen";
 import LobbyMenu from "../menu/lobby/LobbyMenu";
 import LoadingScreen from "../menu/LoadingScreen";
 import AudioController from "../menu/AudioController";
+import StartMenu from "../menu/main/StartMenu";
 
 export default function messageListener(packet: ToClientPacket){
 
@@ -62,7 +64,7 @@ export default function messageListener(packet: ToClientPacket){
             ANCHOR_CONTROLLER?.clearCoverCard();
         break;
         case "rejectJoin":
-            switch(packet.reason) {
+            switch(packet.reason.type) {
                 case "roomDoesntExist":
                     ANCHOR_CONTROLLER?.pushErrorCard({ title: translate("notification.rejectJoin"), body: translate("notification.rejectJoin.roomDoesntExist") });
                     // If the room doesn't exist, don't suggest the user to reconnect to it.
@@ -86,15 +88,21 @@ export default function messageListener(packet: ToClientPacket){
                 break;
                 default:
                     ANCHOR_CONTROLLER?.pushErrorCard({ title: translate("notification.rejectJoin"), body: `${packet.type} message response not implemented: ${packet.reason}` });
-                    console.error(`${packet.type} message response not implemented: ${packet.reason}`);
+                    console.error(`${packet.type} message response not implemented: ${packet.reason.type}`);
                     console.error(packet);
                 break;
             }
             deleteReconnectData();
             
         break;
+        case "lobbyCrashed":
+            ANCHOR_CONTROLLER?.pushErrorCard({ title: translate("notification.lobbyCrashed"), body: translate("notification.lobbyCrashed.body", packet.message) });
+            deleteReconnectData();
+            GAME_MANAGER.setOutsideLobbyState();
+            ANCHOR_CONTROLLER?.setContent(<StartMenu/>);
+        break;
         case "rejectStart":
-            switch(packet.reason) {
+            switch(packet.reason.type) {
                 case "gameEndsInstantly":
                     ANCHOR_CONTROLLER?.pushErrorCard({ title: translate("notification.rejectStart"), body: translate("notification.rejectStart.gameEndsInstantly") });
                 break;
@@ -107,6 +115,25 @@ export default function messageListener(packet: ToClientPacket){
                 case "zeroTimeGame":
                     ANCHOR_CONTROLLER?.pushErrorCard({ title: translate("notification.rejectStart"), body: translate("notification.rejectStart.zeroTimeGame") });
                 break;
+                case "invalidGameRules":
+                    ANCHOR_CONTROLLER?.pushErrorCard({ title: translate("notification.rejectStart"), body: translate("notification.rejectStart.invalidGameRules") });
+                break;
+                case "customRoleSetHasNoEnabledRoles":
+                    ANCHOR_CONTROLLER?.pushErrorCard({ title: translate("notification.rejectStart"), body: translate("notification.rejectStart.customRoleSetHasNoEnabledRoles") });
+                break;
+                case "roleListTooComplex":
+                    ANCHOR_CONTROLLER?.pushErrorCard({ title: translate("notification.rejectStart"), body: translate("notification.rejectStart.roleListTooComplex") });
+                break;
+                case "roleOutlineCannotBeSatisfied":
+                    ANCHOR_CONTROLLER?.pushErrorCard({ title: translate("notification.rejectStart"), body: translate("notification.rejectStart.roleOutlineCannotBeSatisfied", packet.reason.outline + 1) });
+                break;
+                case "invalidRoleOptions":
+                    ANCHOR_CONTROLLER?.pushErrorCard({ title: translate("notification.rejectStart"), body: translate(
+                        "notification.rejectStart.invalidRoleOptions",
+                        translate("role."+packet.reason.role+".name"),
+                        translate("roleOptions.error."+packet.reason.error)
+                    ) });
+                break;
                 default:
                     ANCHOR_CONTROLLER?.pushErrorCard({ title: translate("notification.rejectStart"), body: "" });
                     console.error(`${packet.type} message response not implemented: ${packet.reason}`);
@@ -237,6 +264,51 @@ export default function messageListener(packet: ToClientPacket){
             if(GAME_MANAGER.state.stateType === "lobby" || GAME_MANAGER.state.stateType === "game")
                 GAME_MANAGER.state.phaseTimes = packet.phaseTimeSettings;
         break;
+        case "gameRules":
+            if(GAME_MANAGER.state.stateType === "lobby" || GAME_MANAGER.state.stateType === "game")
+                GAME_MANAGER.state.gameRules = packet.rules;
+        break;
+        case "customRoleSets":
+            if(GAME_MANAGER.state.stateType === "lobby")
+                GAME_MANAGER.state.customRoleSets = packet.customRoleSets;
+        break;
+        case "roleListConstraints":
+            if(GAME_MANAGER.state.stateType === "lobby")
+                GAME_MANAGER.state.roleListConstraints = packet.roleListConstraints;
+        break;
+        case "roleOptions":
+            if(GAME_MANAGER.state.stateType === "lobby" || GAME_MANAGER.state.stateType === "game")
+                GAME_MANAGER.state.roleOptions = packet.roleOptions;
+        break;
+        case "presets":
+            if(GAME_MANAGER.state.stateType === "lobby")
+                GAME_MANAGER.state.presets = packet.presets;
+        break;
+        case "presetCode":
+            if(GAME_MANAGER.state.stateType === "lobby")
+                GAME_MANAGER.state.presetCode = packet.code;
+        break;
+        case "roleListAnalysis":
+            if(GAME_MANAGER.state.stateType === "lobby")
+                GAME_MANAGER.state.roleListAnalysis = packet.analysis;
+        break;
+        case "rejectPreset":
+            switch(packet.reason.type) {
+                case "unsupportedVersion":
+                    ANCHOR_CONTROLLER?.pushErrorCard({ title: translate("notification.rejectPreset"), body: translate("notification.rejectPreset.unsupportedVersion", packet.reason.version) });
+                break;
+                case "unknownRole":
+                    ANCHOR_CONTROLLER?.pushErrorCard({ title: translate("notification.rejectPreset"), body: translate("notification.rejectPreset.unknownRole", packet.reason.role) });
+                break;
+                default:
+                    ANCHOR_CONTROLLER?.pushErrorCard({ title: translate("notification.rejectPreset"), body: translate("notification.rejectPreset." + packet.reason.type) });
+                break;
+            }
+        break;
+        case "autoPause":
+            if(GAME_MANAGER.state.stateType === "lobby")
+                GAME_MANAGER.state.autoPause = packet.autoPause;
+        break;
         case "enabledRoles":
             if(GAME_MANAGER.state.stateType === "lobby" || GAME_MANAGER.state.stateType === "game")
                 GAME_MANAGER.state.enabledRoles = packet.roles;
@@ -255,8 +327,14 @@ export default function messageListener(packet: ToClientPacket){
             }
         break;
         case "phaseTimeLeft":
+            if(GAME_MANAGER.state.stateType === "game"){
+                GAME_MANAGER.state.phaseDeadline = packet.deadline;
+                GAME_MANAGER.state.timeLeftMs = Math.max(0, packet.deadline - Date.now());
+            }
+        break;
+        case "gamePaused":
             if(GAME_MANAGER.state.stateType === "game")
-                GAME_MANAGER.state.timeLeftMs = packet.secondsLeft * 1000;
+                GAME_MANAGER.state.paused = packet.paused;
         break;
         case "playerOnTrial":
             if(GAME_MANAGER.state.stateType === "game" && (
@@ -362,8 +440,13 @@ export default function messageListener(packet: ToClientPacket){
         case "yourRoleState":
             if(GAME_MANAGER.state.stateType === "game" && GAME_MANAGER.state.clientState.type === "player"){
                 GAME_MANAGER.state.clientState.roleState = packet.roleState;
+                GAME_MANAGER.state.clientState.modifiers = packet.modifiers;
             }
         break;
+        case "yourAbilityCharges":
+            if(GAME_MANAGER.state.stateType === "game" && GAME_MANAGER.state.clientState.type === "player")
+                GAME_MANAGER.state.clientState.abilityCharges = packet.charges;
+        break;
         case "yourSelection":
             if(GAME_MANAGER.state.stateType === "game" && GAME_MANAGER.state.clientState.type === "player")
                 GAME_MANAGER.state.clientState.targets = packet.playerIndices;
@@ -399,6 +482,16 @@ export default function messageListener(packet: ToClientPacket){
             if(GAME_MANAGER.state.stateType === "game")
                 GAME_MANAGER.state.graves = [...GAME_MANAGER.state.graves, packet.grave];
         break;
+        case "gameSummary":
+            if(GAME_MANAGER.state.stateType === "game"){
+                GAME_MANAGER.state.gameSummary = packet.summary;
+                ANCHOR_CONTROLLER?.setCoverCard(<GameSummaryCoverCard/>);
+            }
+        break;
+        case "nightTrace":
+            if(GAME_MANAGER.state.stateType === "game")
+                GAME_MANAGER.state.nightTrace = packet.nights;
+        break;
         case "gameOver":
             if(GAME_MANAGER.state.stateType === "game"){
                 GAME_MANAGER.state.ticking = false;
@@ -1,7 +1,7 @@
-import { PhaseType, PlayerIndex, Verdict, PhaseTimes, Tag, LobbyClientID, ChatGroup, PhaseState, LobbyClient } from "./gameState.d"
+import { PhaseType, PlayerIndex, Verdict, PhaseTimes, Tag, LobbyClientID, ChatGroup, PhaseState, LobbyClient, GameRules, GameSummary, Ability, Charges, TracedNight } from "./gameState.d"
 import { Grave } from "./graveState"
 import { ChatMessage } from "../components/ChatMessage"
-import { RoleList, RoleOutline } from "./roleListState.d"
+import { CustomRoleSet, RoleList, RoleListAnalysis, RoleListConstraint, RoleModifier, RoleOptionsError, RoleOptionsMap, RoleOutline } from "./roleListState.d"
 import { Role, RoleState } from "./roleState.d"
 import { DoomsayerGuess } from "../menu/game/gameScreenContent/RoleSpecificMenus/LargeDoomsayerMenu"
 import { OjoAction } from "../menu/game/gameScreenContent/RoleSpecificMenus/SmallOjoMenu"
@@ -13,6 +13,28 @@ export type LobbyPreviewData = {
     players: [LobbyClientID, string][]
 }
 
+export type RejectStartReason = {
+    type: "gameEndsInstantly" | "roleListTooSmall" | "roleListCannotCreateRoles" | "zeroTimeGame" |
+        "playerDisconnected" | "invalidGameRules" | "customRoleSetHasNoEnabledRoles" | "roleListTooComplex"
+} | {
+    type: "roleOutlineCannotBeSatisfied",
+    outline: number
+} | {
+    type: "invalidRoleOptions",
+    role: Role,
+    error: RoleOptionsError
+}
+
+export type PresetError = {
+    type: "invalidCode" | "invalidSettings" | "notFound"
+} | {
+    type: "unsupportedVersion",
+    version: number
+} | {
+    type: "unknownRole",
+    role: string
+}
+
 export type ToClientPacket = {
     type: "pong",
 } | {
@@ -29,6 +51,9 @@ export type ToClientPacket = {
 } | {
     type: "rejectJoin",
     reason: string
+} | {
+    type: "lobbyCrashed",
+    message: string
 } | 
 // Lobby
 {
@@ -45,7 +70,7 @@ export type ToClientPacket = {
     playerIndex: PlayerIndex
 } | {
     type: "rejectStart",
-    reason: string
+    reason: RejectStartReason
 } | {
     type: "playersHost",
     hosts: LobbyClientID[],
@@ -75,9 +100,36 @@ export type ToClientPacket = {
 } | {
     type: "phaseTimes",
     phaseTimeSettings: PhaseTimes
+} | {
+    type: "autoPause",
+    autoPause: boolean
+} | {
+    type: "gameRules",
+    rules: GameRules
+} | {
+    type: "customRoleSets",
+    customRoleSets: CustomRoleSet[]
+} | {
+    type: "roleListConstraints",
+    roleListConstraints: RoleListConstraint[]
+} | {
+    type: "roleOptions",
+    roleOptions: RoleOptionsMap
 } | {
     type: "enabledRoles",
     roles: Role[]
+} | {
+    type: "presets",
+    presets: string[]
+} | {
+    type: "presetCode",
+    code: string
+} | {
+    type: "rejectPreset",
+    reason: PresetError
+} | {
+    type: "roleListAnalysis",
+    analysis: RoleListAnalysis
 } | 
 // Game
 {
@@ -86,7 +138,10 @@ export type ToClientPacket = {
     dayNumber: number, 
 } | {
     type: "phaseTimeLeft",
-    secondsLeft: number
+    deadline: number
+} | {
+    type: "gamePaused",
+    paused: boolean
 } |{
     type: "playerOnTrial",
     playerIndex: PlayerIndex
@@ -126,7 +181,11 @@ export type ToClientPacket = {
     deathNote: string | null
 } | {
     type: "yourRoleState",
-    roleState: RoleState
+    roleState: RoleState,
+    modifiers: RoleModifier[]
+} | {
+    type: "yourAbilityCharges",
+    charges: Partial<Record<Ability, Charges>>
 } | {
     type: "yourSelection",
     playerIndices: [PlayerIndex]
@@ -145,6 +204,12 @@ export type ToClientPacket = {
 } | {
     type: "addGrave",
     grave: Grave
+} | {
+    type: "gameSummary",
+    summary: GameSummary
+} | {
+    type: "nightTrace",
+    nights: TracedNight[]
 } | {
     type: "gameOver",
     reason: string
@@ -185,6 +250,8 @@ export type ToServerPacket = {
     name: string
 } | {
     type: "startGame",
+} | {
+    type: "addBot",
 } | {
     type: "setRoleList", 
     roleList: RoleList,
@@ -204,8 +271,38 @@ export type ToServerPacket = {
 } | {
     type: "setEnabledRoles", 
     roles: Role[], 
+} | {
+    type: "setAutoPause",
+    autoPause: boolean
+} | {
+    type: "setGameRules",
+    rules: GameRules
+} | {
+    type: "setCustomRoleSets",
+    customRoleSets: CustomRoleSet[]
+} | {
+    type: "setRoleListConstraints",
+    roleListConstraints: RoleListConstraint[]
+} | {
+    type: "setRoleOptions",
+    roleOptions: RoleOptionsMap
+} | {
+    type: "loadPreset",
+    name: string
+} | {
+    type: "importPreset",
+    code: string
+} | {
+    type: "exportPreset",
+    name: string
+} | {
+    type: "analyzeRoleList",
 } | {
     type: "backToLobby",
+} | {
+    type: "pause",
+} | {
+    type: "resume",
 } |
 // Game
 {
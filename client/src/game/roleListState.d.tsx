@@ -28,6 +28,20 @@ export function getRolesFromRoleList(roleList: RoleList): Role[] {
     return Array.from(set);
 }
 
+/** Every custom role set used in the role list, once each */
+export function getCustomRoleSetsFromRoleList(roleList: RoleList): CustomRoleSet[] {
+    let sets: CustomRoleSet[] = [];
+    for(let roleOutline of roleList){
+        if(roleOutline.type !== "roleOutlineOptions") continue;
+        for(let option of roleOutline.options){
+            if(option.type === "customRoleSet" && !sets.some(set => set.name === option.customRoleSet.name)){
+                sets.push(option.customRoleSet);
+            }
+        }
+    }
+    return sets;
+}
+
 export function getRolesComplement(roleList: Role[]): Role[] {
     let roles = Object.keys(ROLES) as Role[];
     return roles.filter((role) => {
@@ -73,6 +87,12 @@ export function getRolesFromRoleSet(roleSet: RoleSet): Role[] {
     }
 }
 
+/** A role set the host made in the lobby. Outlines keep a copy of the whole set */
+export type CustomRoleSet = {
+    name: string,
+    roles: Role[],
+}
+
 
 export type RoleOutlineType = RoleOutline["type"];
 export type RoleOutline = ({
@@ -80,11 +100,22 @@ export type RoleOutline = ({
 } | {
     type: "roleOutlineOptions",
     options: RoleOutlineOption[],
-});
+}) & {
+    /** Given to whoever gets this outline's role. Empty if missing */
+    modifiers?: RoleModifier[]
+};
+
+export const ROLE_MODIFIERS = ["bulletproof", "lazy", "blind", "lucky"] as const;
+export type RoleModifier = typeof ROLE_MODIFIERS[number];
 
 
 export type RoleOutlineOptionType = RoleOutlineOption["type"];
-export type RoleOutlineOption = ({
+export type RoleOutlineOption = RoleOutlineOptionRoles & {
+    /** How likely this option is to be picked, compared to the other options in the outline. 1 if missing */
+    weight?: number
+};
+export const MAX_OPTION_WEIGHT = 255;
+export type RoleOutlineOptionRoles = ({
     type: "roleSet",
     roleSet: RoleSet,
 } | {
@@ -93,19 +124,67 @@ export type RoleOutlineOption = ({
 } | {
     type: "faction",
     faction: Faction,
+} | {
+    type: "customRoleSet",
+    customRoleSet: CustomRoleSet,
 });
 
+/** Rules the generated roles have to follow, on top of the role list */
+export type RoleListConstraint = {
+    type: "atMost",
+    roles: Role[],
+    count: number,
+} | {
+    type: "requires",
+    role: Role,
+    requiredRole: Role,
+};
+
+/** What the host changed about a role. Anything missing keeps the role's default */
+export type RoleOptions = {
+    /** Only for roles with limited uses */
+    uses?: number,
+    defense?: number,
+    maximumCount?: number,
+    /** Only for the journalist */
+    startsPublic?: boolean,
+};
+export type RoleOptionsMap = Partial<Record<Role, RoleOptions>>;
+export const MAX_ROLE_OPTION_DEFENSE = 3;
+export type RoleOptionsError = "usesNotSupported" | "startsPublicNotSupported" | "defenseTooHigh" | "maximumCountIsZero";
+
+/** How games made from a role list tend to turn out, sampled by the server */
+export type RoleListAnalysis = {
+    samples: number,
+    failureChance: number,
+    gameEndsInstantlyChance: number,
+    /** The index is the number of players in the faction */
+    factionDistributions: Partial<Record<Faction, number[]>>,
+    roleChances: Partial<Record<Role, number>>,
+    impossibleOutlines: number[],
+};
 
 
 
 export function translateRoleOutline(roleOutline: RoleOutline): string {
+    const modifiers = (roleOutline.modifiers ?? []).map(translateRoleModifier);
+    return [...modifiers, translateRoleOutlineRoles(roleOutline)].join(" ");
+}
+function translateRoleOutlineRoles(roleOutline: RoleOutline): string {
     switch(roleOutline.type){
         case "any":
             return translate("any");
         case "roleOutlineOptions":
-            return roleOutline.options.map(translateRoleOutlineOption).join(" "+translate("union")+" ");
+            return roleOutline.options.map(option => {
+                if(option.weight === undefined || option.weight === 1)
+                    return translateRoleOutlineOption(option);
+                return translate("roleOutlineOption.weighted", translateRoleOutlineOption(option), option.weight);
+            }).join(" "+translate("union")+" ");
     }
 }
+export function translateRoleModifier(modifier: RoleModifier): string {
+    return translate("roleModifier."+modifier);
+}
 export function translateRoleOutlineOption(roleOutlineOption: RoleOutlineOption): string {
     switch(roleOutlineOption.type){
         case "roleSet":
@@ -114,6 +193,8 @@ export function translateRoleOutlineOption(roleOutlineOption: RoleOutlineOption)
             return translate("role."+roleOutlineOption.role+".name");
         case "faction":
             return translate(roleOutlineOption.faction);
+        case "customRoleSet":
+            return roleOutlineOption.customRoleSet.name;
     }
 }
 export function getRolesFromOutline(roleOutline: RoleOutline): Role[] {
@@ -134,6 +215,8 @@ export function getRolesFromOutlineOption(roleOutlineOption: RoleOutlineOption):
             return Object.keys(ROLES).filter((role) => {
                 return ROLES[role as Role].faction === roleOutlineOption.faction;
             }) as Role[];
+        case "customRoleSet":
+            return roleOutlineOption.customRoleSet.roles;
     }
 }
 
@@ -141,18 +224,20 @@ export function simplifyRoleOutline(roleOutline: RoleOutline): RoleOutline {
 
     if(roleOutline.type === "any") return roleOutline;
 
-    let newOptions = [...roleOutline.options];
-
-    for(let optionA of roleOutline.options){
-        for(let optionB of roleOutline.options){
-            if(outlineOptionIsSubset(optionA, optionB) && optionA !== optionB){
-                newOptions = newOptions.filter((option) => option !== optionA);
-            }
-        }
-    }
+    // Different options can have the same roles, like a custom role set that copies a faction.
+    // Only the first of those is kept, same as the server.
+    // Options with their own weight change how likely their roles are, so they're never removed
+    let newOptions = roleOutline.options.filter((optionA, indexA) => 
+        (optionA.weight !== undefined && optionA.weight !== 1) ||
+        !roleOutline.options.some((optionB, indexB) => 
+            indexA !== indexB && 
+            outlineOptionIsSubset(optionA, optionB) && 
+            (!outlineOptionIsSubset(optionB, optionA) || indexB < indexA)
+        )
+    );
 
     newOptions = newOptions.sort(outlineOptionCompare);
-    return {type: "roleOutlineOptions", options: newOptions};
+    return {type: "roleOutlineOptions", options: newOptions, modifiers: roleOutline.modifiers};
     
     
 }
@@ -10,7 +10,6 @@ import { KiraGuess } from "../menu/game/gameScreenContent/RoleSpecificMenus/Larg
 
 export type RoleState = {
     type: "jailor",
-    executionsRemaining: number,
     jailedTargetRef: number | null
 } | {
     type: "mayor",
@@ -41,10 +40,8 @@ export type RoleState = {
     type: "flowerGirl"
 } | {
     type: "doctor",
-    selfHealsRemaining: number,
 } | {
     type: "bodyguard",
-    selfShieldsRemaining: number,
 } | {
     type: "cop",
 } | {
@@ -54,7 +51,6 @@ export type RoleState = {
     trap: {type: "dismantled"} | {type: "ready"} | {type: "set", target: PlayerIndex, shouldUnset: boolean}
 } | {
     type: "armorsmith",
-    openShopsRemaining: number,
     // nightOpenShop: boolean,
     // nightProtectedPlayers: Vec<PlayerReference>,
     // playersArmor: Vec<PlayerReference>
@@ -63,7 +59,6 @@ export type RoleState = {
     state: {type:"notLoaded"} | {type:"willSuicide"} | {type:"loaded",bullets:number} | {type:"suicided"}
 } | {
     type: "veteran"
-    alertsRemaining: number,
 } | {
     type: "marksman"
     state: {type:"notLoaded"} | {type:"shotTownie"} | {type: "marks", marks: PlayerIndex[]}
@@ -73,7 +68,6 @@ export type RoleState = {
     type: "escort"
 } | {
     type: "medium",
-    seancesRemaining: number,
     seancedTarget: PlayerIndex | null
 } | {
     type: "retributionist"
@@ -87,8 +81,7 @@ export type RoleState = {
     backup: PlayerIndex | null
 } | {
     type: "retrainer"
-    backup: PlayerIndex | null,
-    retrainsRemaining: number
+    backup: PlayerIndex | null
 } | {
     type: "eros"
     action: "loveLink" | "kill"
@@ -97,7 +90,6 @@ export type RoleState = {
     action: "forge" | "noForge",
     fakeRole: Role,
     fakeWill: string
-    forgesRemaining: number,
     // forgedRef
 } | {
     type: "mafioso"
@@ -116,7 +108,6 @@ export type RoleState = {
     type: "forger",
     fakeRole: Role,
     fakeWill: string,
-    forgesRemaining: number,
     // forgedRef
 } | {
     type: "framer"
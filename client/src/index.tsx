@@ -10,7 +10,7 @@ export type Theme = "player-list-menu-colors" | "will-menu-colors" | "role-speci
 
 const ROOT = ReactDOM.createRoot(document.querySelector("#root")!);
 const GAME_MANAGER: GameManager = createGameManager();
-const TIME_PERIOD = 1000;
+const TIME_PERIOD = 100;
 export default GAME_MANAGER;
 
 setInterval(() => {
@@ -8,8 +8,10 @@ import StyledText from "../../components/StyledText";
 import Icon from "../../components/Icon";
 import { Button } from "../../components/Button";
 import { useGameState, usePlayerState } from "../../components/useHooks";
-import { MobileContext } from "../Anchor";
+import { AnchorControllerContext, MobileContext } from "../Anchor";
+import GameSummaryCoverCard from "../../components/GameSummaryCoverCard";
 import { roleSpecificMenuType } from "../Settings";
+import { translateRoleModifier } from "../../game/roleListState.d";
 
 
 export default function HeaderMenu(props: Readonly<{
@@ -19,6 +21,8 @@ export default function HeaderMenu(props: Readonly<{
 
     return <div className="header-menu">
         {!(GAME_MANAGER.getMySpectator() && !GAME_MANAGER.getMyHost()) && <FastForwardButton />}
+        {GAME_MANAGER.getMyHost() && <PauseButton />}
+        <GameSummaryButton />
         <Information />
         {!(GAME_MANAGER.getMySpectator() && !mobile) && <MenuButtons chatMenuNotification={props.chatMenuNotification}/>}
         <Timer />
@@ -73,6 +77,10 @@ function Information(): ReactElement {
         clientState => clientState.roleState,
         ["yourRoleState"]
     )
+    const modifiers = usePlayerState(
+        clientState => clientState.modifiers,
+        ["yourRoleState"]
+    )
     const myName = useMemo(() => {
         return myIndex === undefined ? undefined : players[myIndex]?.toString()
     }, [myIndex, players])
@@ -86,7 +94,7 @@ function Information(): ReactElement {
             </h3>
             {GAME_MANAGER.getMySpectator() 
                 || <StyledText>
-                    {myName + " (" + translate("role."+(roleState!.type)+".name") + ")"}
+                    {myName + " (" + [...(modifiers ?? []).map(translateRoleModifier), translate("role."+(roleState!.type)+".name")].join(" ") + ")"}
                 </StyledText>
             }
         </div>
@@ -203,6 +211,38 @@ function MenuButtons(props: Readonly<{ chatMenuNotification: boolean }>): ReactE
     </div>
 }
 
+function PauseButton(): ReactElement {
+    const paused = useGameState(
+        gameState => gameState.paused,
+        ["gamePaused"]
+    )!
+
+    return <Button 
+        onClick={()=>GAME_MANAGER.sendSetPausedPacket(!paused)}
+        className="pause-button"
+        highlighted={paused}
+    >
+        <Icon>{paused ? "play_arrow" : "pause"}</Icon>
+    </Button>
+}
+
+function GameSummaryButton(): ReactElement | null {
+    const { setCoverCard } = useContext(AnchorControllerContext)!;
+    const hasSummary = useGameState(
+        gameState => gameState.gameSummary !== null,
+        ["gameSummary"]
+    )!
+
+    if (!hasSummary) return null;
+
+    return <Button 
+        onClick={()=>setCoverCard(<GameSummaryCoverCard/>)}
+        className="game-summary-button"
+    >
+        <Icon>summarize</Icon>
+    </Button>
+}
+
 export function FastForwardButton(): ReactElement {
     const fastForward = useGameState(
         gameState => gameState.fastForward,
@@ -10,6 +10,7 @@ import Icon from "../../../components/Icon";
 import { EnabledRolesDisplay } from "../../../components/gameModeSettings/EnabledRoleSelector";
 import { useGameState, usePlayerState } from "../../../components/useHooks";
 import { translateRoleOutline } from "../../../game/roleListState.d";
+import RoleOptionsSelector from "../../../components/gameModeSettings/RoleOptionsSelector";
 
 export default function GraveyardMenu(): ReactElement {
     const graves = useGameState(
@@ -32,6 +33,7 @@ export default function GraveyardMenu(): ReactElement {
             />
         }
         <ExcludedRoles />
+        <ChangedRoleOptions />
     </div>
 }
 
@@ -164,6 +166,22 @@ function GraveExtended(props: Readonly<{
     </div>;
 }
 
+function ChangedRoleOptions(): ReactElement | null {
+    const roleOptions = useGameState(
+        gameState => gameState.roleOptions,
+        ["roleOptions"]
+    )!
+
+    if (Object.keys(roleOptions).length === 0) return null;
+
+    return <details className="graveyard-menu-excludedRoles">
+        <summary>
+            {translate("menu.lobby.roleOptions")}
+        </summary>
+        <RoleOptionsSelector disabled={true} roleOptions={roleOptions} onChange={() => {}}/>
+    </details>
+}
+
 function ExcludedRoles(): ReactElement {
     const enabledRoles = useGameState(
         gameState => gameState.enabledRoles,
@@ -8,7 +8,7 @@ import { usePlayerState } from "../../../../components/useHooks";
 import Icon from "../../../../components/Icon";
 import { Button } from "../../../../components/Button";
 import ROLES from "../../../../resources/roles.json";
-import Counter from "../../../../components/Counter";
+import AbilityChargesCounter from "../../../../components/AbilityChargesCounter";
 
 export default function CounterfeiterMenu (props: {}): ReactElement {
     
@@ -17,8 +17,8 @@ export default function CounterfeiterMenu (props: {}): ReactElement {
         ["yourRoleState"]
     )!;
     const forgesRemaining = usePlayerState<number>(
-        playerState => playerState.roleState.type === "counterfeiter" ? playerState.roleState.forgesRemaining : 0,
-        ["yourRoleState"]
+        playerState => playerState.abilityCharges.forge?.remaining ?? 0,
+        ["yourAbilityCharges"]
     )!;
     const savedRole = usePlayerState<Role>(
         playerState => playerState.roleState.type === "counterfeiter" ? playerState.roleState.fakeRole : "jester",
@@ -119,7 +119,7 @@ export default function CounterfeiterMenu (props: {}): ReactElement {
                     <option value={"forge"} key={"forge"}>{translate("forge")}</option>
                 }
             </select>
-            <Counter max={3} current={forgesRemaining}>{translate("role.forger.menu.forgesRemaining", forgesRemaining)}</Counter>
+            <AbilityChargesCounter ability="forge"/>
         </div>
     </div>;
 }
\ No newline at end of file
@@ -6,6 +6,7 @@ import ROLES from "../../../../resources/roles.json";
 import "./largeForgerMenu.css"
 import { Button } from "../../../../components/Button";
 import Icon from "../../../../components/Icon";
+import AbilityChargesCounter from "../../../../components/AbilityChargesCounter";
 
 type LargeForgerMenuProps = {
 }
@@ -14,7 +15,6 @@ type LargeForgerMenuState = {
     savedRole: Role,
     localWill: string,
     savedWill: string,
-    forgesRemaining: number,
 }
 export default class LargeForgerMenu extends React.Component<LargeForgerMenuProps, LargeForgerMenuState> {
     listener: () => void;
@@ -31,7 +31,6 @@ export default class LargeForgerMenu extends React.Component<LargeForgerMenuProp
                 savedRole: GAME_MANAGER.state.clientState.roleState?.fakeRole,
                 localWill: GAME_MANAGER.state.clientState.roleState?.fakeWill,
                 savedWill: GAME_MANAGER.state.clientState.roleState?.fakeWill,
-                forgesRemaining: GAME_MANAGER.state.clientState.roleState?.forgesRemaining,
             };
         this.listener = ()=>{
             if(
@@ -42,7 +41,6 @@ export default class LargeForgerMenu extends React.Component<LargeForgerMenuProp
                 this.setState({
                     savedWill: GAME_MANAGER.state.clientState.roleState.fakeWill,
                     savedRole: GAME_MANAGER.state.clientState.roleState.fakeRole,
-                    forgesRemaining: GAME_MANAGER.state.clientState.roleState.forgesRemaining,
                 });
             }
         };  
@@ -127,9 +125,7 @@ export default class LargeForgerMenu extends React.Component<LargeForgerMenuProp
                     }
                 }}>
             </textarea>
-            <div>
-                {translate("role.forger.menu.forgesRemaining", this.state.forgesRemaining ?? 0)}
-            </div>
+            <AbilityChargesCounter ability="forge"/>
         </div>
     }
 }
\ No newline at end of file
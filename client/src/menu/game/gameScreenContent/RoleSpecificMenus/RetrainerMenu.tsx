@@ -7,7 +7,7 @@ import { Role } from "../../../../game/roleState.d";
 import ROLES from "../../../../resources/roles.json";
 import GAME_MANAGER from "../../../..";
 import { Button } from "../../../../components/Button";
-import Counter from "../../../../components/Counter";
+import AbilityChargesCounter from "../../../../components/AbilityChargesCounter";
 import { useGameState, usePlayerState } from "../../../../components/useHooks";
 import { PhaseType } from "../../../../game/gameState.d";
 
@@ -35,14 +35,8 @@ export default function RetrainerMenu(): ReactElement {
     )
 
     const retrainsRemaining = usePlayerState<number>(
-        (playerState, gameState)=>{
-            if(playerState.roleState.type==="retrainer"){
-                return playerState.roleState.retrainsRemaining;
-            }else{
-                return 0;
-            }
-        },
-        ["yourRoleState"]
+        playerState => playerState.abilityCharges.retrain?.remaining ?? 0,
+        ["yourAbilityCharges"]
     );
 
     const allChoosableMafia : Role[] = Object.keys(ROLES).filter((rle)=>
@@ -74,6 +68,6 @@ export default function RetrainerMenu(): ReactElement {
             />
         </>}
         {!canRetrain && <StyledText>{translate("role.retrainer.cannotRetrain")}</StyledText>}  
-        <Counter max={2} current={retrainsRemaining??0}>{translate("role.retrainer.retrainsRemaining", retrainsRemaining??0)}</Counter>
+        <AbilityChargesCounter ability="retrain"/>
     </>
 }
\ No newline at end of file
@@ -6,11 +6,17 @@ import translate from "../../game/lang";
 import { StateListener } from "../../game/gameManager.d";
 import { AnchorControllerContext, MobileContext } from "../Anchor";
 import { RoomLinkButton } from "../GlobalMenu";
-import { RoleList, getAllRoles } from "../../game/roleListState.d";
+import { RoleList, getAllRoles, getCustomRoleSetsFromRoleList } from "../../game/roleListState.d";
 import LoadingScreen from "../LoadingScreen";
 import StartMenu from "../main/StartMenu";
 import { GameModeContext } from "../../components/gameModeSettings/GameModesEditor";
 import PhaseTimesSelector from "../../components/gameModeSettings/PhaseTimeSelector";
+import GameRulesSelector from "../../components/gameModeSettings/GameRulesSelector";
+import CustomRoleSetsSelector from "../../components/gameModeSettings/CustomRoleSetsSelector";
+import RoleListConstraintsSelector from "../../components/gameModeSettings/RoleListConstraintsSelector";
+import RoleOptionsSelector from "../../components/gameModeSettings/RoleOptionsSelector";
+import PresetSelector from "../../components/gameModeSettings/PresetSelector";
+import RoleListAnalysisDisplay from "../../components/gameModeSettings/RoleListAnalysisDisplay";
 import { OutlineListSelector } from "../../components/gameModeSettings/OutlineSelector";
 import EnabledRoleSelector from "../../components/gameModeSettings/EnabledRoleSelector";
 import Icon from "../../components/Icon";
@@ -75,6 +81,34 @@ function LobbyMenuSettings(props: Readonly<{
         lobbyState => lobbyState.phaseTimes,
         ["phaseTimes"]
     )!;
+    const gameRules = useLobbyState(
+        lobbyState => lobbyState.gameRules,
+        ["gameRules"]
+    )!;
+    const customRoleSets = useLobbyState(
+        lobbyState => lobbyState.customRoleSets,
+        ["customRoleSets"]
+    )!;
+    const roleListConstraints = useLobbyState(
+        lobbyState => lobbyState.roleListConstraints,
+        ["roleListConstraints"]
+    )!;
+    const roleOptions = useLobbyState(
+        lobbyState => lobbyState.roleOptions,
+        ["roleOptions"]
+    )!;
+    const presets = useLobbyState(
+        lobbyState => lobbyState.presets,
+        ["presets"]
+    )!;
+    const presetCode = useLobbyState(
+        lobbyState => lobbyState.presetCode,
+        ["presetCode"]
+    ) ?? null;
+    const roleListAnalysis = useLobbyState(
+        lobbyState => lobbyState.roleListAnalysis,
+        ["roleListAnalysis"]
+    ) ?? null;
 
     const mobile = useContext(MobileContext)!;
     const { setContent: setAnchorContent } = useContext(AnchorControllerContext)!;
@@ -102,8 +136,8 @@ function LobbyMenuSettings(props: Readonly<{
     };
 
     const context = useMemo(() => {
-        return {roleList, enabledRoles, phaseTimes};
-    }, [enabledRoles, phaseTimes, roleList]);
+        return {roleList, enabledRoles, phaseTimes, customRoleSets};
+    }, [enabledRoles, phaseTimes, roleList, customRoleSets]);
 
     return <GameModeContext.Provider value={context}>
         {mobile && <h1>{translate("menu.lobby.settings")}</h1>}
@@ -112,13 +146,38 @@ function LobbyMenuSettings(props: Readonly<{
             loadGameMode={gameMode => {
                 GAME_MANAGER.sendSetPhaseTimesPacket(gameMode.phaseTimes);
                 GAME_MANAGER.sendEnabledRolesPacket(gameMode.enabledRoles);
+                // The game mode's custom role sets replace any with the same name, so its outlines keep their roles
+                const gameModeSets = getCustomRoleSetsFromRoleList(gameMode.roleList);
+                GAME_MANAGER.sendSetCustomRoleSetsPacket([
+                    ...customRoleSets.filter(set => !gameModeSets.some(other => other.name === set.name)),
+                    ...gameModeSets
+                ]);
                 GAME_MANAGER.sendSetRoleListPacket(gameMode.roleList);
             }}
         />}
+        <PresetSelector
+            disabled={!props.isHost}
+            presets={presets}
+            presetCode={presetCode}
+            onLoad={name => GAME_MANAGER.sendLoadPresetPacket(name)}
+            onImport={code => GAME_MANAGER.sendImportPresetPacket(code)}
+            onExport={name => GAME_MANAGER.sendExportPresetPacket(name)}
+        />
         <PhaseTimesSelector 
             disabled={!props.isHost}
             onChange={pts => GAME_MANAGER.sendSetPhaseTimesPacket(pts)}
         />
+        <AutoPauseSelector disabled={!props.isHost}/>
+        <GameRulesSelector
+            disabled={!props.isHost}
+            gameRules={gameRules}
+            onChange={rules => GAME_MANAGER.sendSetGameRulesPacket(rules)}
+        />
+        <CustomRoleSetsSelector
+            disabled={!props.isHost}
+            customRoleSets={customRoleSets}
+            onChange={sets => GAME_MANAGER.sendSetCustomRoleSetsPacket(sets)}
+        />
         <OutlineListSelector
             disabled={!props.isHost}
             onChangeRolePicker={(value, index) => GAME_MANAGER.sendSetRoleOutlinePacket(index, value)}
@@ -126,6 +185,20 @@ function LobbyMenuSettings(props: Readonly<{
             onRemoveOutline={undefined}
             setRoleList={sendRoleList}
         />
+        <RoleListConstraintsSelector
+            disabled={!props.isHost}
+            roleListConstraints={roleListConstraints}
+            onChange={constraints => GAME_MANAGER.sendSetRoleListConstraintsPacket(constraints)}
+        />
+        <RoleOptionsSelector
+            disabled={!props.isHost}
+            roleOptions={roleOptions}
+            onChange={options => GAME_MANAGER.sendSetRoleOptionsPacket(options)}
+        />
+        {props.isHost && <RoleListAnalysisDisplay
+            analysis={roleListAnalysis}
+            onAnalyze={() => GAME_MANAGER.sendAnalyzeRoleListPacket()}
+        />}
         <EnabledRoleSelector
             onEnableRoles={roles => GAME_MANAGER.sendEnabledRolesPacket([...enabledRoles, ...roles])}
             onDisableRoles={roles => GAME_MANAGER.sendEnabledRolesPacket(enabledRoles.filter(role => !roles.includes(role)))}
@@ -135,6 +208,26 @@ function LobbyMenuSettings(props: Readonly<{
     </GameModeContext.Provider>
 }
 
+function AutoPauseSelector(props: Readonly<{
+    disabled: boolean,
+}>): JSX.Element {
+    const autoPause = useLobbyState(
+        lobbyState => lobbyState.autoPause,
+        ["autoPause"]
+    )!;
+
+    return <section className="will-menu-colors selector-section">
+        <h2>{translate("menu.lobby.autoPause")}</h2>
+        <Button
+            disabled={props.disabled}
+            highlighted={autoPause}
+            onClick={() => GAME_MANAGER.sendSetAutoPausePacket(!autoPause)}
+        >
+            <Icon>{autoPause ? "check" : "close"}</Icon> {translate("menu.lobby.autoPause.description")}
+        </Button>
+    </section>
+}
+
 // There's probably a better way to do this that doesn't need the mobile check.
 function LobbyMenuHeader(props: Readonly<{
     advancedView: boolean,
@@ -30,6 +30,7 @@ export default function LobbyPlayerList(): ReactElement {
                                 <div>
                                     {player.connection === "couldReconnect" && <Icon>signal_cellular_connected_no_internet_4_bar</Icon>}
                                     {player.host && <Icon>shield</Icon>}
+                                    {player.connection === "bot" && <Icon>smart_toy</Icon>}
                                     {(player.clientType as PlayerClientType).name}
                                 </div>
                                 {host && <button 
@@ -39,6 +40,9 @@ export default function LobbyPlayerList(): ReactElement {
                         )
                     }
                 </ol>
+                {host && <button onClick={() => GAME_MANAGER.sendAddBotPacket()}>
+                    <Icon>person_add</Icon> {translate("menu.lobby.addBot")}
+                </button>}
             </div>
         </section>
     </>
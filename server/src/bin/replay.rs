@@ -0,0 +1,53 @@
+use mafia_server::game::replay::{Replay, REPLAY_DIRECTORY};
+use std::{env, fs, path::PathBuf, process::ExitCode};
+
+/// Re-simulates every replay it is given and checks that each one still plays out the same way.
+/// Arguments can be replay files or directories of them. With no arguments, the replay directory is used.
+///
+/// # Examples
+/// ```sh
+/// cargo run --bin replay -- ./replays
+/// ```
+fn main() -> ExitCode {
+    let mut arguments: Vec<PathBuf> = env::args().skip(1).map(PathBuf::from).collect();
+    if arguments.is_empty() {
+        arguments.push(PathBuf::from(REPLAY_DIRECTORY));
+    }
+
+    let mut paths = Vec::new();
+    for argument in arguments {
+        if argument.is_dir() {
+            match fs::read_dir(&argument) {
+                Ok(entries) => paths.extend(
+                    entries.filter_map(|entry| entry.ok())
+                        .map(|entry| entry.path())
+                        .filter(|path| path.extension().is_some_and(|extension| extension == "json"))
+                ),
+                Err(err) => println!("Failed to read {}: {}", argument.display(), err),
+            }
+        } else {
+            paths.push(argument);
+        }
+    }
+    paths.sort();
+
+    let mut failed = 0;
+    for path in paths.iter() {
+        let result = match Replay::load(path) {
+            Ok(replay) => replay.verify().map_err(|mismatch| format!("{:?}", mismatch)),
+            Err(err) => Err(err.to_string()),
+        };
+
+        match result {
+            Ok(()) => println!("ok      {}", path.display()),
+            Err(err) => {
+                failed += 1;
+                println!("FAILED  {}: {}", path.display(), err);
+            }
+        }
+    }
+
+    println!("{} replays, {} passed, {} failed", paths.len(), paths.len() - failed, failed);
+
+    if failed == 0 { ExitCode::SUCCESS } else { ExitCode::FAILURE }
+}
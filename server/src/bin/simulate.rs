@@ -0,0 +1,60 @@
+use mafia_server::game::{
+    preset::{Preset, SERVER_PRESETS},
+    resolution_state::ResolutionState,
+    simulation::Simulation
+};
+use rand::{rngs::StdRng, SeedableRng};
+use std::{env, fs, panic, process::ExitCode};
+
+/// Plays many games with bots in every seat and prints how often each faction wins.
+/// Arguments are the number of games, a preset file (the first server preset if left out), and a seed.
+///
+/// # Examples
+/// ```sh
+/// cargo run --release --bin simulate -- 10000 ./resources/presets/classic.json 1
+/// ```
+fn main() -> ExitCode {
+    let arguments: Vec<String> = env::args().skip(1).collect();
+
+    let games = match arguments.first().map(|games| games.parse::<u32>()) {
+        None => 1000,
+        Some(Ok(games)) => games,
+        Some(Err(err)) => {
+            println!("Invalid number of games: {}", err);
+            return ExitCode::FAILURE;
+        }
+    };
+    let preset = match arguments.get(1) {
+        Some(path) => fs::read_to_string(path)
+            .map_err(|err| err.to_string())
+            .and_then(|json| Preset::from_json(&json).map_err(|err| format!("{:?}", err))),
+        None => SERVER_PRESETS.first().cloned().ok_or("There are no server presets".to_string()),
+    };
+    let preset = match preset {
+        Ok(preset) => preset,
+        Err(err) => {
+            println!("Failed to read preset: {}", err);
+            return ExitCode::FAILURE;
+        }
+    };
+    let seed = arguments.get(2).and_then(|seed| seed.parse().ok()).unwrap_or_else(rand::random);
+
+    // Panics are reported with the rest of the results
+    panic::set_hook(Box::new(|_| {}));
+
+    println!("Simulating {} games of {} with seed {}", games, preset.name, seed);
+    let report = Simulation::new(preset.settings).run(games, &mut StdRng::seed_from_u64(seed));
+
+    for resolution in ResolutionState::all() {
+        println!("{:<12}{:>6.2}%", format!("{:?}", resolution), report.win_rate(&resolution) * 100.0);
+    }
+    println!("Average length: {:.2} days", report.average_days());
+    println!("Reached the last day: {}", report.reached_max_day);
+
+    for failure in report.failures.iter() {
+        println!("FAILED  seed {}: {:?}", failure.seed, failure.result);
+    }
+    println!("{} games, {} ended, {} failed", report.games, report.ended_games(), report.failures.len());
+
+    if report.failures.is_empty() { ExitCode::SUCCESS } else { ExitCode::FAILURE }
+}
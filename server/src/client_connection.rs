@@ -2,13 +2,15 @@ use std::time::Duration;
 
 use serde::Serialize;
 
-use crate::{packet::ToClientPacket, websocket_connections::connection::ClientSender};
+use crate::{game::bot::Bot, packet::ToClientPacket, websocket_connections::connection::ClientSender};
 
 #[derive(Clone, Debug)]
 pub enum ClientConnection {
     Connected(ClientSender),
     CouldReconnect { disconnect_timer: Duration },
-    Disconnected
+    Disconnected,
+    /// Played by the server. Nothing is sent to bots, they read the game directly
+    Bot(Bot),
 }
 impl ClientConnection {
     pub fn send_packet(&self, packet: ToClientPacket)->bool {
@@ -28,6 +30,7 @@ impl Serialize for ClientConnection{
             ClientConnection::Connected(_) => serializer.serialize_str("connected"),
             ClientConnection::CouldReconnect { .. } => {serializer.serialize_str("couldReconnect")}
             ClientConnection::Disconnected => serializer.serialize_str("disconnected"),
+            ClientConnection::Bot(_) => serializer.serialize_str("bot"),
         }
     }
 }
\ No newline at end of file
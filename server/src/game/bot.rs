@@ -0,0 +1,103 @@
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+
+use crate::{client_connection::ClientConnection, packet::ToServerPacket};
+
+use super::{available_buttons::AvailableButtons, phase::PhaseType, player::{PlayerIndex, PlayerReference}, verdict::Verdict, Game};
+
+/// A player the server plays for, so games can be filled when there aren't enough people.
+/// Bots send the same packets a client would, and only pick choices the game shows as available.
+/// They have their own rng, so the game plays out the same when it's replayed without them.
+#[derive(Clone, Debug)]
+pub struct Bot {
+    /// Boxed so bots don't make every `ClientConnection` bigger
+    rng: Box<StdRng>,
+}
+
+impl Bot {
+    /// The most targets a bot picks in one night, for roles that can pick more than one
+    const MAX_TARGETS: usize = 3;
+    /// How likely a bot is to vote during nomination, so not every day has a trial
+    const VOTE_CHANCE: f64 = 0.5;
+    /// How likely a bot is to use its day ability, if it has one
+    const DAY_TARGET_CHANCE: f64 = 0.3;
+
+    pub fn new(seed: u64) -> Self {
+        Self { rng: Box::new(StdRng::seed_from_u64(seed)) }
+    }
+    /// The bot that plays this player in a game with this seed, so the game's seed is all it takes to play it again
+    pub fn for_player(game_seed: u64, player_index: PlayerIndex) -> Self {
+        let mut seeds = StdRng::seed_from_u64(game_seed);
+        let seed = (0..=player_index).map(|_| seeds.gen()).last().unwrap_or_default();
+        Self::new(seed)
+    }
+
+    pub fn on_game_start(game: &mut Game) {
+        for player_ref in PlayerReference::all_players(game) {
+            if !player_ref.is_bot(game) {continue}
+            let bot = Self::for_player(game.seed, player_ref.index());
+            player_ref.set_bot(game, bot);
+
+            let will = format!("I am the {:?}", player_ref.role(game));
+            game.on_client_message(player_ref.index(), ToServerPacket::SaveWill { will });
+        }
+    }
+
+    pub fn on_phase_start(game: &mut Game, phase: PhaseType) {
+        for player_ref in PlayerReference::all_players(game) {
+            let ClientConnection::Bot(mut bot) = player_ref.connection(game).clone() else {continue};
+            if !player_ref.alive(game) {continue}
+
+            match phase {
+                PhaseType::Discussion => bot.day_target(game, player_ref),
+                PhaseType::Nomination => bot.vote(game, player_ref),
+                PhaseType::Judgement => bot.judge(game, player_ref),
+                PhaseType::Night => bot.target(game, player_ref),
+                _ => {}
+            }
+
+            player_ref.set_bot(game, bot);
+        }
+    }
+
+    fn choose_player(&mut self, game: &Game, actor_ref: PlayerReference, available: impl Fn(&AvailableButtons) -> bool) -> Option<PlayerReference> {
+        let choices: Vec<PlayerReference> = PlayerReference::all_players(game)
+            .zip(AvailableButtons::from_player(game, actor_ref))
+            .filter(|(_, buttons)| available(buttons))
+            .map(|(player_ref, _)| player_ref)
+            .collect();
+
+        choices.choose(&mut self.rng).copied()
+    }
+
+    fn day_target(&mut self, game: &mut Game, actor_ref: PlayerReference) {
+        if !self.rng.gen_bool(Self::DAY_TARGET_CHANCE) {return}
+        let Some(target_ref) = self.choose_player(game, actor_ref, |buttons| buttons.day_target) else {return};
+
+        game.on_client_message(actor_ref.index(), ToServerPacket::DayTarget { player_index: target_ref.index() });
+    }
+
+    fn vote(&mut self, game: &mut Game, actor_ref: PlayerReference) {
+        if !self.rng.gen_bool(Self::VOTE_CHANCE) {return}
+        let Some(target_ref) = self.choose_player(game, actor_ref, |buttons| buttons.vote) else {return};
+
+        game.on_client_message(actor_ref.index(), ToServerPacket::Vote { player_index: Some(target_ref.index()) });
+    }
+
+    fn judge(&mut self, game: &mut Game, actor_ref: PlayerReference) {
+        let Some(verdict) = [Verdict::Innocent, Verdict::Guilty, Verdict::Abstain].choose(&mut self.rng).copied() else {return};
+
+        game.on_client_message(actor_ref.index(), ToServerPacket::Judgement { verdict });
+    }
+
+    /// Picks targets one at a time, since what a role can select depends on what it already selected
+    fn target(&mut self, game: &mut Game, actor_ref: PlayerReference) {
+        let mut targets = Vec::new();
+
+        while targets.len() < Self::MAX_TARGETS {
+            let Some(target_ref) = self.choose_player(game, actor_ref, |buttons| buttons.target) else {break};
+            targets.push(target_ref.index());
+
+            game.on_client_message(actor_ref.index(), ToServerPacket::Target { player_index_list: targets.clone() });
+        }
+    }
+}
@@ -10,6 +10,9 @@ pub enum ChatGroup {
 
     Mafia,
     Cult,
+    Fiends,
+    Lovers,
+    Puppeteer,
 
     Jail,
     Interview
@@ -1,9 +1,9 @@
 use serde::{Deserialize, Serialize};
 
 use crate::game::{
-    grave::Grave, phase::PhaseState, player::{PlayerIndex, PlayerReference}, role::{
+    grave::Grave, phase::{PauseReason, PhaseState}, player::{PlayerIndex, PlayerReference}, role::{
         auditor::AuditorResult, engineer::TrapState, eros::ErosAction, kira::KiraResult, ojo::OjoAction, puppeteer::PuppeteerAction, spy::SpyBug, Role
-    }, role_list::RoleOutline, tag::Tag, verdict::Verdict
+    }, role_list::RoleOutline, settings::TrialSystem, tag::Tag, verdict::Verdict
 };
 
 
@@ -18,7 +18,7 @@ pub enum MessageSender {
 }
 
 // Determines message color
-#[derive(PartialOrd, Ord, Clone, Debug, Serialize, PartialEq, Eq)]
+#[derive(PartialOrd, Ord, Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 #[serde(tag = "type")]
 pub enum ChatMessageVariant {
@@ -70,7 +70,13 @@ pub enum ChatMessageVariant {
     #[serde(rename_all = "camelCase")]
     TrialInformation{
         required_votes: u8, 
-        trials_left: u8
+        trials_left: u8,
+        trial_system: TrialSystem
+    },
+    /// Nobody was put on trial because these players tied for the most votes
+    #[serde(rename_all = "camelCase")]
+    NominationTied{
+        player_indices: Vec<PlayerIndex>
     },
 
     #[serde(rename_all = "camelCase")]
@@ -104,6 +110,8 @@ pub enum ChatMessageVariant {
 
     #[serde(rename_all = "camelCase")]
     PhaseFastForwarded,
+    GamePaused{reason: PauseReason},
+    GameResumed,
 
     /* Role-specific */
     #[serde(rename_all = "camelCase")]
@@ -1,7 +1,9 @@
 pub mod chat_group;
 pub mod chat_message;
 pub mod chat_message_variant;
+pub mod team_chat;
 
 pub use chat_group::*;
 pub use chat_message::*;
-pub use chat_message_variant::*;
\ No newline at end of file
+pub use chat_message_variant::*;
+pub use team_chat::*;
\ No newline at end of file
@@ -0,0 +1,68 @@
+use std::collections::BTreeSet;
+
+use serde::{Deserialize, Serialize};
+
+use crate::game::{components::puppeteer_marionette::PuppeteerMarionette, player::PlayerReference, role_list::Faction, Game};
+
+use super::ChatGroup;
+
+/// A team whose members can talk to each other at night.
+/// Targets, jailings and interviews are only announced in the faction chats, not to lovers or puppeteers
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(rename_all = "camelCase")]
+pub enum TeamChat {
+    Mafia,
+    Cult,
+    Fiends,
+    /// The puppeteers and their marionettes
+    Puppeteer,
+    /// Each player only talks with the players they're love linked to
+    Lovers,
+}
+impl TeamChat {
+    pub fn chat_group(&self) -> ChatGroup {
+        match self {
+            Self::Mafia => ChatGroup::Mafia,
+            Self::Cult => ChatGroup::Cult,
+            Self::Fiends => ChatGroup::Fiends,
+            Self::Lovers => ChatGroup::Lovers,
+            Self::Puppeteer => ChatGroup::Puppeteer,
+        }
+    }
+    pub fn from_chat_group(chat_group: &ChatGroup) -> Option<Self> {
+        match chat_group {
+            ChatGroup::Mafia => Some(Self::Mafia),
+            ChatGroup::Cult => Some(Self::Cult),
+            ChatGroup::Fiends => Some(Self::Fiends),
+            ChatGroup::Lovers => Some(Self::Lovers),
+            ChatGroup::Puppeteer => Some(Self::Puppeteer),
+            _ => None,
+        }
+    }
+    pub fn is_member(&self, game: &Game, player_ref: PlayerReference) -> bool {
+        match self {
+            Self::Mafia => player_ref.role(game).faction() == Faction::Mafia,
+            Self::Cult => player_ref.role(game).faction() == Faction::Cult,
+            Self::Fiends => player_ref.role(game).faction() == Faction::Fiends,
+            Self::Lovers => !game.love_linked().get_links(player_ref).is_empty(),
+            Self::Puppeteer => PuppeteerMarionette::marionettes_and_puppeteer(game).contains(&player_ref),
+        }
+    }
+    /// The chat groups of every team chat the host turned on that this player is a member of
+    pub fn team_chat_groups(game: &Game, player_ref: PlayerReference) -> Vec<ChatGroup> {
+        game.settings.rules.team_chats.0.iter()
+            .filter(|team_chat| team_chat.is_member(game, player_ref))
+            .map(|team_chat| team_chat.chat_group())
+            .collect()
+    }
+}
+
+/// The team chats the host turned on. Mafia and cult have their chats unless the host turns them off
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(transparent)]
+pub struct TeamChats(pub BTreeSet<TeamChat>);
+impl Default for TeamChats {
+    fn default() -> Self {
+        Self(vec![TeamChat::Mafia, TeamChat::Cult].into_iter().collect())
+    }
+}
@@ -0,0 +1,84 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{game::{player::PlayerReference, role::Role, Game}, packet::ToClientPacket};
+
+impl Game {
+    pub fn ability_charges(&self)->&AbilityCharges{
+        &self.ability_charges
+    }
+    pub fn set_ability_charges(&mut self, ability_charges: AbilityCharges){
+        self.ability_charges = ability_charges;
+    }
+}
+
+/// An ability that can only be used a limited number of times in a game
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Ability {
+    Execute,
+    SelfHeal,
+    SelfShield,
+    OpenShop,
+    Alert,
+    Shoot,
+    Seance,
+    Retrain,
+    Forge,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Charges {
+    pub remaining: u8,
+    /// How many charges the player's role started with
+    pub total: u8,
+}
+
+/// How many more times each player can use their role's limited abilities.
+/// Charges belong to the role, so a player who switches roles gets the new role's charges
+#[derive(Default, Clone)]
+pub struct AbilityCharges {
+    players: HashMap<PlayerReference, HashMap<Ability, Charges>>,
+}
+impl AbilityCharges {
+    pub fn on_role_switch(game: &mut Game, player: PlayerReference, new: Role) {
+        let uses = game.settings.role_options.uses(new);
+        let charges = new.limited_abilities().iter()
+            .enumerate()
+            .map(|(index, (ability, default))| {
+                // The host's uses are for the role's main ability, which is listed first
+                let total = if index == 0 { uses.unwrap_or(*default) } else { *default };
+                (*ability, Charges { remaining: total, total })
+            })
+            .collect();
+
+        let mut ability_charges = game.ability_charges().clone();
+        ability_charges.players.insert(player, charges);
+        game.set_ability_charges(ability_charges);
+
+        Self::send_charges(game, player);
+    }
+
+    pub fn charges(&self, player: PlayerReference, ability: Ability) -> Option<Charges> {
+        self.players.get(&player).and_then(|charges| charges.get(&ability)).copied()
+    }
+    pub fn all_charges(&self, player: PlayerReference) -> HashMap<Ability, Charges> {
+        self.players.get(&player).cloned().unwrap_or_default()
+    }
+
+    /// Sets how many charges are left. Does nothing if the player's role doesn't have this ability
+    pub fn set_remaining(game: &mut Game, player: PlayerReference, ability: Ability, remaining: u8) {
+        let mut ability_charges = game.ability_charges().clone();
+        let Some(charges) = ability_charges.players.get_mut(&player).and_then(|charges| charges.get_mut(&ability)) else {return};
+        charges.remaining = remaining;
+        game.set_ability_charges(ability_charges);
+
+        Self::send_charges(game, player);
+    }
+
+    fn send_charges(game: &Game, player: PlayerReference) {
+        player.send_packet(game, ToClientPacket::YourAbilityCharges { charges: game.ability_charges().all_charges(player) });
+    }
+}
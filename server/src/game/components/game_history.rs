@@ -0,0 +1,170 @@
+use serde::Serialize;
+
+use crate::game::{
+    grave::Grave, phase::{PhaseState, PhaseType}, player::PlayerReference,
+    resolution_state::ResolutionState, role::Role, verdict::Verdict, visit::Visit, Game
+};
+
+/// Everything that happened in a game that players can't see while it's going on.
+/// Sent to everyone once the game ends, so they can review it.
+#[derive(Default, Clone)]
+pub struct GameHistory{
+    role_switches: Vec<(PlayerReference, RoleSwitchSummary)>,
+    nights: Vec<NightSummary>,
+    trials: Vec<TrialSummary>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GameSummary{
+    pub players: Vec<PlayerSummary>,
+    pub nights: Vec<NightSummary>,
+    pub trials: Vec<TrialSummary>,
+    pub graves: Vec<Grave>,
+    /// None if the game ended without a winner, like when it ran out of days
+    pub resolution: Option<ResolutionState>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayerSummary{
+    pub player: PlayerReference,
+    pub starting_role: Role,
+    pub final_role: Role,
+    pub role_switches: Vec<RoleSwitchSummary>,
+    pub alive: bool,
+    pub won: bool,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RoleSwitchSummary{
+    pub day_number: u8,
+    pub phase: PhaseType,
+    pub role: Role,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NightSummary{
+    pub day_number: u8,
+    pub actions: Vec<NightActionSummary>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NightActionSummary{
+    pub player: PlayerReference,
+    pub selection: Vec<PlayerReference>,
+    pub visits: Vec<Visit>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrialSummary{
+    pub day_number: u8,
+    pub player_on_trial: PlayerReference,
+    pub nominated_by: Vec<PlayerReference>,
+    /// Empty if the player was lynched without a judgement
+    pub verdicts: Vec<(PlayerReference, Verdict)>,
+    pub guilty: u8,
+    pub innocent: u8,
+}
+
+impl GameHistory{
+    pub fn on_role_switch(game: &mut Game, player: PlayerReference, old: Role, new: Role){
+        if old == new {return}
+
+        let role_switch = RoleSwitchSummary{
+            day_number: game.day_number(),
+            phase: game.current_phase().phase(),
+            role: new
+        };
+        game.game_history.role_switches.push((player, role_switch));
+    }
+    pub fn on_phase_start(game: &mut Game, phase: PhaseType){
+        match phase {
+            // Night variables aren't reset until the next night, so they still hold last night's actions
+            PhaseType::Obituary => {
+                let night = NightSummary{
+                    day_number: game.day_number() - 1,
+                    actions: PlayerReference::all_players(game)
+                        .filter(|player| !player.selection(game).is_empty() || !player.night_visits(game).is_empty())
+                        .map(|player| NightActionSummary{
+                            player,
+                            selection: player.selection(game).clone(),
+                            visits: player.night_visits(game).clone(),
+                        })
+                        .collect()
+                };
+                game.game_history.nights.push(night);
+            },
+            // Direct lynches skip judgement, so their trial is recorded once final words start
+            PhaseType::FinalWords => {
+                let &PhaseState::FinalWords { player_on_trial } = game.current_phase() else {return};
+                if game.game_history.trials.last().is_some_and(|trial|
+                    trial.day_number == game.day_number() && trial.player_on_trial == player_on_trial
+                ) {return}
+
+                Self::record_trial(game, player_on_trial, Vec::new());
+            },
+            _ => {}
+        }
+    }
+    pub fn before_phase_end(game: &mut Game, phase: PhaseType){
+        if phase != PhaseType::Judgement {return}
+        let &PhaseState::Judgement { player_on_trial, .. } = game.current_phase() else {return};
+
+        let verdicts = PlayerReference::all_players(game)
+            .filter(|player| player.alive(game) && *player != player_on_trial)
+            .map(|player| (player, player.verdict(game)))
+            .collect();
+        Self::record_trial(game, player_on_trial, verdicts);
+    }
+    fn record_trial(game: &mut Game, player_on_trial: PlayerReference, verdicts: Vec<(PlayerReference, Verdict)>){
+        let (guilty, innocent) = game.count_verdict_votes(player_on_trial);
+
+        let trial = TrialSummary{
+            day_number: game.day_number(),
+            player_on_trial,
+            nominated_by: PlayerReference::all_players(game)
+                .filter(|player| player.chosen_vote(game) == Some(player_on_trial))
+                .collect(),
+            verdicts,
+            guilty,
+            innocent,
+        };
+        game.game_history.trials.push(trial);
+    }
+
+    /// The role the player should have now, from their starting role and every role switch since
+    pub fn recorded_role(game: &Game, player: PlayerReference)->Option<Role>{
+        game.game_history.role_switches.iter()
+            .rev()
+            .find(|(p, _)| *p == player)
+            .map(|(_, role_switch)| role_switch.role)
+            .or_else(|| game.roles_to_players.iter().find(|(_, p)| *p == player).map(|(role, _)| *role))
+    }
+
+    pub fn summary(game: &Game)->GameSummary{
+        GameSummary{
+            players: PlayerReference::all_players(game).map(|player| PlayerSummary{
+                player,
+                starting_role: game.roles_to_players.iter()
+                    .find(|(_, p)| *p == player)
+                    .map_or(player.role(game), |(role, _)| *role),
+                final_role: player.role(game),
+                role_switches: game.game_history.role_switches.iter()
+                    .filter(|(p, _)| *p == player)
+                    .map(|(_, role_switch)| role_switch.clone())
+                    .collect(),
+                alive: player.alive(game),
+                won: player.get_won_game(game),
+            }).collect(),
+            nights: game.game_history.nights.clone(),
+            trials: game.game_history.trials.clone(),
+            graves: game.graves.clone(),
+            resolution: ResolutionState::game_is_over(game),
+        }
+    }
+}
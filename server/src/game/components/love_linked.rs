@@ -1,10 +1,10 @@
-use std::collections::HashSet;
+use std::collections::BTreeSet;
 
 use crate::game::{chat::ChatMessageVariant, grave::Grave, player::PlayerReference, tag::Tag, Game};
 
 #[derive(Default, Clone)]
 pub struct LoveLinked {
-    love_linked_players: HashSet<(PlayerReference, PlayerReference)>,
+    love_linked_players: BTreeSet<(PlayerReference, PlayerReference)>,
 }
 impl Game {
     pub fn love_linked(&self)->&LoveLinked{
@@ -41,7 +41,7 @@ impl LoveLinked {
         self.love_linked_players.contains(&(player1, player2)) || self.love_linked_players.contains(&(player2, player1))
     }
 
-    pub fn get_links(&self, player: PlayerReference) -> HashSet<PlayerReference> {
+    pub fn get_links(&self, player: PlayerReference) -> BTreeSet<PlayerReference> {
         self.love_linked_players
             .iter()
             .filter(|(p1, p2)| *p1 == player || *p2 == player)
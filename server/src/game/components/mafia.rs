@@ -1,6 +1,6 @@
 use rand::seq::SliceRandom;
 
-use crate::game::{phase::PhaseType, player::PlayerReference, role::{Role, RoleState}, role_list::{Faction, RoleSet}, Game};
+use crate::game::{phase::PhaseType, player::PlayerReference, role::{Role, RoleState}, role_list::{Faction, RoleSet}, tag::Tag, Game};
 
 
 const DEFAULT_MAFIA_KILLING_ROLE: Role = Role::Godfather;
@@ -19,7 +19,7 @@ impl Mafia{
     pub fn on_phase_start(_game: &mut Game, _phase: PhaseType){
     }
     pub fn on_game_start(game: &mut Game) {
-        Mafia::give_mafia_killing_role(game, DEFAULT_MAFIA_KILLING_ROLE.default_state());
+        Mafia::give_mafia_killing_role(game, game.settings.role_options.new_state(DEFAULT_MAFIA_KILLING_ROLE));
     }
 
 
@@ -29,6 +29,11 @@ impl Mafia{
         if RoleSet::MafiaKilling.get_roles().contains(&dead_player.role(game)) {
             Mafia::give_mafia_killing_role(game, dead_player.role_state(game).clone());
         }
+
+        // Whoever chose this backup might not have that role anymore, so nobody else would remove these
+        for player_ref in PlayerReference::all_players(game){
+            player_ref.remove_player_tag(game, dead_player, Tag::GodfatherBackup);
+        }
     }
     pub fn on_role_switch(game: &mut Game, old: RoleState, _new: RoleState) {
         if RoleSet::MafiaKilling.get_roles().contains(&old.role()) {
@@ -64,7 +69,7 @@ impl Mafia{
 
         //choose random mafia to be mafia killing
         let all_living_mafia = Mafia::get_living_members(game);
-        let random_mafia = all_living_mafia.choose(&mut rand::thread_rng());
+        let random_mafia = all_living_mafia.choose(&mut game.rng);
         
         if let Some(random_mafia) = random_mafia {
             random_mafia.set_role(game, role);
@@ -4,5 +4,8 @@ pub mod arsonist_doused;
 pub mod puppeteer_marionette;
 pub mod love_linked;
 pub mod verdicts_today;
+pub mod game_history;
+pub mod ability_charges;
+pub mod night_trace;
 
 
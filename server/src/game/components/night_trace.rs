@@ -0,0 +1,132 @@
+use serde::Serialize;
+
+use crate::game::{player::{PlayerNightState, PlayerReference}, role::{Priority, Role}, visit::Visit, Game};
+
+impl Game {
+    pub fn night_trace(&self)->&NightTrace{
+        &self.night_trace
+    }
+}
+
+/// A step by step record of how each night was resolved, so hosts can work out why a night ended the way it did.
+/// Only recorded when the host turns on GameRules::trace_night_actions
+#[derive(Default, Clone)]
+pub struct NightTrace{
+    nights: Vec<TracedNight>,
+    /// Visits at the start of the current priority, to find which ones changed during it
+    visits_before_priority: Vec<Vec<Visit>>,
+    resolving: bool,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TracedNight{
+    pub day_number: u8,
+    /// Everyone's visits before any night action ran
+    pub starting_visits: Vec<TracedVisits>,
+    pub priorities: Vec<TracedPriority>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TracedVisits{
+    pub player: PlayerReference,
+    pub visits: Vec<Visit>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TracedPriority{
+    pub priority: Priority,
+    pub events: Vec<TraceEvent>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum TraceEvent{
+    /// The actor's role changed something with its night action this priority
+    NightAction{actor: PlayerReference, role: Role},
+    #[serde(rename_all = "camelCase")]
+    Attack{attacker: Option<PlayerReference>, target: PlayerReference, attack: u8, defense: u8, succeeded: bool},
+    Roleblock{player: PlayerReference, immune: bool},
+    Wardblock{warder: PlayerReference, player: PlayerReference},
+    /// A player's visits were different at the end of this priority, like after a transport or a possession
+    VisitsChanged{player: PlayerReference, visits: Vec<Visit>},
+}
+
+impl NightTrace{
+    pub fn nights(&self)->&[TracedNight]{
+        &self.nights
+    }
+
+    pub fn start_night(game: &mut Game){
+        if !game.settings.rules.trace_night_actions {return}
+
+        let night = TracedNight{
+            day_number: game.day_number(),
+            starting_visits: PlayerReference::all_players(game)
+                .filter(|player| !player.night_visits(game).is_empty())
+                .map(|player| TracedVisits{player, visits: player.night_visits(game).clone()})
+                .collect(),
+            priorities: Vec::new(),
+        };
+        game.night_trace.nights.push(night);
+        game.night_trace.resolving = true;
+    }
+    pub fn start_priority(game: &mut Game, priority: Priority){
+        if !game.night_trace.resolving {return}
+
+        game.night_trace.visits_before_priority = PlayerReference::all_players(game)
+            .map(|player| player.night_visits(game).clone())
+            .collect();
+        if let Some(night) = game.night_trace.nights.last_mut() {
+            night.priorities.push(TracedPriority{priority, events: Vec::new()});
+        }
+    }
+    pub fn end_priority(game: &mut Game){
+        if !game.night_trace.resolving {return}
+
+        let changed: Vec<TraceEvent> = PlayerReference::all_players(game)
+            .zip(game.night_trace.visits_before_priority.iter())
+            .filter(|(player, before)| player.night_visits(game) != *before)
+            .map(|(player, _)| TraceEvent::VisitsChanged{player, visits: player.night_visits(game).clone()})
+            .collect();
+        for event in changed {
+            Self::push_event(game, event);
+        }
+    }
+    pub fn end_night(game: &mut Game){
+        game.night_trace.resolving = false;
+        game.night_trace.visits_before_priority.clear();
+    }
+
+    /// Everyone's state before a night action runs, or None if nothing is being traced
+    pub fn before_night_action(game: &Game)->Option<Vec<PlayerNightState>>{
+        if !game.night_trace.resolving {return None}
+        Some(PlayerReference::all_players(game).map(|player| player.night_state(game)).collect())
+    }
+    /// Records the night action only if it changed something, since every role is asked to act at every priority
+    pub fn after_night_action(game: &mut Game, actor: PlayerReference, before: Option<Vec<PlayerNightState>>){
+        let Some(before) = before else {return};
+        if PlayerReference::all_players(game).zip(before.iter()).all(|(player, before)| player.night_state(game) == *before) {return}
+
+        let role = actor.role(game);
+        Self::push_event(game, TraceEvent::NightAction{actor, role});
+    }
+    pub fn on_attack(game: &mut Game, attacker: Option<PlayerReference>, target: PlayerReference, attack: u8, defense: u8){
+        Self::push_event(game, TraceEvent::Attack{attacker, target, attack, defense, succeeded: attack > defense});
+    }
+    pub fn on_roleblock(game: &mut Game, player: PlayerReference, immune: bool){
+        Self::push_event(game, TraceEvent::Roleblock{player, immune});
+    }
+    pub fn on_wardblock(game: &mut Game, warder: PlayerReference, player: PlayerReference){
+        Self::push_event(game, TraceEvent::Wardblock{warder, player});
+    }
+
+    /// Does nothing outside of night resolution, or if the host didn't turn tracing on
+    fn push_event(game: &mut Game, event: TraceEvent){
+        if !game.night_trace.resolving {return}
+        let Some(priority) = game.night_trace.nights.last_mut().and_then(|night| night.priorities.last_mut()) else {return};
+        priority.events.push(event);
+    }
+}
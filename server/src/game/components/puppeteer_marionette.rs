@@ -1,4 +1,4 @@
-use std::collections::HashSet;
+use std::collections::BTreeSet;
 
 use crate::game::{
     chat::ChatMessageVariant, 
@@ -21,8 +21,8 @@ impl Game{
 
 #[derive(Default, Clone)]
 pub struct PuppeteerMarionette{
-    to_be_converted: HashSet<PlayerReference>,
-    poisoned: HashSet<PlayerReference>,
+    to_be_converted: BTreeSet<PlayerReference>,
+    poisoned: BTreeSet<PlayerReference>,
 }
 impl PuppeteerMarionette{
     pub fn string(game: &mut Game, player: PlayerReference){
@@ -70,7 +70,7 @@ impl PuppeteerMarionette{
 
         PuppeteerMarionette::attack_players(game, poisoned);
 
-        puppeteer_marionette.poisoned = HashSet::new();
+        puppeteer_marionette.poisoned = BTreeSet::new();
 
         game.set_puppeteer_marionette(puppeteer_marionette)
     }
@@ -115,20 +115,20 @@ impl PuppeteerMarionette{
     pub fn is_marionette(game: &Game, player: PlayerReference)->bool{
         game.puppeteer_marionette().to_be_converted.contains(&player)
     }
-    pub fn marionettes(game: &Game)->HashSet<PlayerReference>{
+    pub fn marionettes(game: &Game)->BTreeSet<PlayerReference>{
         PlayerReference::all_players(game)
             .filter(|p|
                 game.puppeteer_marionette().to_be_converted.contains(p)
             )
             .collect()
     }
-    pub fn puppeteers(game: &Game)->HashSet<PlayerReference>{
+    pub fn puppeteers(game: &Game)->BTreeSet<PlayerReference>{
         PlayerReference::all_players(game)
             .filter(|p|p.role(game)==Role::Puppeteer)
             .map(|p|p.clone())
             .collect()
     }
-    pub fn marionettes_and_puppeteer(game: &Game)->HashSet<PlayerReference>{
+    pub fn marionettes_and_puppeteer(game: &Game)->BTreeSet<PlayerReference>{
         let mut marionettes_and_puppeteer = PuppeteerMarionette::marionettes(game);
         marionettes_and_puppeteer.extend(PuppeteerMarionette::puppeteers(game));
         marionettes_and_puppeteer
@@ -1,11 +1,13 @@
-use crate::game::{components::{cult::Cult, mafia::Mafia, puppeteer_marionette::PuppeteerMarionette}, Game};
+use crate::game::{bot::Bot, components::{cult::Cult, mafia::Mafia, puppeteer_marionette::PuppeteerMarionette}, role_modifier::RoleModifier, Game};
 
 #[must_use = "Event must be invoked"]
 pub struct OnGameStart;
 impl OnGameStart{
     pub fn invoke(game: &mut Game){
+        RoleModifier::on_game_start(game);
         Mafia::on_game_start(game);
         Cult::on_game_start(game);
         PuppeteerMarionette::on_game_start(game);
+        Bot::on_game_start(game);
     }
 }
\ No newline at end of file
@@ -1,4 +1,4 @@
-use crate::game::{components::puppeteer_marionette::PuppeteerMarionette, role::Priority, Game};
+use crate::game::{components::puppeteer_marionette::PuppeteerMarionette, role::Priority, role_modifier::RoleModifier, Game};
 
 ///runs before all players' night actions
 #[must_use = "Event must be invoked"]
@@ -11,5 +11,6 @@ impl OnNightPriority{
     }
     pub fn invoke(self, game: &mut Game){
         PuppeteerMarionette::on_night_priority(game, self.priority);
+        RoleModifier::on_night_priority(game, self.priority);
     }
 }
\ No newline at end of file
@@ -1,4 +1,4 @@
-use crate::game::{components::{cult::Cult, mafia::Mafia, verdicts_today::VerdictsToday}, phase::PhaseType, player::PlayerReference, Game};
+use crate::game::{bot::Bot, components::{cult::Cult, game_history::GameHistory, mafia::Mafia, verdicts_today::VerdictsToday}, phase::PhaseType, player::PlayerReference, Game};
 
 #[must_use = "Event must be invoked"]
 pub struct OnPhaseStart{
@@ -16,7 +16,13 @@ impl OnPhaseStart{
         VerdictsToday::on_phase_start(game, self.phase);
         Mafia::on_phase_start(game, self.phase);
         Cult::on_phase_start(game, self.phase);
+        GameHistory::on_phase_start(game, self.phase);
 
         game.on_phase_start(self.phase);
+
+        // Bots act last, after everything else in the phase has started
+        Bot::on_phase_start(game, self.phase);
+
+        game.report_invariant_violations(self.phase);
     }
 }
\ No newline at end of file
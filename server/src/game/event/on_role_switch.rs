@@ -1,5 +1,5 @@
 use crate::game::{
-    components::{arsonist_doused::ArsonistDoused, cult::Cult, mafia::Mafia},
+    components::{ability_charges::AbilityCharges, arsonist_doused::ArsonistDoused, cult::Cult, game_history::GameHistory, mafia::Mafia},
     player::PlayerReference, 
     role::RoleState, 
     Game
@@ -17,8 +17,11 @@ impl OnRoleSwitch{
     }
     pub fn invoke(self, game: &mut Game){
 
+        AbilityCharges::on_role_switch(game, self.player, self.new.role());
         game.on_role_switch(self.player, self.old.role(), self.new.role());
 
+        // Recorded before the cult reacts, since that can switch this player's role again
+        GameHistory::on_role_switch(game, self.player, self.old.role(), self.new.role());
         Cult::on_role_switch(game, self.old.role(), self.new.role());
         Mafia::on_role_switch(game, self.old, self.new);
 
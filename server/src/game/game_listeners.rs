@@ -1,7 +1,7 @@
 use crate::packet::ToClientPacket;
 
 use super::{
-    chat::{ChatGroup, ChatMessageVariant}, grave::GraveReference, phase::PhaseType, player::PlayerReference, role::Role, Game, GameOverReason
+    chat::{ChatGroup, ChatMessageVariant}, components::game_history::GameHistory, grave::GraveReference, phase::PhaseType, player::PlayerReference, replay::GameLogEntry, role::Role, Game, GameOverReason
 };
 
 //Event listerner functions for game defined here
@@ -11,7 +11,7 @@ impl Game{
             phase: self.current_phase().clone(),
             day_number: self.phase_machine.day_number,
         });
-        self.send_packet_to_all(ToClientPacket::PhaseTimeLeft{ seconds_left: self.phase_machine.time_remaining.as_secs() });
+        self.send_packet_to_all(ToClientPacket::PhaseTimeLeft{ deadline: self.phase_deadline() });
         for player in PlayerReference::all_players(self){
             player.send_packet(self, ToClientPacket::YourSendChatGroups { send_chat_groups: 
                 player.get_current_send_chat_groups(self).into_iter().collect()
@@ -19,6 +19,8 @@ impl Game{
         }
     }
     pub fn on_any_death(&mut self, _dead_player: PlayerReference){
+        self.resume_if_everyone_reconnected();
+
         for player in PlayerReference::all_players(self){
             player.send_packet(self, ToClientPacket::YourSendChatGroups { send_chat_groups: 
                 player.get_current_send_chat_groups(self).into_iter().collect()
@@ -29,6 +31,7 @@ impl Game{
         if self.game_is_over() {
             self.add_message_to_chat_group(ChatGroup::All, ChatMessageVariant::GameOver);
             self.send_packet_to_all(ToClientPacket::GameOver{ reason: GameOverReason::Draw });
+            self.send_packet_to_all(ToClientPacket::GameSummary{ summary: GameHistory::summary(self) });
 
             for player_ref in PlayerReference::all_players(self){
                 self.add_message_to_chat_group(ChatGroup::All,
@@ -45,10 +48,11 @@ impl Game{
         }
     }
     pub fn on_fast_forward(&mut self){
+        self.log_entry(GameLogEntry::FastForward);
         self.phase_machine.time_remaining = std::time::Duration::from_secs(0);
         
         self.add_message_to_chat_group(ChatGroup::All, ChatMessageVariant::PhaseFastForwarded);
-        self.send_packet_to_all(ToClientPacket::PhaseTimeLeft{ seconds_left: self.phase_machine.time_remaining.as_secs() });
+        self.send_packet_to_all(ToClientPacket::PhaseTimeLeft{ deadline: self.phase_deadline() });
     }
     pub fn on_grave_added(&mut self, grave: GraveReference){   
         let grave = grave.deref(self).clone();     
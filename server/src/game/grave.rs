@@ -6,6 +6,7 @@ use super::phase::PhaseType;
 use super::Game;
 use super::player::PlayerReference;
 use super::role::Role;
+use super::role_modifier::RoleModifier;
 use super::role_list::Faction;
 
 
@@ -32,6 +33,8 @@ pub enum GraveInformation {
         will: String,
         death_cause: GraveDeathCause,
         death_notes: Vec<String>,
+        #[serde(default)]
+        modifiers: Vec<RoleModifier>,
     }
 }
 
@@ -88,7 +91,8 @@ impl Grave{
                 role: player_ref.night_grave_role(game).clone().unwrap_or(player_ref.role(game)),
                 will: player_ref.night_grave_will(game).clone(),
                 death_cause: GraveDeathCause::Killers(player_ref.night_grave_killers(game).clone()),
-                death_notes: player_ref.night_grave_death_notes(game).clone()
+                death_notes: player_ref.night_grave_death_notes(game).clone(),
+                modifiers: player_ref.modifiers(game).clone()
             },
         }
     }
@@ -101,7 +105,8 @@ impl Grave{
                 role: player_ref.role(game), 
                 death_cause: GraveDeathCause::Execution, 
                 will: player_ref.will(game).clone(), 
-                death_notes: vec![]
+                death_notes: vec![],
+                modifiers: player_ref.modifiers(game).clone()
             }
         }
     }
@@ -116,7 +121,7 @@ impl Grave{
                 death_cause: GraveDeathCause::Killers(vec![GraveKiller::Suicide]), 
                 death_notes: vec![],
                 will: player_ref.will(game).clone(), 
-
+                modifiers: player_ref.modifiers(game).clone()
             }
         }
     }
@@ -130,7 +135,8 @@ impl Grave{
                 role: player_ref.role(game), 
                 death_cause: GraveDeathCause::LeftTown, 
                 will: player_ref.will(game).clone(), 
-                death_notes: vec![]
+                death_notes: vec![],
+                modifiers: player_ref.modifiers(game).clone()
             }
         }
     }
@@ -144,7 +150,8 @@ impl Grave{
                 role: player_ref.role(game),
                 death_cause: GraveDeathCause::BrokenHeart, 
                 will: player_ref.will(game).clone(),
-                death_notes: vec![]
+                death_notes: vec![],
+                modifiers: player_ref.modifiers(game).clone()
             }
         }
     }
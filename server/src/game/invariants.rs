@@ -0,0 +1,90 @@
+use std::collections::HashSet;
+
+use serde::Serialize;
+
+use crate::log;
+
+use super::{
+    components::game_history::GameHistory,
+    player::PlayerReference,
+    role::Role,
+    role_list::Faction,
+    tag::Tag,
+    Game
+};
+
+/// Something about the game's state that should never be true, no matter what roles did.
+/// These are bugs, usually in roles that move actions or roles around
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum InvariantViolation {
+    DeadPlayerHasVote{player: PlayerReference, chosen_vote: PlayerReference},
+    /// Cultists have to be alive and in the cult, and every living cult member has to be a cultist
+    OrderedCultistsWrong{ordered_cultists: Vec<PlayerReference>, expected: HashSet<PlayerReference>},
+    /// This tag is removed when the tagged player dies, so a dead player shouldn't have it
+    TagOnDeadPlayer{player: PlayerReference, tagged: PlayerReference, tag: Tag},
+    /// Each player should have exactly one starting role
+    PlayerNotInRolesToPlayers{player: PlayerReference, count: usize},
+    /// The player's role isn't the one they started with, or the last one they switched to
+    RoleDisagreesWithHistory{player: PlayerReference, role: Role, recorded_role: Option<Role>},
+}
+
+impl Game {
+    /// Checks everything that should always be true about the game, and returns every violation.
+    /// Called at the start of every phase in debug builds
+    pub fn check_invariants(&self) -> Result<(), Vec<InvariantViolation>> {
+        let mut violations = Vec::new();
+
+        for player in PlayerReference::all_players(self) {
+            if !player.alive(self) {
+                if let Some(chosen_vote) = player.chosen_vote(self) {
+                    violations.push(InvariantViolation::DeadPlayerHasVote { player, chosen_vote });
+                }
+            }
+
+            for (tagged, tags) in player.player_tags(self) {
+                if tagged.alive(self) {continue}
+                for tag in tags.iter().filter(|tag| Self::tag_is_removed_on_death(**tag)) {
+                    violations.push(InvariantViolation::TagOnDeadPlayer { player, tagged: *tagged, tag: *tag });
+                }
+            }
+
+            let count = self.roles_to_players.iter().filter(|(_, p)| *p == player).count();
+            if count != 1 {
+                violations.push(InvariantViolation::PlayerNotInRolesToPlayers { player, count });
+            }
+
+            let recorded_role = GameHistory::recorded_role(self, player);
+            if recorded_role != Some(player.role(self)) {
+                violations.push(InvariantViolation::RoleDisagreesWithHistory { player, role: player.role(self), recorded_role });
+            }
+        }
+
+        let expected: HashSet<PlayerReference> = PlayerReference::all_players(self)
+            .filter(|player| player.alive(self) && player.role(self).faction() == Faction::Cult)
+            .collect();
+        let ordered_cultists = &self.cult().ordered_cultists;
+        if
+            ordered_cultists.len() != expected.len() ||
+            !ordered_cultists.iter().all(|player| expected.contains(player))
+        {
+            violations.push(InvariantViolation::OrderedCultistsWrong { ordered_cultists: ordered_cultists.clone(), expected });
+        }
+
+        if violations.is_empty() { Ok(()) } else { Err(violations) }
+    }
+
+    fn tag_is_removed_on_death(tag: Tag) -> bool {
+        matches!(tag, Tag::GodfatherBackup)
+    }
+
+    /// Logs every violation, or panics with them if `panic_on_invariant_violation` is set
+    pub(super) fn report_invariant_violations(&self, phase: super::phase::PhaseType) {
+        let Err(violations) = self.check_invariants() else {return};
+
+        if self.panic_on_invariant_violation {
+            panic!("Invariants violated at the start of {:?} on day {}: {:#?}", phase, self.day_number(), violations);
+        }
+        log!(error "Game"; "Invariants violated at the start of {:?} on day {}: {:?}", phase, self.day_number(), violations);
+    }
+}
@@ -3,9 +3,14 @@ pub mod phase;
 pub mod player;
 pub mod chat;
 pub mod role;
+pub mod role_modifier;
+pub mod role_options;
 pub mod visit;
 pub mod verdict;
 pub mod role_list;
+pub mod role_list_generation;
+pub mod role_list_analysis;
+pub mod preset;
 pub mod settings;
 pub mod resolution_state;
 pub mod components;
@@ -15,17 +20,27 @@ pub mod tag;
 pub mod event;
 pub mod spectator;
 pub mod game_listeners;
-
-use std::collections::HashMap;
-use std::time::Duration;
+pub mod replay;
+pub mod win_condition;
+pub mod bot;
+pub mod invariants;
+pub mod simulation;
+
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use components::love_linked::LoveLinked;
 use components::mafia::Mafia;
 use components::verdicts_today::VerdictsToday;
+use components::game_history::GameHistory;
+use components::ability_charges::AbilityCharges;
+use components::night_trace::NightTrace;
+use rand::rngs::StdRng;
 use rand::seq::SliceRandom;
-use rand::thread_rng;
+use rand::SeedableRng;
 use serde::Serialize;
 
 use crate::client_connection::ClientConnection;
+use crate::log;
 use crate::game::event::on_game_start::OnGameStart;
 use crate::game::player::PlayerIndex;
 use crate::packet::ToClientPacket;
@@ -33,7 +48,7 @@ use chat::{ChatMessageVariant, ChatGroup, ChatMessage};
 use player::PlayerReference;
 use player::Player;
 use phase::PhaseStateMachine;
-use settings::Settings;
+use settings::{Settings, TrialSystem};
 use grave::Grave;
 use self::components::{
     arsonist_doused::ArsonistDoused,
@@ -44,7 +59,7 @@ use self::resolution_state::ResolutionState;
 use self::event::on_game_ending::OnGameEnding;
 use self::event::on_grave_added::OnGraveAdded;
 use self::grave::GraveReference;
-use self::phase::PhaseState;
+use self::phase::{PauseReason, PhaseState};
 use self::player::PlayerInitializeParameters;
 use self::spectator::{
     spectator_pointer::{
@@ -54,7 +69,9 @@ use self::spectator::{
     SpectatorInitializeParameters
 };
 use self::role::{Role, RoleState};
+use self::role_options::RoleOptionsError;
 use self::verdict::Verdict;
+use self::replay::GameLogEntry;
 
 
 pub struct Game {
@@ -70,8 +87,17 @@ pub struct Game {
 
     phase_machine : PhaseStateMachine,
 
+    /// The seed that `rng` was created with. The same seed and the same inputs always produce the same game.
+    pub seed: u64,
+    /// Every random decision made during the game must be drawn from this
+    pub rng: StdRng,
+
     /// Whether the game is still updating phase times
     pub ticking: bool,
+    /// Tests panic when an invariant is broken so they fail right away. Live games only log it
+    pub panic_on_invariant_violation: bool,
+
+    game_log: Vec<GameLogEntry>,
 
 
     //components with data
@@ -80,17 +106,27 @@ pub struct Game {
     pub arsonist_doused: ArsonistDoused,
     pub puppeteer_marionette: PuppeteerMarionette,
     pub love_linked: LoveLinked,
-    pub verdicts_today: VerdictsToday
+    pub verdicts_today: VerdictsToday,
+    game_history: GameHistory,
+    ability_charges: AbilityCharges,
+    night_trace: NightTrace,
 }
 
-#[derive(Serialize, Debug, Clone, Copy)]
-#[serde(rename_all = "camelCase")]
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "camelCase")]
 pub enum RejectStartReason {
     GameEndsInstantly,
     RoleListTooSmall,
     RoleListCannotCreateRoles,
+    /// No role can be picked for this outline with the enabled roles and role list constraints
+    RoleOutlineCannotBeSatisfied{outline: u8},
+    /// Too many ways to fill the role list were tried without finding one that works
+    RoleListTooComplex,
     ZeroTimeGame,
-    PlayerDisconnected
+    PlayerDisconnected,
+    InvalidGameRules,
+    CustomRoleSetHasNoEnabledRoles,
+    InvalidRoleOptions{role: Role, error: RoleOptionsError},
 }
 
 #[derive(Serialize, Debug, Clone, Copy)]
@@ -101,32 +137,62 @@ pub enum GameOverReason {
     Draw
 }
 
+/// How the nomination votes came out under the plurality trial system
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PluralityVoteResult {
+    Winner(PlayerReference),
+    /// Everyone tied for the most votes
+    Tie(Vec<PlayerReference>),
+    NoVotes
+}
+
 
 
 impl Game {
     pub fn new(settings: Settings, players: Vec<PlayerInitializeParameters>, spectators: Vec<SpectatorInitializeParameters>) -> Result<Self, RejectStartReason>{
+        if players.iter().any(|player| !matches!(player.connection, ClientConnection::Connected(_) | ClientConnection::Bot(_))) {
+            return Err(RejectStartReason::PlayerDisconnected);
+        }
+        Self::new_with_any_connection(settings, players, spectators)
+    }
+    /// Same as `Game::new`, but players don't need to be connected.
+    /// Used to rebuild games from replays, where every player is a mock.
+    fn new_with_any_connection(settings: Settings, players: Vec<PlayerInitializeParameters>, spectators: Vec<SpectatorInitializeParameters>) -> Result<Self, RejectStartReason>{
         //check settings are not completly off the rails
         if settings.phase_times.game_ends_instantly() {
             return Err(RejectStartReason::ZeroTimeGame);
         }
-        
+        if !settings.rules.is_valid() {
+            return Err(RejectStartReason::InvalidGameRules);
+        }
+        if settings.role_list.custom_role_sets().any(|set| !set.is_valid(&settings.enabled_roles)) {
+            return Err(RejectStartReason::CustomRoleSetHasNoEnabledRoles);
+        }
+        if let Err((role, error)) = settings.role_options.check() {
+            return Err(RejectStartReason::InvalidRoleOptions { role, error });
+        }
 
+        let seed = settings.seed.unwrap_or_else(rand::random);
+        let mut rng = StdRng::seed_from_u64(seed);
+
+        //Some role lists can make games that are already over, like ones with only town.
+        //Those are thrown out and the roles are generated again
         let mut role_generation_tries = 0;
         const MAX_ROLE_GENERATION_TRIES: u8 = 250;
         let mut game = loop {
 
             if role_generation_tries >= MAX_ROLE_GENERATION_TRIES {
-                return Err(RejectStartReason::RoleListCannotCreateRoles);
+                return Err(RejectStartReason::GameEndsInstantly);
             }
 
             let settings = settings.clone();
             let role_list = settings.role_list.clone();
 
 
-            let roles_to_players = Self::assign_players_to_roles(match role_list.create_random_roles(&settings.enabled_roles){
-                Some(roles) => {roles},
-                None => {return Err(RejectStartReason::RoleListCannotCreateRoles);}
-            });
+            let roles_to_players = Self::assign_players_to_roles(
+                role_list.create_random_roles(&settings.enabled_roles, &settings.role_list_constraints, &settings.role_options, &mut rng)?,
+                &mut rng
+            );
 
             let mut roles_to_players_clone = roles_to_players.clone();
             roles_to_players_clone.sort_by(|(_, i), (_,j)| i.cmp(j));
@@ -135,12 +201,9 @@ impl Game {
 
             let mut new_players = Vec::new();
             for (player_index, player) in players.iter().enumerate() {
-                let ClientConnection::Connected(ref sender) = player.connection else {
-                    return Err(RejectStartReason::PlayerDisconnected)
-                };
                 let new_player = Player::new(
                     player.name.clone(),
-                    sender.clone(),
+                    player.connection.clone(),
                     match shuffled_roles.get(player_index){
                         Some(role) => *role,
                         None => return Err(RejectStartReason::RoleListTooSmall),
@@ -150,23 +213,7 @@ impl Game {
             }
             drop(shuffled_roles); // Ensure we don't use the order of roles anywhere
 
-            let game = Self{
-                roles_to_players: roles_to_players.into_iter().map(|(r,i)|(r,PlayerReference::new_unchecked(i))).collect(),
-                ticking: true,
-                spectators: spectators.clone().into_iter().map(Spectator::new).collect(),
-                spectator_chat_messages: Vec::new(),
-                players: new_players.into_boxed_slice(),
-                graves: Vec::new(),
-                phase_machine: PhaseStateMachine::new(settings.phase_times.clone()),
-                settings,
-
-                cult: Cult::default(),
-                mafia: Mafia,
-                arsonist_doused: ArsonistDoused::default(),
-                puppeteer_marionette: PuppeteerMarionette::default(),
-                love_linked: LoveLinked::default(),
-                verdicts_today: VerdictsToday::default()
-            };
+            let game = Self::from_players(settings, new_players, roles_to_players, spectators.clone(), seed, rng.clone());
 
             if !game.game_is_over() {
                 break game;
@@ -175,16 +222,17 @@ impl Game {
         };
 
         if game.game_is_over() {
-            return Err(RejectStartReason::RoleListCannotCreateRoles);
+            return Err(RejectStartReason::GameEndsInstantly);
         }
 
+        log!(info "Game"; "Starting game with seed {}", game.seed);
         
         game.send_packet_to_all(ToClientPacket::StartGame);
         
         //on role creation needs to be called after all players roles are known
         for player_ref in PlayerReference::all_players(&game){
-            let role_data_copy = player_ref.role_state(&game).clone();
-            player_ref.set_role(&mut game, role_data_copy);
+            let role_state = game.settings.role_options.new_state(player_ref.role(&game));
+            player_ref.set_role(&mut game, role_state);
         }
 
         for player_ref in PlayerReference::all_players(&game){
@@ -198,9 +246,45 @@ impl Game {
 
         Ok(game)
     }
-    fn assign_players_to_roles(roles: Vec<Role>)->Vec<(Role, PlayerIndex)>{
+    /// A game that hasn't started yet, with players whose roles are already picked.
+    /// Nothing is sent to the players and no roles are created
+    pub(crate) fn from_players(
+        settings: Settings,
+        players: Vec<Player>,
+        roles_to_players: Vec<(Role, PlayerIndex)>,
+        spectators: Vec<SpectatorInitializeParameters>,
+        seed: u64,
+        rng: StdRng
+    ) -> Self {
+        Self{
+            roles_to_players: roles_to_players.into_iter().map(|(r,i)|(r,PlayerReference::new_unchecked(i))).collect(),
+            ticking: true,
+            panic_on_invariant_violation: false,
+            spectators: spectators.into_iter().map(Spectator::new).collect(),
+            spectator_chat_messages: Vec::new(),
+            players: players.into_boxed_slice(),
+            graves: Vec::new(),
+            phase_machine: PhaseStateMachine::new(settings.phase_times.clone()),
+            settings,
+            seed,
+            rng,
+            game_log: Vec::new(),
+
+            cult: Cult::default(),
+            mafia: Mafia,
+            arsonist_doused: ArsonistDoused::default(),
+            puppeteer_marionette: PuppeteerMarionette::default(),
+            love_linked: LoveLinked::default(),
+            verdicts_today: VerdictsToday::default(),
+            game_history: GameHistory::default(),
+            ability_charges: AbilityCharges::default(),
+            night_trace: NightTrace::default(),
+        }
+    }
+
+    fn assign_players_to_roles(roles: Vec<Role>, rng: &mut StdRng)->Vec<(Role, PlayerIndex)>{
         let mut player_indices: Vec<PlayerIndex> = (0..roles.len() as PlayerIndex).collect();
-        player_indices.shuffle(&mut thread_rng());
+        player_indices.shuffle(rng);
         roles.into_iter().zip(player_indices).collect()
     }
 
@@ -216,7 +300,7 @@ impl Game {
             let mut voting_power = 1;
             if let RoleState::Mayor(mayor) = player_ref.role_state(self).clone(){
                 if mayor.revealed {
-                    voting_power += 2;
+                    voting_power = self.settings.rules.mayor_voting_power;
                 }
             }
             
@@ -228,10 +312,8 @@ impl Game {
         }
         (guilty, innocent)
     }
-    pub fn count_votes_and_start_trial(&mut self){
-
-        let &PhaseState::Nomination { trials_left } = self.current_phase() else {return};
-
+    /// Returns how many votes each player has, counting revealed mayors' extra votes
+    pub fn count_nomination_votes(&self)->HashMap<PlayerReference, u8>{
         let mut voted_player_votes: HashMap<PlayerReference, u8> = HashMap::new();
 
         for player in PlayerReference::all_players(self){
@@ -242,7 +324,7 @@ impl Game {
             let mut voting_power = 1;
             if let RoleState::Mayor(mayor) = player.role_state(self).clone() {
                 if mayor.revealed {
-                    voting_power = 3;
+                    voting_power = self.settings.rules.mayor_voting_power;
                 }
             }
 
@@ -252,6 +334,13 @@ impl Game {
                 voted_player_votes.insert(voted_player, voting_power);
             }
         }
+        voted_player_votes
+    }
+    pub fn count_votes_and_start_trial(&mut self){
+
+        let &PhaseState::Nomination { trials_left } = self.current_phase() else {return};
+
+        let voted_player_votes = self.count_nomination_votes();
         
         self.send_packet_to_all(
             ToClientPacket::PlayerVotes { votes_for_player: 
@@ -259,6 +348,8 @@ impl Game {
             }
         );
 
+        // Under plurality, nobody is put on trial until nomination ends
+        if self.settings.rules.trial_system == TrialSystem::Plurality {return}
 
         let mut next_player_on_trial = None;
         for (player, votes) in voted_player_votes.iter(){
@@ -268,19 +359,52 @@ impl Game {
             }
         }
         
-        if let Some(player_on_trial) = next_player_on_trial {
-            self.send_packet_to_all(ToClientPacket::PlayerOnTrial { player_index: player_on_trial.index() } );
+        let Some(player_on_trial) = next_player_on_trial else {return};
+        self.send_packet_to_all(ToClientPacket::PlayerOnTrial { player_index: player_on_trial.index() } );
+
+        if self.settings.rules.trial_system == TrialSystem::DirectLynch {
+            self.announce_nomination(player_on_trial);
+            PhaseStateMachine::next_phase(self, Some(PhaseState::FinalWords { player_on_trial }));
+        }else{
             PhaseStateMachine::next_phase(self, Some(PhaseState::Testimony { trials_left: trials_left-1, player_on_trial }));
         }
     }
+    /// Returns the player with the most votes, or everyone tied for the most votes
+    pub fn plurality_vote_winner(&self)->PluralityVoteResult{
+        let votes = self.count_nomination_votes();
+        let Some(most_votes) = votes.values().max().copied() else {return PluralityVoteResult::NoVotes};
+
+        let mut tied: Vec<PlayerReference> = votes.into_iter()
+            .filter(|(_, player_votes)| *player_votes == most_votes)
+            .map(|(player, _)| player)
+            .collect();
+        
+        if tied.len() == 1 {
+            PluralityVoteResult::Winner(tied[0])
+        }else{
+            tied.sort();
+            PluralityVoteResult::Tie(tied)
+        }
+    }
+    pub fn announce_nomination(&mut self, player_on_trial: PlayerReference){
+        self.add_message_to_chat_group(ChatGroup::All, 
+            ChatMessageVariant::PlayerNominated {
+                player_index: player_on_trial.index(),
+                players_voted: PlayerReference::all_players(self)
+                    .filter(|player_ref| player_ref.chosen_vote(self) == Some(player_on_trial))
+                    .map(|player_ref| player_ref.index())
+                    .collect()
+            }
+        );
+    }
     pub fn nomination_votes_is_enough(&self, votes: u8)->bool{
         votes >= self.nomination_votes_required()
     }
     pub fn nomination_votes_required(&self)->u8{
-        1 + (
+        self.settings.rules.nomination_threshold.votes_required(
             PlayerReference::all_players(self)
                 .filter(|p| p.alive(self) && !p.forfeit_vote(self))
-                .count() / 2
+                .count()
         ) as u8
     }
 
@@ -300,28 +424,77 @@ impl Game {
         self.phase_machine.day_number
     }
 
+    pub fn phase_time_remaining(&self) -> Duration {
+        self.phase_machine.time_remaining
+    }
+    pub fn set_phase_time_remaining(&mut self, time_remaining: Duration) {
+        self.phase_machine.time_remaining = time_remaining;
+    }
+    pub fn paused(&self) -> Option<PauseReason> {
+        self.phase_machine.paused
+    }
+    /// Stops the phase timer. Everything else keeps working, the phase just won't end until the game is resumed
+    pub fn pause(&mut self, reason: PauseReason) {
+        if self.paused().is_some() || !self.ticking { return }
+
+        self.phase_machine.paused = Some(reason);
+        self.log_entry(GameLogEntry::Paused { reason });
+        self.add_message_to_chat_group(ChatGroup::All, ChatMessageVariant::GamePaused { reason });
+        self.send_packet_to_all(ToClientPacket::GamePaused { paused: true });
+    }
+    pub fn resume(&mut self) {
+        if self.paused().is_none() { return }
+
+        self.phase_machine.paused = None;
+        self.log_entry(GameLogEntry::Resumed);
+        self.add_message_to_chat_group(ChatGroup::All, ChatMessageVariant::GameResumed);
+        self.send_packet_to_all(ToClientPacket::GamePaused { paused: false });
+        self.send_packet_to_all(ToClientPacket::PhaseTimeLeft { deadline: self.phase_deadline() });
+    }
+    /// Resumes a game that was paused because someone lost connection, once nobody alive is still gone
+    pub fn resume_if_everyone_reconnected(&mut self) {
+        if self.paused() != Some(PauseReason::LostConnection) { return }
+
+        if !PlayerReference::all_players(self).any(|p| p.alive(self) && p.could_reconnect(self)) {
+            self.resume();
+        }
+    }
+    /// When the current phase ends, in milliseconds since the unix epoch.
+    /// Only as accurate as the last tick, so the game should be ticked before this is sent.
+    pub fn phase_deadline(&self) -> u64 {
+        (SystemTime::now() + self.phase_machine.time_remaining)
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |deadline| deadline.as_millis() as u64)
+    }
+
     pub fn tick(&mut self, time_passed: Duration){
 
         if !self.ticking { return }
 
         if self.game_is_over() {
+            self.log_entry(GameLogEntry::GameEnded);
             OnGameEnding::invoke(self);
         }
 
         if self.phase_machine.day_number == u8::MAX {
             self.add_message_to_chat_group(ChatGroup::All, ChatMessageVariant::GameOver);
             self.send_packet_to_all(ToClientPacket::GameOver{ reason: GameOverReason::ReachedMaxDay });
+            self.send_packet_to_all(ToClientPacket::GameSummary{ summary: GameHistory::summary(self) });
             self.ticking = false;
             return;
         }
 
-        while self.phase_machine.time_remaining <= Duration::ZERO {
-            PhaseStateMachine::next_phase(self, None);
-        }
         PlayerReference::all_players(self).for_each(|p|p.tick(self, time_passed));
         SpectatorPointer::all_spectators(self).for_each(|s|s.tick(self, time_passed));
 
+        if self.paused().is_some() { return }
+
         self.phase_machine.time_remaining = self.phase_machine.time_remaining.saturating_sub(time_passed);
+
+        // Lobbies sleep until the phase ends, so the next phase has to start in the same tick
+        while self.phase_machine.time_remaining <= Duration::ZERO {
+            PhaseStateMachine::next_phase(self, None);
+        }
     }
 
     pub fn add_grave(&mut self, grave: Grave){
@@ -343,6 +516,22 @@ impl Game {
             self.add_chat_message_to_spectators(variant);
         }
     }
+    /// Sends a player's message to each of their team chats, so someone who shares more than one with them only gets it once.
+    /// Lovers chat is split by love link, so there it only goes to the sender and who they're linked to
+    pub fn add_message_to_team_chats(&mut self, sender: PlayerReference, groups: Vec<ChatGroup>, variant: ChatMessageVariant){
+        let links = self.love_linked().get_links(sender);
+        let mut received = HashSet::new();
+
+        for group in groups {
+            let message = ChatMessage::new_non_private(variant.clone(), group.clone());
+            for player_ref in group.all_players_in_group(self){
+                if group == ChatGroup::Lovers && player_ref != sender && !links.contains(&player_ref) {continue}
+                if !received.insert(player_ref) {continue}
+                player_ref.add_chat_message(self, message.clone());
+                player_ref.send_chat_messages(self);
+            }
+        }
+    }
     pub fn add_messages_to_chat_group(&mut self, group: ChatGroup, messages: Vec<ChatMessageVariant>){
         for message in messages.into_iter(){
             self.add_message_to_chat_group(group.clone(), message);
@@ -379,8 +568,10 @@ impl Game {
 
 pub mod test {
 
+    use rand::{rngs::StdRng, SeedableRng};
+
     use super::{
-        components::{arsonist_doused::ArsonistDoused, cult::Cult, love_linked::LoveLinked, mafia::Mafia, puppeteer_marionette::PuppeteerMarionette, verdicts_today::VerdictsToday},
+        components::{arsonist_doused::ArsonistDoused, cult::Cult, love_linked::LoveLinked, mafia::Mafia, puppeteer_marionette::PuppeteerMarionette, verdicts_today::VerdictsToday, game_history::GameHistory, ability_charges::AbilityCharges, night_trace::NightTrace},
         event::on_game_start::OnGameStart,
         phase::PhaseStateMachine,
         player::{test::mock_player, PlayerIndex, PlayerReference},
@@ -400,11 +591,13 @@ pub mod test {
 
         let settings = settings.clone();
         let role_list = settings.role_list.clone();
+
+        let seed = settings.seed.unwrap_or_else(rand::random);
+        let mut rng = StdRng::seed_from_u64(seed);
         
-        let roles_to_players = assign_players_to_roles(match role_list.create_random_roles(&settings.enabled_roles){
-            Some(roles) => {roles},
-            None => {return Err(RejectStartReason::RoleListCannotCreateRoles);}
-        });
+        let roles_to_players = assign_players_to_roles(
+            role_list.create_random_roles(&settings.enabled_roles, &settings.role_list_constraints, &settings.role_options, &mut rng)?
+        );
         
         let mut roles_to_players_clone = roles_to_players.clone();
         roles_to_players_clone.sort_by(|(_, i), (_,j)| i.cmp(j));
@@ -427,25 +620,32 @@ pub mod test {
         let mut game = Game{
             roles_to_players: roles_to_players.into_iter().map(|(r,i)|(r,PlayerReference::new_unchecked(i))).collect(),
             ticking: true,
+            panic_on_invariant_violation: true,
             spectators: Vec::new(),
             spectator_chat_messages: Vec::new(),
             players: players.into_boxed_slice(),
             graves: Vec::new(),
             phase_machine: PhaseStateMachine::new(settings.phase_times.clone()),
             settings,
+            seed,
+            rng,
+            game_log: Vec::new(),
 
             cult: Cult::default(),
             mafia: Mafia,
             arsonist_doused: ArsonistDoused::default(),
             puppeteer_marionette: PuppeteerMarionette::default(),
             love_linked: LoveLinked::default(),
-            verdicts_today: VerdictsToday::default()
+            verdicts_today: VerdictsToday::default(),
+            game_history: GameHistory::default(),
+            ability_charges: AbilityCharges::default(),
+            night_trace: NightTrace::default(),
         };
 
         //on role creation needs to be called after all players roles are known
         for player_ref in PlayerReference::all_players(&game){
-            let role_data_copy = player_ref.role_state(&game).clone();
-            player_ref.set_role(&mut game, role_data_copy);
+            let role_state = game.settings.role_options.new_state(player_ref.role(&game));
+            player_ref.set_role(&mut game, role_state);
         }
 
         OnGameStart::invoke(&mut game);
@@ -3,9 +3,10 @@ use std::collections::HashMap;
 use crate::{packet::ToServerPacket, strings::TidyableString, log};
 
 use super::{
-    chat::{ChatGroup, ChatMessageVariant, MessageSender},
+    chat::{ChatGroup, ChatMessageVariant, MessageSender, TeamChat},
     event::on_fast_forward::OnFastForward,
     phase::{PhaseState, PhaseType},
+    replay::GameLogEntry,
     player::{PlayerIndex, PlayerReference},
     role::{kira::{Kira, KiraGuess}, mayor::Mayor, puppeteer::PuppeteerAction, retrainer::Retrainer, Role, RoleState}, role_list::{Faction, RoleSet}, 
     spectator::spectator_pointer::{SpectatorIndex, SpectatorPointer},
@@ -19,6 +20,8 @@ impl Game {
     pub fn on_spectator_message(&mut self, sender_index: SpectatorIndex, incoming_packet: ToServerPacket){
         let sender_pointer = SpectatorPointer::new(sender_index);
 
+        self.log_entry(GameLogEntry::SpectatorPacket{spectator: sender_index, packet: incoming_packet.clone()});
+
         #[allow(clippy::single_match)]
         match incoming_packet {
             ToServerPacket::VoteFastForwardPhase { fast_forward } => {
@@ -40,6 +43,8 @@ impl Game {
             }
         };
 
+        self.log_entry(GameLogEntry::PlayerPacket{player: sender_player_index, packet: incoming_packet.clone()});
+
         'packet_match: {match incoming_packet {
             ToServerPacket::Vote { player_index: player_voted_index } => {
                 let &PhaseState::Nomination { .. } = self.current_phase() else {break 'packet_match};
@@ -72,8 +77,8 @@ impl Game {
                 let mut target_message_sent = false;
                 for chat_group in sender_player_ref.get_current_send_chat_groups(self){
                     match chat_group {
-                        ChatGroup::All | ChatGroup::Interview | ChatGroup::Dead => {},
-                        ChatGroup::Mafia | ChatGroup::Cult => {
+                        ChatGroup::All | ChatGroup::Interview | ChatGroup::Dead | ChatGroup::Lovers | ChatGroup::Puppeteer => {},
+                        ChatGroup::Mafia | ChatGroup::Cult | ChatGroup::Fiends => {
                             self.add_message_to_chat_group( chat_group,
                                 ChatMessageVariant::Targeted { 
                                     targeter: sender_player_ref.index(), 
@@ -117,8 +122,16 @@ impl Game {
                 if text.replace(['\n', '\r'], "").trim().is_empty() {
                     break 'packet_match;
                 }
+
+                let text = text.trim_newline().trim_whitespace().truncate(400).truncate_lines(20);
+                let mut team_chat_groups = Vec::new();
                 
                 for chat_group in sender_player_ref.get_current_send_chat_groups(self){
+                    if TeamChat::from_chat_group(&chat_group).is_some() {
+                        team_chat_groups.push(chat_group);
+                        continue;
+                    }
+
                     let message_sender = match chat_group {
                         ChatGroup::Jail => {
                             if sender_player_ref.role(self) == Role::Jailor {
@@ -145,7 +158,18 @@ impl Game {
                         chat_group.clone(),
                         ChatMessageVariant::Normal{
                             message_sender,
-                            text: text.trim_newline().trim_whitespace().truncate(400).truncate_lines(20), 
+                            text: text.clone(), 
+                        }
+                    );
+                }
+
+                if !team_chat_groups.is_empty() {
+                    self.add_message_to_team_chats(
+                        sender_player_ref,
+                        team_chat_groups,
+                        ChatMessageVariant::Normal{
+                            message_sender: MessageSender::Player { player: sender_player_index },
+                            text,
                         }
                     );
                 }
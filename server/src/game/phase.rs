@@ -6,7 +6,7 @@ use serde::{Serialize, Deserialize};
 use crate::packet::ToClientPacket;
 
 use super::{
-    chat::{ChatGroup, ChatMessageVariant}, event::{before_phase_end::BeforePhaseEnd, on_any_death::OnAnyDeath, on_night_priority::OnNightPriority, on_phase_start::OnPhaseStart}, grave::Grave, player::PlayerReference, role::Priority, settings::PhaseTimeSettings, Game
+    chat::{ChatGroup, ChatMessageVariant}, components::night_trace::NightTrace, event::{before_phase_end::BeforePhaseEnd, on_any_death::OnAnyDeath, on_night_priority::OnNightPriority, on_phase_start::OnPhaseStart}, grave::Grave, player::PlayerReference, replay::GameLogEntry, role::Priority, settings::{PhaseTimeSettings, TrialSystem}, Game, PluralityVoteResult
 };
 
 
@@ -42,10 +42,19 @@ pub enum PhaseState {
     Night,
 }
 
+/// Why the phase timer was stopped
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "camelCase")]
+pub enum PauseReason {
+    Host,
+    LostConnection,
+}
+
 pub struct PhaseStateMachine {
     pub time_remaining: Duration,
     pub current_state: PhaseState,
     pub day_number: u8, // Hopefully nobody is having more than 256 days anyway
+    pub paused: Option<PauseReason>,
 }
 
 impl PhaseStateMachine {
@@ -56,6 +65,7 @@ impl PhaseStateMachine {
             time_remaining: times.get_time_for(current_state.phase()),
             day_number: 1,
             current_state,
+            paused: None,
         }
     }
 
@@ -68,11 +78,15 @@ impl PhaseStateMachine {
         }
 
         game.phase_machine.current_state = new_phase;
+        game.log_entry(GameLogEntry::PhaseStart{
+            phase: game.current_phase().clone(),
+            day_number: game.day_number()
+        });
         game.phase_machine.time_remaining = game.settings.phase_times.get_time_for(game.current_phase().phase());
 
-        //if there are less than 3 players alive then the game is sped up by 2x
-        if PlayerReference::all_players(game).filter(|p|p.alive(game)).count() <= 3{
-            game.phase_machine.time_remaining /= 2;
+        let rules = &game.settings.rules;
+        if PlayerReference::all_players(game).filter(|p|p.alive(game)).count() <= rules.speed_up_living_players as usize {
+            game.phase_machine.time_remaining /= rules.speed_up_factor;
         }
 
         PhaseState::start(game);
@@ -123,22 +137,18 @@ impl PhaseState {
             },
             PhaseState::Nomination { trials_left } => {
                 let required_votes = game.nomination_votes_required();
-                game.add_message_to_chat_group(ChatGroup::All, ChatMessageVariant::TrialInformation { required_votes, trials_left });
+                game.add_message_to_chat_group(ChatGroup::All, ChatMessageVariant::TrialInformation {
+                    required_votes,
+                    trials_left,
+                    trial_system: game.settings.rules.trial_system
+                });
                 
 
                 let packet = ToClientPacket::new_player_votes(game);
                 game.send_packet_to_all(packet);
             },
             PhaseState::Testimony { player_on_trial, .. } => {
-                game.add_message_to_chat_group(ChatGroup::All, 
-                    ChatMessageVariant::PlayerNominated {
-                        player_index: player_on_trial.index(),
-                        players_voted: PlayerReference::all_players(game)
-                            .filter(|player_ref| player_ref.chosen_vote(game) == Some(player_on_trial))
-                            .map(|player_ref| player_ref.index())
-                            .collect()
-                    }
-                );
+                game.announce_nomination(player_on_trial);
                 game.send_packet_to_all(ToClientPacket::PlayerOnTrial { player_index: player_on_trial.index() });
             },
             PhaseState::Briefing 
@@ -161,10 +171,23 @@ impl PhaseState {
                 Self::Discussion
             },
             PhaseState::Discussion => {
-                Self::Nomination { trials_left: 3 }
+                Self::Nomination { trials_left: game.settings.rules.trials_per_day }
             },
-            PhaseState::Nomination {..} => {                
-                Self::Dusk
+            &PhaseState::Nomination { trials_left } => {
+                if game.settings.rules.trial_system != TrialSystem::Plurality {
+                    Self::Dusk
+                } else {
+                    match game.plurality_vote_winner() {
+                        PluralityVoteResult::Winner(player_on_trial) => Self::Testimony { trials_left: trials_left-1, player_on_trial },
+                        PluralityVoteResult::Tie(tied) => {
+                            game.add_message_to_chat_group(ChatGroup::All, ChatMessageVariant::NominationTied {
+                                player_indices: PlayerReference::ref_vec_to_index(&tied)
+                            });
+                            Self::Dusk
+                        },
+                        PluralityVoteResult::NoVotes => Self::Dusk
+                    }
+                }
             },
             &PhaseState::Testimony { trials_left, player_on_trial } => {
                 Self::Judgement { trials_left, player_on_trial }
@@ -202,7 +225,8 @@ impl PhaseState {
             &PhaseState::FinalWords { player_on_trial } => {
                 let (guilty, innocent) = game.count_verdict_votes(player_on_trial);
                 
-                if innocent < guilty {
+                // Direct lynches skip judgement, so there are no verdicts to count
+                if game.settings.rules.trial_system == TrialSystem::DirectLynch || innocent < guilty {
                     let new_grave = Grave::from_player_lynch(game, player_on_trial);
                     player_on_trial.die(game, new_grave);
                 }
@@ -222,16 +246,22 @@ impl PhaseState {
                     player_ref.set_night_visits(game, visits.clone());
                 }
 
+                NightTrace::start_night(game);
                 for priority in Priority::values(){
+                    NightTrace::start_priority(game, priority);
                     OnNightPriority::new(priority).invoke(game);
                     for player_ref in PlayerReference::all_players(game){
+                        let before = NightTrace::before_night_action(game);
                         player_ref.do_night_action(game, priority);
+                        NightTrace::after_night_action(game, player_ref, before);
                     }
+                    NightTrace::end_priority(game);
                 }
+                NightTrace::end_night(game);
 
                 for player_ref in PlayerReference::all_players(game){
                     let mut messages = player_ref.night_messages(game).to_vec();
-                    messages.shuffle(&mut rand::thread_rng());
+                    messages.shuffle(&mut game.rng);
                     messages.sort();
                     player_ref.add_private_chat_messages(game, messages);
                 }
@@ -12,15 +12,13 @@ use std::collections::HashMap;
 use std::collections::HashSet;
 
 use crate::client_connection::ClientConnection;
-use crate::{
-    game::{
-        role::{Role, RoleState}, 
-        chat::ChatMessageVariant, 
-        visit::Visit, 
-        grave::GraveKiller, 
-        verdict::Verdict, available_buttons::AvailableButtons
-    },
-    websocket_connections::connection::ClientSender,
+use crate::game::{
+    role::{Role, RoleState}, 
+    role_modifier::RoleModifier,
+    chat::ChatMessageVariant, 
+    visit::Visit, 
+    grave::GraveKiller, 
+    verdict::Verdict, available_buttons::AvailableButtons
 };
 
 use super::chat::ChatMessage;
@@ -36,6 +34,7 @@ pub struct Player {
 
     name: String,
     role_state: RoleState,
+    modifiers: Vec<RoleModifier>,
     alive: bool,
     will: String,
     notes: String,
@@ -62,6 +61,7 @@ struct PlayerVotingVariables{
     chosen_vote:    Option<PlayerReference>,
     verdict:        Verdict,
 }
+#[derive(Clone, PartialEq)]
 struct PlayerNightVariables{
     died: bool,
     attacked: bool,
@@ -84,13 +84,22 @@ struct PlayerNightVariables{
     grave_will: String,
     grave_death_notes: Vec<String>,
 }
+/// Everything a night action can change about a player, so the night trace can tell whether an action did anything
+#[derive(Clone, PartialEq)]
+pub struct PlayerNightState{
+    role_state: serde_json::Value,
+    alive: bool,
+    player_tags: HashMap<PlayerReference, Vec1<Tag>>,
+    night_variables: PlayerNightVariables,
+}
 impl Player {
-    pub fn new(name: String, sender: ClientSender, role: Role) -> Self {
+    pub fn new(name: String, connection: ClientConnection, role: Role) -> Self {
         Self {
-            connection: ClientConnection::Connected(sender),
+            connection,
 
             name,
             role_state: role.default_state(),
+            modifiers: Vec::new(),
             alive: true,
             will: "".to_string(),
             notes: "".to_string(),
@@ -152,6 +161,7 @@ pub mod test {
 
             name,
             role_state: role.default_state(),
+            modifiers: Vec::new(),
             alive: true,
             will: "".to_string(),
             notes: "".to_string(),
@@ -6,9 +6,10 @@ use crate::{
     game::{
         chat::{
             ChatGroup, ChatMessage, ChatMessageVariant
-        }, event::on_fast_forward::OnFastForward,
+        }, components::ability_charges::{Ability, AbilityCharges}, event::on_fast_forward::OnFastForward,
         grave::GraveKiller,
         role::{Role, RoleState},
+        role_modifier::{RoleModifier, BLIND_APPEARED_VISITS},
         tag::Tag,
         verdict::Verdict,
         visit::Visit,
@@ -16,7 +17,7 @@ use crate::{
     }, 
     packet::ToClientPacket, 
 };
-use super::PlayerReference;
+use super::{PlayerNightState, PlayerReference};
 
 
 impl PlayerReference{
@@ -32,7 +33,38 @@ impl PlayerReference{
     }
     pub fn set_role_state(&self, game: &mut Game, new_role_data: RoleState){
         self.deref_mut(game).role_state = new_role_data;
-        self.send_packet(game, ToClientPacket::YourRoleState { role_state: self.deref(game).role_state.clone() } );
+        self.send_packet(game, ToClientPacket::YourRoleState {
+            role_state: self.deref(game).role_state.clone(),
+            modifiers: self.deref(game).modifiers.clone()
+        });
+    }
+    pub fn modifiers<'a>(&self, game: &'a Game) -> &'a Vec<RoleModifier> {
+        &self.deref(game).modifiers
+    }
+    pub fn has_modifier(&self, game: &Game, modifier: RoleModifier) -> bool {
+        self.deref(game).modifiers.contains(&modifier)
+    }
+    pub fn set_modifiers(&self, game: &mut Game, modifiers: Vec<RoleModifier>){
+        self.deref_mut(game).modifiers = modifiers;
+        self.send_packet(game, ToClientPacket::YourRoleState {
+            role_state: self.deref(game).role_state.clone(),
+            modifiers: self.deref(game).modifiers.clone()
+        });
+    }
+
+    /// How many more times this player can use a limited ability of their role
+    pub fn ability_charges(&self, game: &Game, ability: Ability) -> u8 {
+        game.ability_charges().charges(*self, ability).map_or(0, |charges| charges.remaining)
+    }
+    pub fn set_ability_charges(&self, game: &mut Game, ability: Ability, remaining: u8) {
+        AbilityCharges::set_remaining(game, *self, ability, remaining);
+    }
+    /// Uses up one charge of the ability. Returns false if there were none left
+    pub fn use_ability_charge(&self, game: &mut Game, ability: Ability) -> bool {
+        let remaining = self.ability_charges(game, ability);
+        if remaining == 0 {return false}
+        self.set_ability_charges(game, ability, remaining - 1);
+        true
     }
 
     pub fn alive(&self, game: &Game) -> bool{
@@ -259,6 +291,15 @@ impl PlayerReference{
     Night
     */
     
+    pub fn night_state(&self, game: &Game) -> PlayerNightState {
+        let player = self.deref(game);
+        PlayerNightState{
+            role_state: serde_json::to_value(&player.role_state).unwrap_or_default(),
+            alive: player.alive,
+            player_tags: player.player_tags.clone(),
+            night_variables: player.night_variables.clone(),
+        }
+    }
     pub fn night_died(&self, game: &Game) -> bool {
         self.deref(game).night_variables.died
     }
@@ -291,7 +332,7 @@ impl PlayerReference{
         if let Some(defense) = self.deref(game).night_variables.upgraded_defense {
             defense
         }else{
-            self.role(game).defense()
+            self.base_defense(game)
         }
     }
     pub fn set_night_upgraded_defense(&self, game: &mut Game, defense: Option<u8>){
@@ -306,6 +347,9 @@ impl PlayerReference{
     }
 
     pub fn night_appeared_visits<'a>(&self, game: &'a Game) -> &'a Option<Vec<Visit>>{
+        if self.has_modifier(game, RoleModifier::Blind) {
+            return &BLIND_APPEARED_VISITS;
+        }
         &self.deref(game).night_variables.appeared_visits
     }
     pub fn set_night_appeared_visits(&self, game: &mut Game, appeared_visits: Option<Vec<Visit>>){
@@ -400,8 +444,8 @@ impl PlayerReference{
             let mut message_sent = false;
             for chat_group in self.get_current_send_chat_groups(game){
                 match chat_group {
-                    ChatGroup::All | ChatGroup::Jail | ChatGroup::Interview | ChatGroup::Dead => {},
-                    ChatGroup::Mafia | ChatGroup::Cult  => {
+                    ChatGroup::All | ChatGroup::Jail | ChatGroup::Interview | ChatGroup::Dead | ChatGroup::Lovers | ChatGroup::Puppeteer => {},
+                    ChatGroup::Mafia | ChatGroup::Cult | ChatGroup::Fiends => {
                         game.add_message_to_chat_group(
                             chat_group,
                             ChatMessageVariant::JailedSomeone { player_index: self.index() }
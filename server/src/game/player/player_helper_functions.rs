@@ -2,15 +2,16 @@ use std::collections::{HashMap, HashSet};
 
 use crate::game::
 {
-    chat::{ChatGroup, ChatMessageVariant}, components::{arsonist_doused::ArsonistDoused, puppeteer_marionette::PuppeteerMarionette}, event::{before_role_switch::BeforeRoleSwitch, on_any_death::OnAnyDeath, on_role_switch::OnRoleSwitch}, grave::{Grave, GraveKiller, GraveReference}, resolution_state::ResolutionState, role::{same_evil_team, Priority, Role, RoleState}, visit::Visit, Game
+    chat::{ChatGroup, ChatMessageVariant}, components::{arsonist_doused::ArsonistDoused, night_trace::NightTrace, puppeteer_marionette::PuppeteerMarionette}, event::{before_role_switch::BeforeRoleSwitch, on_any_death::OnAnyDeath, on_role_switch::OnRoleSwitch}, grave::{Grave, GraveKiller, GraveReference}, resolution_state::ResolutionState, role::{same_evil_team, Priority, Role, RoleState}, role_modifier::RoleModifier, visit::Visit, win_condition::WinCondition, Game
 };
 
 use super::PlayerReference;
 
 impl PlayerReference{
     pub fn roleblock(&self, game: &mut Game, send_messages: bool) {
-        let roleblock_immune = self.role(game).roleblock_immune();
+        let roleblock_immune = self.role(game).roleblock_immune() || RoleModifier::lucky_roleblock_fails(game, *self);
 
+        NightTrace::on_roleblock(game, *self, roleblock_immune);
         if !roleblock_immune {
             self.set_night_roleblocked(game, true);
             self.set_night_visits(game, vec![]);
@@ -29,6 +30,7 @@ impl PlayerReference{
                 visitor.set_night_wardblocked(game, true);
                 visitor.set_night_visits(game, vec![]);
                 visitor.push_night_message(game, ChatMessageVariant::Wardblocked);
+                NightTrace::on_wardblock(game, *self, visitor);
                 wardblocked.push(visitor);
             }
         }
@@ -42,7 +44,9 @@ impl PlayerReference{
     pub fn try_night_kill(&self, attacker_ref: PlayerReference, game: &mut Game, grave_killer: GraveKiller, attack: u8, should_leave_death_note: bool) -> bool {
         self.set_night_attacked(game, true);
 
-        if self.night_defense(game) >= attack {
+        let defense = self.night_defense(game);
+        NightTrace::on_attack(game, Some(attacker_ref), *self, attack, defense);
+        if defense >= attack {
             self.push_night_message(game, ChatMessageVariant::YouSurvivedAttack);
             attacker_ref.push_night_message(game,ChatMessageVariant::SomeoneSurvivedYourAttack);
             return false;
@@ -68,7 +72,9 @@ impl PlayerReference{
     pub fn try_night_kill_anonymous(&self, game: &mut Game, grave_killer: GraveKiller, attack: u8) -> bool {
         self.set_night_attacked(game, true);
 
-        if self.night_defense(game) >= attack {
+        let defense = self.night_defense(game);
+        NightTrace::on_attack(game, None, *self, attack, defense);
+        if defense >= attack {
             self.push_night_message(game, ChatMessageVariant::YouSurvivedAttack);
             return false;
         }
@@ -162,6 +168,8 @@ impl PlayerReference{
     }
     pub fn die_return_event(&self, game: &mut Game, grave: Grave)->OnAnyDeath{
         self.set_alive(game, false);
+        // Otherwise a player shot during nomination would still be listed as nominating whoever they voted for
+        self.set_chosen_vote(game, None, false);
         self.add_private_chat_message(game, ChatMessageVariant::YouDied);
         game.add_grave(grave.clone());
 
@@ -237,7 +245,16 @@ impl PlayerReference{
         if game.current_phase().is_night() {
             self.night_defense(game)
         }else{
-            self.role(game).defense()
+            self.base_defense(game)
+        }
+    }
+    /// The defense of this player's role, with the host's role options and their modifiers
+    pub fn base_defense(&self, game: &Game) -> u8 {
+        let defense = game.settings.role_options.defense(self.role(game));
+        if self.has_modifier(game, RoleModifier::Bulletproof) {
+            defense + 1
+        }else{
+            defense
         }
     }
     pub fn possession_immune(&self, game: &Game) -> bool {
@@ -251,13 +268,16 @@ impl PlayerReference{
         self.night_framed(game) ||
         ArsonistDoused::has_suspicious_aura_douse(game, *self)
     }
+    pub fn win_condition(&self, game: &Game) -> WinCondition {
+        if PuppeteerMarionette::is_marionette(game, *self) {return WinCondition::wins_with(vec![ResolutionState::Fiends]);}
+        self.role_state(game).clone().win_condition(game, *self)
+    }
     pub fn required_resolution_states_for_win(&self, game: &Game) -> Option<HashSet<ResolutionState>> {
-        if PuppeteerMarionette::is_marionette(game, *self) {return Some(vec![ResolutionState::Fiends].into_iter().collect());}
-        ResolutionState::required_resolution_states_for_win(self.role(game))
+        self.win_condition(game).required_resolution_states_for_win().cloned()
     }
     pub fn keeps_game_running(&self, game: &Game) -> bool {
         if PuppeteerMarionette::is_marionette(game, *self) {return false;}
-        ResolutionState::keeps_game_running(self.role(game))
+        self.role_state(game).clone().keeps_game_running(game, *self)
     }
 
     /*
@@ -110,8 +110,18 @@ impl Serialize for PlayerReference {
 }
 impl<'a> Deserialize<'a> for PlayerReference {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: serde::Deserializer<'a> {
-        Ok(PlayerReference {
-            index: u8::deserialize(deserializer)?
-        })
+        // Player references are map keys in some packets, and JSON map keys are always strings
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum IndexOrKey {
+            Index(PlayerIndex),
+            Key(String),
+        }
+
+        let index = match IndexOrKey::deserialize(deserializer)? {
+            IndexOrKey::Index(index) => index,
+            IndexOrKey::Key(key) => key.parse().map_err(serde::de::Error::custom)?,
+        };
+        Ok(PlayerReference { index })
     }
 }
\ No newline at end of file
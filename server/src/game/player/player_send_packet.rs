@@ -1,7 +1,7 @@
 use std::time::Duration;
 
 use crate::{
-    client_connection::ClientConnection, game::{available_buttons::AvailableButtons, chat::ChatMessageVariant, phase::PhaseState, Game, GameOverReason}, lobby::GAME_DISCONNECT_TIMER_SECS, packet::ToClientPacket, websocket_connections::connection::ClientSender
+    client_connection::ClientConnection, game::{available_buttons::AvailableButtons, bot::Bot, chat::ChatMessageVariant, components::game_history::GameHistory, phase::{PauseReason, PhaseState}, replay::GameLogEntry, Game, GameOverReason}, lobby::GAME_DISCONNECT_TIMER_SECS, packet::ToClientPacket, websocket_connections::connection::ClientSender
 };
 
 use super::PlayerReference;
@@ -10,18 +10,24 @@ impl PlayerReference{
     pub fn connect(&self, game: &mut Game, sender: ClientSender){
         self.deref_mut(game).connection = ClientConnection::Connected(sender);
         self.send_join_game_data(game);
+        game.resume_if_everyone_reconnected();
     }
     pub fn lose_connection(&self, game: &mut Game){
         self.deref_mut(game).connection = ClientConnection::CouldReconnect { disconnect_timer: Duration::from_secs(GAME_DISCONNECT_TIMER_SECS) };
+        if game.settings.auto_pause && self.alive(game) {
+            game.pause(PauseReason::LostConnection);
+        }
     }
     pub fn quit(&self, game: &mut Game) {
         self.deref_mut(game).connection = ClientConnection::Disconnected;
+        game.log_entry(GameLogEntry::PlayerQuit{player: self.index()});
         if self.alive(game) {
             game.add_message_to_chat_group(
                 crate::game::chat::ChatGroup::All, 
                 ChatMessageVariant::PlayerQuit{player_index: self.index()}
             );
         }
+        game.resume_if_everyone_reconnected();
     }
 
     pub fn connection<'a>(&self, game: &'a Game) -> &'a ClientConnection {
@@ -36,6 +42,12 @@ impl PlayerReference{
     pub fn is_disconnected(&self, game: &Game) -> bool {
         matches!(self.deref(game).connection, ClientConnection::Disconnected)
     }
+    pub fn is_bot(&self, game: &Game) -> bool {
+        matches!(self.deref(game).connection, ClientConnection::Bot(_))
+    }
+    pub fn set_bot(&self, game: &mut Game, bot: Bot) {
+        self.deref_mut(game).connection = ClientConnection::Bot(bot);
+    }
 
     pub fn send_packet(&self, game: &Game, packet: ToClientPacket){
         self.deref(game).connection.send_packet(packet);
@@ -57,13 +69,15 @@ impl PlayerReference{
             },
             ToClientPacket::EnabledRoles { roles: game.settings.enabled_roles.clone().into_iter().collect() },
             ToClientPacket::RoleList {role_list: game.settings.role_list.clone()},
+            ToClientPacket::RoleOptions {role_options: game.settings.role_options.clone()},
             ToClientPacket::PlayerAlive{
                 alive: PlayerReference::all_players(game).map(|p|p.alive(game)).collect()
             }
         ]);
 
         if !game.ticking {
-            self.send_packet(game, ToClientPacket::GameOver { reason: GameOverReason::Draw })
+            self.send_packet(game, ToClientPacket::GameOver { reason: GameOverReason::Draw });
+            self.send_packet(game, ToClientPacket::GameSummary { summary: GameHistory::summary(game) });
         }
 
         if let PhaseState::Testimony { player_on_trial, .. }
@@ -89,7 +103,11 @@ impl PlayerReference{
                 player_index: self.index() 
             },
             ToClientPacket::YourRoleState {
-                role_state: self.role_state(game).clone()
+                role_state: self.role_state(game).clone(),
+                modifiers: self.modifiers(game).clone()
+            },
+            ToClientPacket::YourAbilityCharges {
+                charges: game.ability_charges().all_charges(*self)
             },
             ToClientPacket::YourRoleLabels { 
                 role_labels: PlayerReference::ref_map_to_index(self.role_label_map(game)) 
@@ -122,7 +140,8 @@ impl PlayerReference{
                 phase: game.current_phase().clone(),
                 day_number: game.phase_machine.day_number 
             },
-            ToClientPacket::PhaseTimeLeft { seconds_left: game.phase_machine.time_remaining.as_secs() },
+            ToClientPacket::PhaseTimeLeft { deadline: game.phase_deadline() },
+            ToClientPacket::GamePaused { paused: game.paused().is_some() },
             ToClientPacket::GameInitializationComplete
         ]);
     }
@@ -0,0 +1,160 @@
+use std::{fs, io::{Read, Write}, path::Path};
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use flate2::{read::DeflateDecoder, write::DeflateEncoder, Compression};
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::log;
+
+use super::{role::Role, role_list::CustomRoleSet, settings::Settings};
+
+/// Increase this whenever the settings format changes in a way that old presets can't be read
+pub const PRESET_VERSION: u32 = 1;
+pub const PRESET_DIRECTORY: &str = "./resources/presets";
+
+lazy_static!(
+    /// The presets every lobby can load, read from the preset directory when they're first needed
+    pub static ref SERVER_PRESETS: Vec<Preset> = Preset::load_directory(Path::new(PRESET_DIRECTORY));
+);
+
+/// Named lobby settings that can be loaded again later, or shared with other hosts as a code
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Preset {
+    pub name: String,
+    pub version: u32,
+    pub settings: Settings,
+}
+
+#[derive(Clone, Debug, Serialize, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum PresetError {
+    /// The code isn't compressed, base64 encoded JSON, or it's too long
+    InvalidCode,
+    /// The preset was made by a newer server
+    UnsupportedVersion{version: u32},
+    /// The preset uses a role this server doesn't have
+    UnknownRole{role: String},
+    /// The preset is missing settings, or they can't be used in a game
+    InvalidSettings,
+    /// There's no server preset with this name
+    NotFound,
+}
+
+impl Preset {
+    pub const MAX_NAME_LENGTH: usize = 30;
+    /// Keeps players from sending huge codes, or codes that decompress to huge settings
+    pub const MAX_CODE_LENGTH: usize = 10_000;
+    const MAX_JSON_LENGTH: u64 = 100_000;
+
+    /// The seed is left out, so every game made from the preset is different
+    pub fn new(name: String, settings: &Settings) -> Self {
+        Self {
+            name: name.trim().chars().take(Self::MAX_NAME_LENGTH).collect(),
+            version: PRESET_VERSION,
+            settings: Settings { seed: None, ..settings.clone() },
+        }
+    }
+    pub fn find_server_preset(name: &str) -> Result<Self, PresetError> {
+        SERVER_PRESETS.iter().find(|preset| preset.name == name).cloned().ok_or(PresetError::NotFound)
+    }
+
+    /// Deflates the preset and encodes it with URL safe base64, so it can be pasted anywhere
+    pub fn to_code(&self) -> String {
+        let json = serde_json::to_vec(self).expect("Presets can always be serialized");
+
+        let mut encoder = DeflateEncoder::new(Vec::new(), Compression::best());
+        encoder.write_all(&json).expect("Writing to a Vec can't fail");
+        URL_SAFE_NO_PAD.encode(encoder.finish().expect("Writing to a Vec can't fail"))
+    }
+    pub fn from_code(code: &str) -> Result<Self, PresetError> {
+        let code = code.trim();
+        if code.len() > Self::MAX_CODE_LENGTH {
+            return Err(PresetError::InvalidCode);
+        }
+
+        let compressed = URL_SAFE_NO_PAD.decode(code).map_err(|_| PresetError::InvalidCode)?;
+        let mut json = String::new();
+        DeflateDecoder::new(compressed.as_slice())
+            .take(Self::MAX_JSON_LENGTH)
+            .read_to_string(&mut json)
+            .map_err(|_| PresetError::InvalidCode)?;
+
+        Self::from_json(&json)
+    }
+    /// Checks the version and every role before reading the settings,
+    /// so presets from other servers say what's wrong with them
+    pub fn from_json(json: &str) -> Result<Self, PresetError> {
+        let value: Value = serde_json::from_str(json).map_err(|_| PresetError::InvalidCode)?;
+
+        let Some(version) = value.get("version").and_then(Value::as_u64) else {
+            return Err(PresetError::InvalidSettings);
+        };
+        if version > PRESET_VERSION as u64 {
+            return Err(PresetError::UnsupportedVersion { version: version as u32 });
+        }
+        if let Some(role) = Self::find_unknown_role(&value) {
+            return Err(PresetError::UnknownRole { role });
+        }
+
+        let mut preset: Self = serde_json::from_value(value).map_err(|_| PresetError::InvalidSettings)?;
+        if
+            !preset.settings.rules.is_valid() ||
+            preset.settings.phase_times.game_ends_instantly() ||
+            preset.settings.role_options.check().is_err()
+        {
+            return Err(PresetError::InvalidSettings);
+        }
+
+        preset.name = preset.name.trim().chars().take(Self::MAX_NAME_LENGTH).collect();
+        preset.settings.seed = None;
+        preset.settings.custom_role_sets = CustomRoleSet::sanitize_all(
+            preset.settings.custom_role_sets.iter().chain(preset.settings.role_list.custom_role_sets()).cloned().collect()
+        );
+        preset.settings.role_list.update_custom_role_sets(&preset.settings.custom_role_sets);
+        Ok(preset)
+    }
+    /// Looks through every field that holds roles, anywhere in the settings
+    fn find_unknown_role(value: &Value) -> Option<String> {
+        let is_unknown = |value: &Value| match value {
+            Value::String(role) => serde_json::from_value::<Role>(value.clone()).is_err().then(|| role.clone()),
+            _ => None,
+        };
+
+        match value {
+            Value::Object(fields) => fields.iter().find_map(|(key, value)| match (key.as_str(), value) {
+                ("role" | "requiredRole", value) => is_unknown(value),
+                ("roles" | "enabledRoles", Value::Array(roles)) => roles.iter().find_map(is_unknown),
+                ("roleOptions", Value::Object(options)) => options.keys().find_map(|role| is_unknown(&Value::String(role.clone()))),
+                (_, value) => Self::find_unknown_role(value),
+            }),
+            Value::Array(values) => values.iter().find_map(Self::find_unknown_role),
+            _ => None,
+        }
+    }
+
+    /// Reads every preset in the directory. Presets that can't be read are logged and skipped
+    pub fn load_directory(directory: &Path) -> Vec<Self> {
+        let Ok(entries) = fs::read_dir(directory) else {return Vec::new()};
+
+        let mut paths: Vec<_> = entries.filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|extension| extension == "json"))
+            .collect();
+        paths.sort();
+
+        paths.into_iter().filter_map(|path| {
+            match fs::read_to_string(&path).map_err(|err| format!("{err}"))
+                .and_then(|json| Self::from_json(&json).map_err(|err| format!("{err:?}")))
+            {
+                Ok(preset) => Some(preset),
+                Err(err) => {
+                    log!(error "Preset"; "Failed to read preset {}: {}", path.display(), err);
+                    None
+                }
+            }
+        }).collect()
+    }
+}
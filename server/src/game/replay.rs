@@ -0,0 +1,243 @@
+use std::{fs, io, path::{Path, PathBuf}, time::Duration};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{client_connection::ClientConnection, lobby::GAME_DISCONNECT_TIMER_SECS, packet::ToServerPacket};
+
+use super::{
+    chat::ChatMessageVariant,
+    event::{on_fast_forward::OnFastForward, on_game_ending::OnGameEnding},
+    grave::Grave,
+    phase::{PauseReason, PhaseState, PhaseStateMachine},
+    player::{PlayerIndex, PlayerInitializeParameters, PlayerReference},
+    role::Role,
+    settings::Settings,
+    spectator::spectator_pointer::SpectatorIndex,
+    Game,
+    RejectStartReason
+};
+
+/// Increase this whenever the replay format changes in a way that old replays can't be read
+pub const REPLAY_VERSION: u32 = 2;
+pub const REPLAY_DIRECTORY: &str = "./replays";
+
+/// Something that happened in the game, in the order it happened
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum GameLogEntry {
+    #[serde(rename_all = "camelCase")]
+    PlayerPacket{player: PlayerIndex, packet: ToServerPacket},
+    #[serde(rename_all = "camelCase")]
+    SpectatorPacket{spectator: SpectatorIndex, packet: ToServerPacket},
+    #[serde(rename_all = "camelCase")]
+    PhaseStart{phase: PhaseState, day_number: u8},
+    #[serde(rename_all = "camelCase")]
+    PlayerQuit{player: PlayerIndex},
+    FastForward,
+    #[serde(rename_all = "camelCase")]
+    Paused{reason: PauseReason},
+    Resumed,
+    GameEnded,
+}
+
+/// Everything needed to rebuild a game after it has ended.
+/// The same seed, settings and log will always produce the same game.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Replay {
+    pub version: u32,
+    pub seed: u64,
+    pub settings: Settings,
+    pub player_names: Vec<String>,
+    pub starting_roles: Vec<Role>,
+    pub log: Vec<GameLogEntry>,
+    /// What the game looked like when the replay was saved
+    pub outcome: ReplayOutcome,
+}
+
+/// The parts of a finished game that a replay is checked against
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayOutcome {
+    pub graves: Vec<Grave>,
+    /// Every chat message each player received, indexed by player
+    pub chat_messages: Vec<Vec<ChatMessageVariant>>,
+    pub winners: Vec<PlayerIndex>,
+}
+
+/// The first way a re-simulated game differed from its replay
+#[derive(Debug)]
+pub enum ReplayMismatch {
+    CouldNotStart(RejectStartReason),
+    StartingRoles{expected: Vec<Role>, actual: Vec<Role>},
+    Log{index: usize, expected: Option<GameLogEntry>, actual: Option<GameLogEntry>},
+    Graves{expected: Vec<Grave>, actual: Vec<Grave>},
+    ChatMessage{player: PlayerIndex, index: usize, expected: Option<ChatMessageVariant>, actual: Option<ChatMessageVariant>},
+    Winners{expected: Vec<PlayerIndex>, actual: Vec<PlayerIndex>},
+}
+
+impl Game {
+    pub fn log_entry(&mut self, entry: GameLogEntry){
+        self.game_log.push(entry);
+    }
+    pub fn game_log(&self) -> &Vec<GameLogEntry> {
+        &self.game_log
+    }
+}
+
+impl Replay {
+    pub fn from_game(game: &Game) -> Self {
+        Self {
+            version: REPLAY_VERSION,
+            seed: game.seed,
+            settings: Settings {
+                seed: Some(game.seed),
+                ..game.settings.clone()
+            },
+            player_names: PlayerReference::all_players(game).map(|p|p.name(game).clone()).collect(),
+            starting_roles: Self::starting_roles(game),
+            log: game.game_log().clone(),
+            outcome: ReplayOutcome::from_game(game),
+        }
+    }
+    fn starting_roles(game: &Game) -> Vec<Role> {
+        let mut starting_roles: Vec<(Role, PlayerReference)> = game.roles_to_players.clone();
+        starting_roles.sort_by_key(|(_, player_ref)| *player_ref);
+        starting_roles.into_iter().map(|(role, _)| role).collect()
+    }
+
+    /// Writes this replay to the replay directory as JSON, returning the path of the new file
+    pub fn save(&self, name: &str) -> io::Result<PathBuf> {
+        fs::create_dir_all(REPLAY_DIRECTORY)?;
+
+        let path = PathBuf::from(REPLAY_DIRECTORY).join(format!("{name}.json"));
+        fs::write(&path, serde_json::to_string(self)?)?;
+        Ok(path)
+    }
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let replay: Self = serde_json::from_str(&fs::read_to_string(path)?)?;
+
+        if replay.version != REPLAY_VERSION {
+            return Err(io::Error::new(io::ErrorKind::InvalidData,
+                format!("Replay version {} is not supported, expected version {}", replay.version, REPLAY_VERSION)
+            ));
+        }
+        Ok(replay)
+    }
+
+    /// Rebuilds the game with mock players and feeds it every recorded packet and phase change.
+    /// Nothing is sent anywhere, and the game is never ticked.
+    pub fn simulate(&self) -> Result<Game, RejectStartReason> {
+        let players = self.player_names.iter().map(|name| PlayerInitializeParameters{
+            connection: ClientConnection::CouldReconnect { disconnect_timer: Duration::from_secs(GAME_DISCONNECT_TIMER_SECS) },
+            name: name.clone(),
+            host: false,
+        }).collect();
+
+        let mut game = Game::new_with_any_connection(self.settings.clone(), players, Vec::new())?;
+
+        for (index, entry) in self.log.iter().enumerate() {
+            // This entry was caused by an earlier one (ex: a vote starting a trial), so it already happened
+            if index < game.game_log().len() {
+                continue;
+            }
+
+            match entry.clone() {
+                GameLogEntry::PlayerPacket { player, packet } => {
+                    game.on_client_message(player, packet);
+                },
+                // Spectators are not part of the replay. The only thing they can do is fast forward, which is logged on its own
+                GameLogEntry::SpectatorPacket { .. } => {
+                    game.log_entry(entry.clone());
+                },
+                GameLogEntry::PhaseStart { .. } => {
+                    PhaseStateMachine::next_phase(&mut game, None);
+                },
+                GameLogEntry::PlayerQuit { player } => {
+                    if let Ok(player_ref) = PlayerReference::new(&game, player) {
+                        player_ref.quit(&mut game);
+                    }
+                },
+                GameLogEntry::FastForward => {
+                    OnFastForward::invoke(&mut game);
+                },
+                GameLogEntry::Paused { reason } => {
+                    game.pause(reason);
+                },
+                GameLogEntry::Resumed => {
+                    game.resume();
+                },
+                GameLogEntry::GameEnded => {
+                    game.log_entry(GameLogEntry::GameEnded);
+                    OnGameEnding::invoke(&mut game);
+                },
+            }
+        }
+
+        Ok(game)
+    }
+
+    /// Re-simulates the game and checks that it plays out exactly as it did when it was recorded
+    pub fn verify(&self) -> Result<(), Box<ReplayMismatch>> {
+        let game = self.simulate().map_err(|reason| Box::new(ReplayMismatch::CouldNotStart(reason)))?;
+
+        let starting_roles = Self::starting_roles(&game);
+        if starting_roles != self.starting_roles {
+            return Err(Box::new(ReplayMismatch::StartingRoles { expected: self.starting_roles.clone(), actual: starting_roles }));
+        }
+
+        if let Some(index) = first_difference(&self.log, game.game_log()) {
+            return Err(Box::new(ReplayMismatch::Log {
+                index,
+                expected: self.log.get(index).cloned(),
+                actual: game.game_log().get(index).cloned()
+            }));
+        }
+
+        let outcome = ReplayOutcome::from_game(&game);
+
+        if outcome.graves != self.outcome.graves {
+            return Err(Box::new(ReplayMismatch::Graves { expected: self.outcome.graves.clone(), actual: outcome.graves }));
+        }
+        for (player, (expected, actual)) in self.outcome.chat_messages.iter().zip(outcome.chat_messages.iter()).enumerate() {
+            if let Some(index) = first_difference(expected, actual) {
+                return Err(Box::new(ReplayMismatch::ChatMessage {
+                    player: player as PlayerIndex,
+                    index,
+                    expected: expected.get(index).cloned(),
+                    actual: actual.get(index).cloned()
+                }));
+            }
+        }
+        if outcome.winners != self.outcome.winners {
+            return Err(Box::new(ReplayMismatch::Winners { expected: self.outcome.winners.clone(), actual: outcome.winners }));
+        }
+
+        Ok(())
+    }
+}
+
+impl ReplayOutcome {
+    pub fn from_game(game: &Game) -> Self {
+        Self {
+            graves: game.graves.clone(),
+            chat_messages: PlayerReference::all_players(game)
+                .map(|p|p.deref(game).chat_messages.iter().map(|m|m.variant.clone()).collect())
+                .collect(),
+            winners: PlayerReference::all_players(game)
+                .filter(|p|p.get_won_game(game))
+                .map(|p|p.index())
+                .collect(),
+        }
+    }
+}
+
+fn first_difference<T: PartialEq>(expected: &[T], actual: &[T]) -> Option<usize> {
+    expected.iter().zip(actual.iter())
+        .position(|(expected, actual)| expected != actual)
+        .or(if expected.len() != actual.len() {
+            Some(expected.len().min(actual.len()))
+        } else {
+            None
+        })
+}
@@ -1,8 +1,9 @@
-use std::collections::HashSet;
+use serde::Serialize;
 
-use super::{player::PlayerReference, role::Role, role_list::Faction, Game};
+use super::{player::PlayerReference, win_condition::WinCondition, Game};
 
-#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub enum ResolutionState {
     Town,
     Mafia,
@@ -30,16 +31,12 @@ impl ResolutionState {
     ///either return Some(EndGameCondition) or None (if the game is not over yet)
     pub fn game_is_over(game: &Game)->Option<ResolutionState> {
 
-        //Special wildcard case
-        let living_roles = PlayerReference::all_players(game).filter_map(|player|{
-            if player.alive(game){
-                Some(player.role(game))
-            }else{
-                None
-            }
-        }).collect::<Vec<_>>();
+        let living_players = PlayerReference::all_players(game)
+            .filter(|player|player.alive(game))
+            .collect::<Vec<_>>();
 
-        if living_roles.iter().all(|role|matches!(role, Role::Wildcard|Role::TrueWildcard)) && living_roles.len() > 1 {
+        //nobody left has decided how they want to win yet
+        if living_players.len() > 1 && living_players.iter().all(|player|player.win_condition(game) == WinCondition::Undecided) {
             return None;
         }
         
@@ -52,16 +49,9 @@ impl ResolutionState {
         for end_game_condition in ResolutionState::all() {
             //if everyone who keeps the game running agrees on this end game condition, return it
             if
-                PlayerReference::all_players(game)
-                    .filter(|p|p.alive(game))
+                living_players.iter()
                     .filter(|p|p.keeps_game_running(game))
-                    .all(|p|
-                        if let Some(set) = p.required_resolution_states_for_win(game){
-                            set.contains(&end_game_condition)
-                        }else{
-                            true
-                        }
-                    )
+                    .all(|p|p.win_condition(game).can_win_with(&end_game_condition))
             {
                 return Some(end_game_condition);
             }
@@ -71,12 +61,7 @@ impl ResolutionState {
     }
     
     pub fn can_win_with(game: &Game, player: PlayerReference, resolution_state: ResolutionState)->bool{
-        if let Some(set) = player.required_resolution_states_for_win(game){
-            set.contains(&resolution_state)
-        }else{
-            true
-        }
-        
+        player.win_condition(game).can_win_with(&resolution_state)
     }
     pub fn requires_only_this_resolution_state(game: &Game, player: PlayerReference, resolution_state: ResolutionState)->bool{
         if let Some(set) = player.required_resolution_states_for_win(game) {
@@ -95,40 +80,6 @@ impl ResolutionState {
             _ => true
         }
     }
-    
-
-    ///this role wins if the end game state is in this list
-    pub fn required_resolution_states_for_win(role: Role)->Option<HashSet<ResolutionState>>{
-        Some(match role.faction(){
-            Faction::Mafia => vec![ResolutionState::Mafia],
-            Faction::Cult => vec![ResolutionState::Cult],
-            Faction::Town => vec![ResolutionState::Town],
-            Faction::Fiends => vec![ResolutionState::Fiends],
-            Faction::Neutral => match role {
-                Role::Minion | Role::Scarecrow => {
-                    ResolutionState::all().into_iter().filter(|end_game_condition|
-                        match end_game_condition {
-                            ResolutionState::Town | ResolutionState::Draw => false,
-                            _ => true
-                        }
-                    ).collect()
-                },
-                Role::Politician => vec![ResolutionState::Politician],
-                _ => {return None;}
-            },
-        }.into_iter().collect())
-    }
-    ///Town, Mafia, Cult, NK
-    /// Is either town, or has the ability to consistently kill till the end of the game
-    /// *has the ability to change what the set of living players win conditions are until game over (convert, marionette, kill)*
-    /// A detective and a minion game never ends
-    pub fn keeps_game_running(role: Role)->bool{
-        if role.faction() == Faction::Neutral{
-            false
-        }else{
-            true  
-        }
-    }
 }
 
 
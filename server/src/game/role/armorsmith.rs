@@ -1,8 +1,8 @@
-use rand::thread_rng;
 use rand::seq::SliceRandom;
 use serde::Serialize;
 
 use crate::game::chat::ChatMessageVariant;
+use crate::game::components::ability_charges::Ability;
 use crate::game::phase::PhaseType;
 use crate::game::player::PlayerReference;
 use crate::game::role_list::Faction;
@@ -11,26 +11,14 @@ use crate::game::visit::Visit;
 use crate::game::Game;
 use super::{Priority, RoleState, RoleStateImpl};
 
-#[derive(Clone, Serialize, Debug)]
+#[derive(Clone, Serialize, Debug, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct Armorsmith {
-    open_shops_remaining: u8,
     night_open_shop: bool,
     night_protected_players: Vec<PlayerReference>,
     players_armor: Vec<PlayerReference>
 }
 
-impl Default for Armorsmith {
-    fn default() -> Self {
-        Self { 
-            open_shops_remaining: 2,
-            night_open_shop: false,
-            night_protected_players: Vec::new(),
-            players_armor: Vec::new()
-        }
-    }
-}
-
 pub(super) const FACTION: Faction = Faction::Town;
 pub(super) const MAXIMUM_COUNT: Option<u8> = None;
 pub(super) const DEFENSE: u8 = 0;
@@ -41,10 +29,9 @@ impl RoleStateImpl for Armorsmith {
             Priority::Armorsmith => {
 
                 if let Some(_) = actor_ref.night_visits(game).first(){
-                    if self.open_shops_remaining > 0 {
+                    if actor_ref.use_ability_charge(game, Ability::OpenShop) {
                         actor_ref.set_role_state(game, RoleState::Armorsmith(
                             Armorsmith {
-                                open_shops_remaining: self.open_shops_remaining.saturating_sub(1),
                                 night_open_shop: true,
                                 night_protected_players: Vec::new(),
                                 ..self
@@ -68,7 +55,7 @@ impl RoleStateImpl for Armorsmith {
                         visitor.increase_defense_to(game, 2);
                     }
 
-                    if let Some(random_visitor) = visitors.choose(&mut thread_rng()) {
+                    if let Some(random_visitor) = visitors.choose(&mut game.rng) {
                         self.players_armor.push(random_visitor.clone());
                     }
 
@@ -106,7 +93,7 @@ impl RoleStateImpl for Armorsmith {
     }
     fn can_select(self, game: &Game, actor_ref: PlayerReference, target_ref: PlayerReference) -> bool {
         actor_ref == target_ref &&
-        self.open_shops_remaining > 0 &&
+        actor_ref.ability_charges(game, Ability::OpenShop) > 0 &&
         !actor_ref.night_jailed(game) &&
         actor_ref.selection(game).is_empty() &&
         actor_ref.alive(game)
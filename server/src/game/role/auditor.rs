@@ -1,4 +1,4 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::game::chat::ChatMessageVariant;
 use crate::game::phase::PhaseType;
@@ -18,7 +18,7 @@ pub struct Auditor{
     pub previously_given_results: Vec<(u8, AuditorResult)>,
 }
 
-#[derive(Clone, Debug, Serialize, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
 #[serde(rename_all = "camelCase")]
 #[serde(tag = "type")]
 pub enum AuditorResult{
@@ -58,12 +58,12 @@ impl RoleStateImpl for Auditor {
                 .filter(|x|game.settings.enabled_roles.contains(x))
                 .filter(|x|*x != role)
                 .collect::<Vec<Role>>()
-                .choose(&mut rand::thread_rng())
+                .choose(&mut game.rng)
                 .cloned();
 
             if let Some(fake_role) = fake_role{
                 let mut two = [role, fake_role];
-                two.shuffle(&mut rand::thread_rng());
+                two.shuffle(&mut game.rng);
                 AuditorResult::Two{roles: [two[0], two[1]]}
             } else {
                 AuditorResult::One{role}
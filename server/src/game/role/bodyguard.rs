@@ -2,6 +2,7 @@
 use serde::Serialize;
 
 use crate::game::chat::ChatMessageVariant;
+use crate::game::components::ability_charges::Ability;
 use crate::game::grave::GraveKiller;
 use crate::game::phase::PhaseType;
 use crate::game::player::PlayerReference;
@@ -11,24 +12,13 @@ use crate::game::visit::Visit;
 use crate::game::Game;
 use super::{Priority, RoleState, Role, RoleStateImpl};
 
-#[derive(Clone, Debug, Serialize)]
+#[derive(Clone, Debug, Serialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct Bodyguard {
-    self_shields_remaining: u8,
     target_protected_ref: Option<PlayerReference>,
     redirected_player_refs: Vec<PlayerReference>
 }
 
-impl Default for Bodyguard {
-    fn default() -> Self {
-        Self { 
-            self_shields_remaining: 1, 
-            target_protected_ref: None, 
-            redirected_player_refs: Vec::new()
-        }
-    }
-}
-
 pub(super) const FACTION: Faction = Faction::Town;
 pub(super) const MAXIMUM_COUNT: Option<u8> = None;
 pub(super) const DEFENSE: u8 = 0;
@@ -58,7 +48,6 @@ impl RoleStateImpl for Bodyguard {
                 }
 
                 actor_ref.set_role_state(game, RoleState::Bodyguard(Bodyguard {
-                    self_shields_remaining: self.self_shields_remaining, 
                     target_protected_ref, 
                     redirected_player_refs
                 }));
@@ -69,13 +58,7 @@ impl RoleStateImpl for Bodyguard {
                 let target_ref = visit.target;
     
                 if actor_ref == target_ref {
-                    let self_shields_remaining = self.self_shields_remaining - 1;
-                    actor_ref.set_role_state(game, RoleState::Bodyguard(Bodyguard{
-                        self_shields_remaining, 
-                        ..self
-                    }));
-                    
-                    
+                    actor_ref.use_ability_charge(game, Ability::SelfShield);
                     target_ref.increase_defense_to(game, 2);
                 }
             },
@@ -95,7 +78,7 @@ impl RoleStateImpl for Bodyguard {
     }
     fn can_select(self, game: &Game, actor_ref: PlayerReference, target_ref: PlayerReference) -> bool {
         game.day_number() > 1 &&
-        (actor_ref != target_ref || self.self_shields_remaining > 0) &&
+        (actor_ref != target_ref || actor_ref.ability_charges(game, Ability::SelfShield) > 0) &&
         !actor_ref.night_jailed(game) &&
         actor_ref.selection(game).is_empty() &&
         actor_ref.alive(game) &&
@@ -107,6 +90,6 @@ impl RoleStateImpl for Bodyguard {
     fn on_phase_start(self, game: &mut Game, actor_ref: PlayerReference, _phase: PhaseType){
         let redirected_player_refs = Vec::new();
         let target_protected_ref = None;
-        actor_ref.set_role_state(game, RoleState::Bodyguard(Bodyguard { self_shields_remaining: self.self_shields_remaining, redirected_player_refs, target_protected_ref }));
+        actor_ref.set_role_state(game, RoleState::Bodyguard(Bodyguard { redirected_player_refs, target_protected_ref }));
     }
 }
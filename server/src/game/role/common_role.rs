@@ -1,6 +1,6 @@
 use std::collections::HashSet;
 
-use crate::game::{chat::ChatGroup, player::PlayerReference, Game, visit::Visit, role_list::Faction, phase::{PhaseState, PhaseType}, resolution_state::ResolutionState};
+use crate::game::{chat::{ChatGroup, TeamChat}, player::PlayerReference, Game, visit::Visit, role_list::Faction, phase::{PhaseState, PhaseType}, resolution_state::ResolutionState, win_condition::WinCondition};
 
 use super::{journalist::Journalist, medium::Medium, same_evil_team, RoleState};
 
@@ -77,19 +77,8 @@ pub(super) fn get_current_send_chat_groups(game: &Game, actor_ref: PlayerReferen
             let mut jail_or_night_chats = if actor_ref.night_jailed(game){
                 vec![ChatGroup::Jail]
             }else{
-                match actor_ref.role(game).faction() {
-                    Faction::Mafia => {
-                        night_chat_groups.push(ChatGroup::Mafia);
-                        night_chat_groups
-                    },
-                    Faction::Cult => {
-                        night_chat_groups.push(ChatGroup::Cult);
-                        night_chat_groups
-                    },
-                    _ => {
-                        night_chat_groups
-                    }
-                }
+                night_chat_groups.append(&mut TeamChat::team_chat_groups(game, actor_ref));
+                night_chat_groups
             };
 
 
@@ -107,12 +96,7 @@ pub(super) fn get_current_receive_chat_groups(game: &Game, actor_ref: PlayerRefe
         out.push(ChatGroup::Dead);
     }
 
-    if actor_ref.role(game).faction() == Faction::Mafia {
-        out.push(ChatGroup::Mafia);
-    }
-    if actor_ref.role(game).faction() == Faction::Cult {
-        out.push(ChatGroup::Cult);
-    }
+    out.append(&mut TeamChat::team_chat_groups(game, actor_ref));
     if actor_ref.night_jailed(game){
         out.push(ChatGroup::Jail);
     }
@@ -151,8 +135,16 @@ pub(super) fn get_current_receive_chat_groups(game: &Game, actor_ref: PlayerRefe
 ///Only works for roles that win based on end game condition
 pub(super) fn get_won_game(game: &Game, actor_ref: PlayerReference) -> bool {
     if let Some(end_game_condition) = ResolutionState::game_is_over(game) {
-        ResolutionState::can_win_with(game, actor_ref, end_game_condition)
+        actor_ref.win_condition(game).won(game, actor_ref, &end_game_condition)
     } else {
         false
     }
+}
+
+pub(super) fn win_condition(game: &Game, actor_ref: PlayerReference) -> WinCondition {
+    WinCondition::wins_with_faction(actor_ref.role(game).faction())
+}
+
+pub(super) fn keeps_game_running(game: &Game, actor_ref: PlayerReference) -> bool {
+    actor_ref.role(game).faction() != Faction::Neutral
 }
\ No newline at end of file
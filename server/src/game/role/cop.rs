@@ -53,7 +53,7 @@ impl RoleStateImpl for Cop {
                             .iter()
                             .any(|v|v.target==target_ref)
                     ).collect::<Vec<PlayerReference>>()
-                    .choose(&mut rand::thread_rng())
+                    .choose(&mut game.rng)
                     .copied(){
                     player_to_attack = Some(non_town_visitor);
                 }else if let Some(town_visitor) = PlayerReference::all_players(game)
@@ -64,7 +64,7 @@ impl RoleStateImpl for Cop {
                             .iter()
                             .any(|v|v.target==target_ref)
                     ).collect::<Vec<PlayerReference>>()
-                    .choose(&mut rand::thread_rng())
+                    .choose(&mut game.rng)
                     .copied(){
                     player_to_attack = Some(town_visitor)
                 }
@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 
 use crate::game::chat::{ChatGroup, ChatMessageVariant};
+use crate::game::components::ability_charges::Ability;
 use crate::game::grave::GraveKiller;
 use crate::game::phase::PhaseType;
 use crate::game::player::PlayerReference;
@@ -19,7 +20,6 @@ pub struct Counterfeiter{
     
     pub fake_role: Role,
     pub fake_will: String,
-    pub forges_remaining: u8,
     pub forged_ref: Option<PlayerReference>,
 
     pub action: CounterfeiterAction
@@ -35,7 +35,6 @@ impl Default for Counterfeiter {
         Counterfeiter {
             backup: None,
 
-            forges_remaining: 3,
             forged_ref: None,
             fake_role: Role::Jester,
             fake_will: "".to_owned(),
@@ -80,15 +79,15 @@ impl RoleStateImpl for Counterfeiter {
         }else{
             match priority {
                 Priority::Deception => {
-                    if self.forges_remaining == 0 || self.action == CounterfeiterAction::NoForge {return}
+                    if actor_ref.ability_charges(game, Ability::Forge) == 0 || self.action == CounterfeiterAction::NoForge {return}
 
                     let Some(visit) = actor_ref.night_visits(game).first() else{return};
                     let target_ref = visit.target;
     
                     target_ref.set_night_grave_role(game, Some(self.fake_role));
                     target_ref.set_night_grave_will(game, self.fake_will.clone());
+                    actor_ref.use_ability_charge(game, Ability::Forge);
                     actor_ref.set_role_state(game, RoleState::Counterfeiter(Counterfeiter { 
-                        forges_remaining: self.forges_remaining - 1, 
                         forged_ref: Some(target_ref), 
                         ..self
                     }));
@@ -183,8 +182,9 @@ impl RoleStateImpl for Counterfeiter {
             
             if !backup.alive(game){return}
 
-            //convert backup to Counterfeiter
+            //convert backup to Counterfeiter, they only get the forges that are left
             backup.set_role(game, RoleState::Counterfeiter(Counterfeiter{backup: None, ..self}));
+            backup.set_ability_charges(game, Ability::Forge, actor_ref.ability_charges(game, Ability::Forge));
         }
         else if self.backup.is_some_and(|p|p == dead_player_ref) {
             actor_ref.set_role_state(game, RoleState::Counterfeiter(Counterfeiter{backup: None, ..self}));
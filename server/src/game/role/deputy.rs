@@ -2,6 +2,7 @@
 use serde::Serialize;
 
 use crate::game::chat::{ChatGroup, ChatMessageVariant};
+use crate::game::components::ability_charges::Ability;
 use crate::game::resolution_state::ResolutionState;
 use crate::game::grave::{Grave, GraveDeathCause, GraveInformation, GraveKiller};
 use crate::game::phase::PhaseType;
@@ -9,21 +10,13 @@ use crate::game::player::PlayerReference;
 use crate::game::role_list::Faction;
 
 use crate::game::Game;
-use super::{RoleStateImpl, Role, RoleState};
+use super::{RoleStateImpl, Role};
 
 
 
 
-#[derive(Clone, Debug, Serialize)]
-#[serde(rename_all = "camelCase")]
-pub struct Deputy {
-    bullets_remaining: u8,
-}
-impl Default for Deputy {
-    fn default() -> Self {
-        Self { bullets_remaining: 1 }
-    }
-}
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct Deputy;
 
 pub(super) const FACTION: Faction = Faction::Town;
 pub(super) const MAXIMUM_COUNT: Option<u8> = Some(1);
@@ -53,12 +46,12 @@ impl RoleStateImpl for Deputy {
             }
         }
 
-        actor_ref.set_role_state(game, RoleState::Deputy(Deputy{bullets_remaining:self.bullets_remaining-1}));
+        actor_ref.use_ability_charge(game, Ability::Shoot);
     }
     fn can_day_target(self, game: &Game, actor_ref: PlayerReference, target_ref: PlayerReference) -> bool {
         game.current_phase().is_day() &&
         game.phase_machine.day_number > 1 &&
-        self.bullets_remaining > 0 &&
+        actor_ref.ability_charges(game, Ability::Shoot) > 0 &&
         actor_ref != target_ref &&
         target_ref.alive(game) && actor_ref.alive(game) &&
         (PhaseType::Discussion == game.current_phase().phase() || PhaseType::Nomination == game.current_phase().phase())
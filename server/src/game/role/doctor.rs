@@ -2,6 +2,7 @@
 use serde::Serialize;
 
 use crate::game::chat::ChatMessageVariant;
+use crate::game::components::ability_charges::Ability;
 use crate::game::phase::PhaseType;
 use crate::game::player::PlayerReference;
 use crate::game::role_list::Faction;
@@ -10,22 +11,12 @@ use crate::game::visit::Visit;
 use crate::game::Game;
 use super::{Priority, RoleState, RoleStateImpl};
 
-#[derive(Clone, Serialize, Debug)]
+#[derive(Clone, Serialize, Debug, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct Doctor {
-    self_heals_remaining: u8,
     target_healed_ref: Option<PlayerReference>
 }
 
-impl Default for Doctor {
-    fn default() -> Self {
-        Self { 
-            self_heals_remaining: 1,
-            target_healed_ref: None
-        }
-    }
-}
-
 pub(super) const FACTION: Faction = Faction::Town;
 pub(super) const MAXIMUM_COUNT: Option<u8> = None;
 pub(super) const DEFENSE: u8 = 0;
@@ -36,7 +27,6 @@ impl RoleStateImpl for Doctor {
             Priority::TopPriority => {
                 actor_ref.set_role_state(game, RoleState::Doctor(
                     Doctor {
-                        self_heals_remaining: self.self_heals_remaining, 
                         target_healed_ref: None
                     }
                 ));
@@ -48,16 +38,11 @@ impl RoleStateImpl for Doctor {
                 target_ref.increase_defense_to(game, 2);
 
                 if actor_ref == target_ref{
-                    actor_ref.set_role_state(game, RoleState::Doctor(Doctor{
-                        self_heals_remaining: self.self_heals_remaining.saturating_sub(1), 
-                        target_healed_ref: Some(target_ref)
-                    }));
-                }else{
-                    actor_ref.set_role_state(game, RoleState::Doctor(Doctor{
-                        target_healed_ref: Some(target_ref),
-                        ..self
-                    }));
+                    actor_ref.use_ability_charge(game, Ability::SelfHeal);
                 }
+                actor_ref.set_role_state(game, RoleState::Doctor(Doctor{
+                    target_healed_ref: Some(target_ref)
+                }));
 
             }
             Priority::Investigative => {
@@ -74,7 +59,7 @@ impl RoleStateImpl for Doctor {
     }
     fn can_select(self, game: &Game, actor_ref: PlayerReference, target_ref: PlayerReference) -> bool {
         game.day_number() > 1 &&
-        (actor_ref != target_ref || self.self_heals_remaining > 0) &&
+        (actor_ref != target_ref || actor_ref.ability_charges(game, Ability::SelfHeal) > 0) &&
         !actor_ref.night_jailed(game) &&
         actor_ref.selection(game).is_empty() &&
         actor_ref.alive(game) &&
@@ -84,6 +69,6 @@ impl RoleStateImpl for Doctor {
         crate::game::role::common_role::convert_selection_to_visits(game, actor_ref, target_refs, false)
     }
     fn on_phase_start(self, game: &mut Game, actor_ref: PlayerReference, _phase: PhaseType){
-        actor_ref.set_role_state(game, RoleState::Doctor(Doctor {self_heals_remaining: self.self_heals_remaining, target_healed_ref: None}));
+        actor_ref.set_role_state(game, RoleState::Doctor(Doctor {target_healed_ref: None}));
     }
 }
\ No newline at end of file
@@ -1,4 +1,4 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::game::chat::ChatMessageVariant;
 use crate::game::phase::PhaseType;
@@ -33,7 +33,7 @@ impl Trap {
         }
     }
 }
-#[derive(Default, Clone, Serialize, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Default, Clone, Serialize, Deserialize, Debug, PartialEq, Eq, PartialOrd, Ord)]
 #[serde(rename_all = "camelCase", tag = "type")]
 pub enum TrapState {
     #[default]
@@ -2,6 +2,7 @@
 use serde::Serialize;
 
 use crate::game::chat::ChatMessageVariant;
+use crate::game::components::ability_charges::Ability;
 use crate::game::phase::PhaseType;
 use crate::game::player::PlayerReference;
 use crate::game::role_list::Faction;
@@ -17,14 +18,12 @@ use super::{Priority, RoleState, RoleStateImpl};
 pub struct Forger {
     pub fake_role: Role,
     pub fake_will: String,
-    pub forges_remaining: u8,
     pub forged_ref: Option<PlayerReference>
 }
 
 impl Default for Forger {
     fn default() -> Self {
         Forger {
-            forges_remaining: 3,
             forged_ref: None,
             fake_role: Role::Jester,
             fake_will: "".to_owned(),
@@ -40,7 +39,7 @@ impl RoleStateImpl for Forger {
     fn do_night_action(self, game: &mut Game, actor_ref: PlayerReference, priority: Priority) {
         if actor_ref.night_jailed(game) {return}
 
-        if self.forges_remaining == 0 {return}
+        if actor_ref.ability_charges(game, Ability::Forge) == 0 {return}
 
         match priority {
             Priority::Deception=>{
@@ -50,8 +49,8 @@ impl RoleStateImpl for Forger {
 
                 target_ref.set_night_grave_role(game, Some(self.fake_role));
                 target_ref.set_night_grave_will(game, self.fake_will.clone());
+                actor_ref.use_ability_charge(game, Ability::Forge);
                 actor_ref.set_role_state(game, RoleState::Forger(Forger { 
-                    forges_remaining: self.forges_remaining - 1, 
                     forged_ref: Some(target_ref), 
                     ..self
                 }));
@@ -71,7 +70,7 @@ impl RoleStateImpl for Forger {
         }
     }
     fn can_select(self, game: &Game, actor_ref: PlayerReference, target_ref: PlayerReference) -> bool {
-        crate::game::role::common_role::can_night_select(game, actor_ref, target_ref) && self.forges_remaining > 0
+        crate::game::role::common_role::can_night_select(game, actor_ref, target_ref) && actor_ref.ability_charges(game, Ability::Forge) > 0
     }
     fn convert_selection_to_visits(self, game: &Game, actor_ref: PlayerReference, target_refs: Vec<PlayerReference>) -> Vec<Visit> {
         crate::game::role::common_role::convert_selection_to_visits(game, actor_ref, target_refs, false)
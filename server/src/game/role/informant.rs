@@ -1,4 +1,3 @@
-use rand::thread_rng;
 use rand::prelude::SliceRandom;
 use serde::Serialize;
 
@@ -25,10 +24,10 @@ impl RoleStateImpl for Informant {
             let target_ref = visit.target;
 
             let mut visited_by: Vec<PlayerReference> =  visit.target.appeared_visitors(game).into_iter().filter(|p|actor_ref!=*p).collect();
-            visited_by.shuffle(&mut thread_rng());
+            visited_by.shuffle(&mut game.rng);
 
             let mut visited: Vec<PlayerReference> = target_ref.tracker_seen_visits(game).iter().map(|v|v.target).collect();
-            visited.shuffle(&mut thread_rng());
+            visited.shuffle(&mut game.rng);
 
             let message = ChatMessageVariant::InformantResult{
                 role: target_ref.role(game), 
@@ -2,6 +2,7 @@ use std::collections::HashSet;
 
 use serde::Serialize;
 
+use crate::game::components::ability_charges::Ability;
 use crate::game::chat::{ChatGroup, ChatMessageVariant};
 use crate::game::resolution_state::ResolutionState;
 use crate::game::grave::GraveKiller;
@@ -14,20 +15,10 @@ use crate::game::Game;
 use super::{Priority, RoleState, Role, RoleStateImpl};
 
 
-#[derive(Serialize, Clone, Debug)]
+#[derive(Serialize, Clone, Debug, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct Jailor { 
     jailed_target_ref: Option<PlayerReference>, 
-    executions_remaining: u8
-}
-
-impl Default for Jailor {
-    fn default() -> Self {
-        Self { 
-            jailed_target_ref: None, 
-            executions_remaining: 3
-        }
-    }
 }
 
 pub(super) const FACTION: Faction = Faction::Town;
@@ -60,8 +51,11 @@ impl RoleStateImpl for Jailor {
                     if target_ref.night_jailed(game){
                         target_ref.try_night_kill(actor_ref, game, GraveKiller::Role(Role::Jailor), 3, false);
         
-                        self.executions_remaining = 
-                            if ResolutionState::requires_only_this_resolution_state(game, target_ref, ResolutionState::Town) {0} else {self.executions_remaining - 1};
+                        if ResolutionState::requires_only_this_resolution_state(game, target_ref, ResolutionState::Town) {
+                            actor_ref.set_ability_charges(game, Ability::Execute, 0);
+                        } else {
+                            actor_ref.use_ability_charge(game, Ability::Execute);
+                        }
                         self.jailed_target_ref = None;
                         actor_ref.set_role_state(game, RoleState::Jailor(self));
                     }
@@ -77,17 +71,17 @@ impl RoleStateImpl for Jailor {
         actor_ref.alive(game) &&
         target_ref.alive(game) &&
         game.phase_machine.day_number > 1 &&
-        self.executions_remaining > 0
+        actor_ref.ability_charges(game, Ability::Execute) > 0
     }
     fn do_day_action(self, game: &mut Game, actor_ref: PlayerReference, target_ref: PlayerReference) {
         if let Some(old_target_ref) = self.jailed_target_ref {
             if old_target_ref == target_ref {
-                actor_ref.set_role_state(game, RoleState::Jailor(Jailor { jailed_target_ref: None, ..self}));
+                actor_ref.set_role_state(game, RoleState::Jailor(Jailor { jailed_target_ref: None}));
             } else {
-                actor_ref.set_role_state(game, RoleState::Jailor(Jailor { jailed_target_ref: Some(target_ref), ..self }));
+                actor_ref.set_role_state(game, RoleState::Jailor(Jailor { jailed_target_ref: Some(target_ref) }));
             }
         } else {
-            actor_ref.set_role_state(game, RoleState::Jailor(Jailor { jailed_target_ref: Some(target_ref), ..self }));
+            actor_ref.set_role_state(game, RoleState::Jailor(Jailor { jailed_target_ref: Some(target_ref) }));
         }
     }
     fn can_day_target(self, game: &Game, actor_ref: PlayerReference, target_ref: PlayerReference) -> bool {        
@@ -45,7 +45,7 @@ impl RoleStateImpl for Jester {
         let player = match actor_ref.selection(game).first() {
             Some(v) => *v,
             None => {
-                let Some(target_ref) = all_killable_players.choose(&mut rand::thread_rng()) else {return};
+                let Some(target_ref) = all_killable_players.choose(&mut game.rng) else {return};
                 *target_ref
             },
         };
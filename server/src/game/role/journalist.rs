@@ -96,8 +96,8 @@ impl RoleStateImpl for Journalist {
                         let mut message_sent = false;
                         for chat_group in interviewed_target_ref.get_current_send_chat_groups(game){
                             match chat_group {
-                                ChatGroup::All | ChatGroup::Jail | ChatGroup::Interview | ChatGroup::Dead => {},
-                                ChatGroup::Mafia | ChatGroup::Cult  => {
+                                ChatGroup::All | ChatGroup::Jail | ChatGroup::Interview | ChatGroup::Dead | ChatGroup::Lovers | ChatGroup::Puppeteer => {},
+                                ChatGroup::Mafia | ChatGroup::Cult | ChatGroup::Fiends => {
                                     game.add_message_to_chat_group(
                                         chat_group,
                                         ChatMessageVariant::PlayerIsBeingInterviewed { player_index: interviewed_target_ref.index() }
@@ -1,4 +1,3 @@
-use rand::thread_rng;
 use rand::prelude::SliceRandom;
 use serde::Serialize;
 
@@ -24,7 +23,7 @@ impl RoleStateImpl for Lookout {
         if let Some(visit) = actor_ref.night_visits(game).first(){
             
             let mut seen_players: Vec<PlayerReference> = visit.target.appeared_visitors(game).into_iter().filter(|p|actor_ref!=*p).collect();
-            seen_players.shuffle(&mut thread_rng());
+            seen_players.shuffle(&mut game.rng);
 
             let message = ChatMessageVariant::LookoutResult { players:
                 PlayerReference::ref_vec_to_index(seen_players.as_slice())
@@ -47,12 +47,13 @@ impl MafiaSupportWildcard {
             role_can_generate(
                 self.role, 
                 &game.settings.enabled_roles, 
+                &game.settings.role_options,
                 &PlayerReference::all_players(game)
                     .map(|player_ref| player_ref.role(game))
                     .collect::<Vec<Role>>()
             )
         {
-            actor_ref.set_role(game, self.role.default_state());
+            actor_ref.set_role(game, game.settings.role_options.new_state(self.role));
         }else{
             actor_ref.add_private_chat_message(game, ChatMessageVariant::WildcardConvertFailed{role: self.role.clone()})
         }
@@ -3,6 +3,7 @@ use std::collections::HashSet;
 use serde::Serialize;
 
 use crate::game::chat::{ChatGroup, ChatMessageVariant};
+use crate::game::components::ability_charges::Ability;
 use crate::game::phase::PhaseType;
 use crate::game::player::PlayerReference;
 use crate::game::role_list::Faction;
@@ -10,17 +11,11 @@ use crate::game::Game;
 
 use super::{RoleStateImpl, RoleState};
 
-#[derive(Clone, Debug, Serialize)]
+#[derive(Clone, Debug, Serialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct Medium{
-    pub seances_remaining: u8,
     pub seanced_target: Option<PlayerReference>
 }
-impl Default for Medium{
-    fn default() -> Self {
-        Self { seances_remaining: 2, seanced_target: None}
-    }
-}
 
 pub(super) const FACTION: Faction = Faction::Town;
 pub(super) const MAXIMUM_COUNT: Option<u8> = None;
@@ -30,17 +25,17 @@ impl RoleStateImpl for Medium {
     fn do_day_action(self, game: &mut Game, actor_ref: PlayerReference, target_ref: PlayerReference) {
         if let Some(old_target_ref) = self.seanced_target {
             if old_target_ref == target_ref {
-                actor_ref.set_role_state(game, RoleState::Medium(Medium { seanced_target: None, seances_remaining: self.seances_remaining}));
+                actor_ref.set_role_state(game, RoleState::Medium(Medium { seanced_target: None }));
             } else {
-                actor_ref.set_role_state(game, RoleState::Medium(Medium { seanced_target: Some(target_ref), seances_remaining: self.seances_remaining }));
+                actor_ref.set_role_state(game, RoleState::Medium(Medium { seanced_target: Some(target_ref) }));
             }
         } else {
-            actor_ref.set_role_state(game, RoleState::Medium(Medium { seanced_target: Some(target_ref), seances_remaining: self.seances_remaining }));
+            actor_ref.set_role_state(game, RoleState::Medium(Medium { seanced_target: Some(target_ref) }));
         }
     }
     fn can_day_target(self, game: &Game, actor_ref: PlayerReference, target_ref: PlayerReference) -> bool {
         game.current_phase().is_day() &&
-        self.seances_remaining > 0 && 
+        actor_ref.ability_charges(game, Ability::Seance) > 0 && 
         actor_ref != target_ref &&
         !actor_ref.alive(game) && target_ref.alive(game) && 
         game.current_phase().phase() != PhaseType::Night
@@ -70,7 +65,7 @@ impl RoleStateImpl for Medium {
                             ChatMessageVariant::MediumHauntStarted{ medium: actor_ref.index(), player: seanced.index() }
                         );
 
-                        self.seances_remaining -= 1;
+                        actor_ref.use_ability_charge(game, Ability::Seance);
                     }
                 }
                 actor_ref.set_role_state(game, RoleState::Medium(self));
@@ -4,7 +4,9 @@ use crate::game::grave::Grave;
 use crate::game::phase::PhaseType;
 use crate::game::player::PlayerReference;
 use crate::game::role_list::Faction;
+use crate::game::resolution_state::ResolutionState;
 use crate::game::visit::Visit;
+use crate::game::win_condition::WinCondition;
 use crate::game::Game;
 use super::{Priority, RoleState, RoleStateImpl};
 
@@ -20,6 +22,13 @@ pub(super) const MAXIMUM_COUNT: Option<u8> = None;
 pub(super) const DEFENSE: u8 = 0;
 
 impl RoleStateImpl for Minion {
+    fn win_condition(self, _game: &Game, _actor_ref: PlayerReference) -> WinCondition {
+        WinCondition::wins_with(
+            ResolutionState::all().into_iter()
+                .filter(|resolution_state| !matches!(resolution_state, ResolutionState::Town | ResolutionState::Draw))
+                .collect()
+        )
+    }
     fn do_night_action(self, game: &mut Game, actor_ref: PlayerReference, priority: Priority) {
         if let Some(currently_used_player) = actor_ref.possess_night_action(game, priority, self.currently_used_player){
             actor_ref.set_role_state(game, RoleState::Minion(Minion{
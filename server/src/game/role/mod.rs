@@ -11,7 +11,7 @@ use crate::game::phase::PhaseType;
 
 use serde::{Serialize, Deserialize};
 
-use super::{event::before_role_switch::BeforeRoleSwitch, grave::GraveReference};
+use super::{components::ability_charges::Ability, event::before_role_switch::BeforeRoleSwitch, grave::GraveReference, role_options::RoleOptions, win_condition::WinCondition};
 
 trait RoleStateImpl: Clone + std::fmt::Debug + Serialize + Default {
     fn do_night_action(self, _game: &mut Game, _actor_ref: PlayerReference, _priority: Priority) {}
@@ -37,6 +37,14 @@ trait RoleStateImpl: Clone + std::fmt::Debug + Serialize + Default {
     fn get_won_game(self, game: &Game, actor_ref: PlayerReference) -> bool {
         crate::game::role::common_role::get_won_game(game, actor_ref)
     }
+    fn win_condition(self, game: &Game, actor_ref: PlayerReference) -> WinCondition {
+        crate::game::role::common_role::win_condition(game, actor_ref)
+    }
+    /// Is either town, or has the ability to consistently kill till the end of the game.
+    /// The game can only end once everyone alive who keeps it running agrees on how it ends
+    fn keeps_game_running(self, game: &Game, actor_ref: PlayerReference) -> bool {
+        crate::game::role::common_role::keeps_game_running(game, actor_ref)
+    }
 
     fn on_phase_start(self, _game: &mut Game, _actor_ref: PlayerReference, _phase: PhaseType) {}
     fn on_role_creation(self, _game: &mut Game, _actor_ref: PlayerReference) {}
@@ -250,6 +258,16 @@ mod macros {
                         $(Self::$name(role_struct) => role_struct.get_won_game(game, actor_ref)),*
                     }
                 }
+                pub fn win_condition(self, game: &Game, actor_ref: PlayerReference) -> WinCondition{
+                    match self {
+                        $(Self::$name(role_struct) => role_struct.win_condition(game, actor_ref)),*
+                    }
+                }
+                pub fn keeps_game_running(self, game: &Game, actor_ref: PlayerReference) -> bool{
+                    match self {
+                        $(Self::$name(role_struct) => role_struct.keeps_game_running(game, actor_ref)),*
+                    }
+                }
                 pub fn on_phase_start(self, game: &mut Game, actor_ref: PlayerReference, phase: PhaseType){
                     match self {
                         $(Self::$name(role_struct) => role_struct.on_phase_start(game, actor_ref, phase)),*
@@ -349,6 +367,37 @@ impl Role{
             _ => false,
         }
     }
+    /// The abilities this role can only use a limited number of times, and how many times by default.
+    /// The main ability is listed first, it's the one the host can change the uses of
+    pub fn limited_abilities(&self)->&'static [(Ability, u8)]{
+        match self {
+            Role::Jailor => &[(Ability::Execute, 3)],
+            Role::Doctor => &[(Ability::SelfHeal, 1)],
+            Role::Bodyguard => &[(Ability::SelfShield, 1)],
+            Role::Armorsmith => &[(Ability::OpenShop, 2)],
+            Role::Veteran => &[(Ability::Alert, 3)],
+            Role::Deputy => &[(Ability::Shoot, 1)],
+            Role::Medium => &[(Ability::Seance, 2)],
+            Role::Retrainer => &[(Ability::Retrain, 2)],
+            Role::Counterfeiter => &[(Ability::Forge, 3)],
+            Role::Forger => &[(Ability::Forge, 3)],
+            _ => &[],
+        }
+    }
+    /// How many times a role with limited uses can use its ability, before the host changes it
+    pub fn default_uses(&self)->Option<u8>{
+        self.limited_abilities().first().map(|(_, uses)| *uses)
+    }
+}
+impl RoleState {
+    /// This state with the host's options for its role. Options the role doesn't have are ignored.
+    /// Uses aren't part of the role state, they're given out by AbilityCharges
+    pub fn with_options(mut self, options: &RoleOptions) -> Self {
+        if let (RoleState::Journalist(journalist), Some(starts_public)) = (&mut self, options.starts_public) {
+            journalist.public = starts_public;
+        }
+        self
+    }
 }
 pub fn same_evil_team(game: &Game, actor_ref: PlayerReference, target_ref: PlayerReference) -> bool {
     (actor_ref.role(game).faction() == super::role_list::Faction::Mafia && target_ref.role(game).faction() == super::role_list::Faction::Mafia) ||
@@ -7,6 +7,7 @@ use crate::game::phase::PhaseType;
 use crate::game::player::PlayerReference;
 use crate::game::role_list::Faction;
 
+use crate::game::win_condition::WinCondition;
 use crate::game::Game;
 use super::jester::Jester;
 use super::{RoleStateImpl, Role, RoleState};
@@ -25,6 +26,9 @@ impl RoleStateImpl for Politician {
     fn get_won_game(self, _game: &Game, _actor_ref: PlayerReference) -> bool {
         self.won
     }
+    fn win_condition(self, _game: &Game, _actor_ref: PlayerReference) -> WinCondition {
+        WinCondition::wins_with(vec![ResolutionState::Politician])
+    }
     fn on_phase_start(self, game: &mut Game, actor_ref: PlayerReference, _phase: PhaseType){
         if self.should_suicide(game, actor_ref) {
             actor_ref.die(game, Grave::from_player_leave_town(game, actor_ref));
@@ -23,63 +23,59 @@ impl RoleStateImpl for Psychic {
 
         if priority != Priority::Investigative {return}
 
-        actor_ref.push_night_message(game, match game.day_number() % 2 {
+        let message = match game.day_number() % 2 {
             1=>{
                 Psychic::get_psychic_result_evil(game, actor_ref)
             },
             _=>{
                 Psychic::get_psychic_result_good(game, actor_ref)
             },
-        });
+        };
+        actor_ref.push_night_message(game, message);
         
     }
 }
 
 impl Psychic {
-    fn get_psychic_result_evil(game: &Game, actor_ref: PlayerReference)->ChatMessageVariant{
-        
-        let mut rng = rand::thread_rng();
-
+    fn get_psychic_result_evil(game: &mut Game, actor_ref: PlayerReference)->ChatMessageVariant{
         let evil_players: Vec<_> = Psychic::get_valid_players(game, actor_ref).into_iter()
             .filter(|player_ref|Psychic::player_is_evil(game, *player_ref))
             .filter(|player_ref|!player_ref.has_innocent_aura(game))
             .collect();
 
-        let Some(selected_evil) = evil_players.choose(&mut rng) else {return ChatMessageVariant::PsychicFailed};
+        let Some(selected_evil) = evil_players.choose(&mut game.rng) else {return ChatMessageVariant::PsychicFailed};
 
         let random_players: Vec<_> = Psychic::get_valid_players(game, actor_ref).into_iter()
             .filter(|p|p!=selected_evil)
             .filter(|player_ref|!player_ref.has_innocent_aura(game))
             .collect::<Vec<_>>()
-            .choose_multiple(&mut rng, 2).copied().collect();
+            .choose_multiple(&mut game.rng, 2).copied().collect();
         
         let Some(random_player0) = random_players.get(0) else {return ChatMessageVariant::PsychicFailed};
         let Some(random_player1) = random_players.get(1) else {return ChatMessageVariant::PsychicFailed};
 
         let mut out = [selected_evil, random_player0, random_player1];
-        out.shuffle(&mut rng);
+        out.shuffle(&mut game.rng);
         ChatMessageVariant::PsychicEvil { players: [out[0].index(), out[1].index(), out[2].index()] }
 
     }
-    fn get_psychic_result_good(game: &Game, actor_ref: PlayerReference)->ChatMessageVariant{
-        let mut rng = rand::thread_rng();
-
+    fn get_psychic_result_good(game: &mut Game, actor_ref: PlayerReference)->ChatMessageVariant{
         let good_players: Vec<_> = Psychic::get_valid_players(game, actor_ref).into_iter()
             .filter(|player_ref|!Psychic::player_is_evil(game, *player_ref))
             .filter(|player_ref|!player_ref.has_suspicious_aura(game))
             .collect();
 
-        let Some(selected_good) = good_players.choose(&mut rng) else {return ChatMessageVariant::PsychicFailed};
+        let Some(selected_good) = good_players.choose(&mut game.rng) else {return ChatMessageVariant::PsychicFailed};
 
         let random_players: Vec<_> = Psychic::get_valid_players(game, actor_ref).into_iter()
             .filter(|player_ref|!player_ref.has_suspicious_aura(game))
             .filter(|p|p!=selected_good)
             .collect::<Vec<_>>();
         
-        let Some(random_player) = random_players.choose(&mut rng) else {return ChatMessageVariant::PsychicFailed};
+        let Some(random_player) = random_players.choose(&mut game.rng) else {return ChatMessageVariant::PsychicFailed};
 
         let mut out = [selected_good, random_player];
-        out.shuffle(&mut rng);
+        out.shuffle(&mut game.rng);
         ChatMessageVariant::PsychicGood { players: [out[0].index(), out[1].index()] }
     }
 
@@ -80,7 +80,7 @@ impl RoleStateImpl for RabbleRouser {
                 p.role(game) != Role::Mayor &&
                 p.role(game) != Role::Journalist
             ).collect::<Vec<PlayerReference>>()
-            .choose(&mut rand::thread_rng())
+            .choose(&mut game.rng)
         {
             actor_ref.push_player_tag(game, *target, Tag::RabbleRouserTarget);
             actor_ref.set_role_state(game, RoleState::RabbleRouser(RabbleRouser{target: RabbleRouserTarget::Target(*target)}));
@@ -1,6 +1,7 @@
 use serde::Serialize;
 
 use crate::game::chat::{ChatGroup, ChatMessageVariant};
+use crate::game::components::ability_charges::Ability;
 use crate::game::grave::GraveKiller;
 use crate::game::player::PlayerReference;
 use crate::game::role_list::{Faction, RoleSet};
@@ -11,19 +12,10 @@ use crate::game::Game;
 use super::{Priority, Role, RoleState, RoleStateImpl};
 
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct Retrainer{
     pub backup: Option<PlayerReference>,
-    pub retrains_remaining: u8
-}
-impl Default for Retrainer {
-    fn default() -> Self {
-        Self {
-            backup: None,
-            retrains_remaining: 2
-        }
-    }
 }
 
 pub(super) const FACTION: Faction = Faction::Mafia;
@@ -67,12 +59,12 @@ impl RoleStateImpl for Retrainer {
     fn do_day_action(self, game: &mut Game, actor_ref: PlayerReference, target_ref: PlayerReference) {
         if let Some(old_target_ref) = self.backup {
             if old_target_ref == target_ref {
-                actor_ref.set_role_state(game, RoleState::Retrainer(Retrainer{backup: None, ..self}));
+                actor_ref.set_role_state(game, RoleState::Retrainer(Retrainer{backup: None}));
             } else {
-                actor_ref.set_role_state(game, RoleState::Retrainer(Retrainer{backup: Some(target_ref), ..self}));
+                actor_ref.set_role_state(game, RoleState::Retrainer(Retrainer{backup: Some(target_ref)}));
             }
         } else {
-            actor_ref.set_role_state(game, RoleState::Retrainer(Retrainer{backup: Some(target_ref), ..self}));
+            actor_ref.set_role_state(game, RoleState::Retrainer(Retrainer{backup: Some(target_ref)}));
         }
 
         let RoleState::Retrainer(Retrainer { backup, .. }) = *actor_ref.role_state(game) else {
@@ -111,7 +103,7 @@ impl RoleStateImpl for Retrainer {
         if actor_ref == dead_player_ref {
             let Some(backup) = self.backup else {return};
 
-            actor_ref.set_role_state(game, RoleState::Retrainer(Retrainer{backup: None, ..self}));
+            actor_ref.set_role_state(game, RoleState::Retrainer(Retrainer{backup: None}));
             for player_ref in PlayerReference::all_players(game){
                 if player_ref.role(game).faction() != Faction::Mafia{
                     continue;
@@ -121,11 +113,12 @@ impl RoleStateImpl for Retrainer {
             
             if !backup.alive(game){return}
 
-            //convert backup to godfather
-            backup.set_role(game, RoleState::Retrainer(Retrainer{backup: None, ..self}));
+            //convert backup to godfather, they only get the retrains that are left
+            backup.set_role(game, RoleState::Retrainer(Retrainer{backup: None}));
+            backup.set_ability_charges(game, Ability::Retrain, actor_ref.ability_charges(game, Ability::Retrain));
         }
         else if self.backup.is_some_and(|p|p == dead_player_ref) {
-            actor_ref.set_role_state(game, RoleState::Retrainer(Retrainer{backup: None, ..self}));
+            actor_ref.set_role_state(game, RoleState::Retrainer(Retrainer{backup: None}));
         }
     }
 }
@@ -142,16 +135,10 @@ impl Retrainer {
         }
 
 
-        if let RoleState::Retrainer(mut retrainer) = actor_ref.role_state(game).clone() {
-
-            if let Some(backup) = retrainer.backup {
-                if retrainer.retrains_remaining > 0 && backup.role(game) != role{
-                    backup.set_role(game, role.default_state());
-                    retrainer.retrains_remaining = retrainer.retrains_remaining.saturating_sub(1);
-                }
+        if let RoleState::Retrainer(Retrainer { backup: Some(backup) }) = actor_ref.role_state(game).clone() {
+            if backup.role(game) != role && actor_ref.use_ability_charge(game, Ability::Retrain) {
+                backup.set_role(game, game.settings.role_options.new_state(role));
             }
-            
-            actor_ref.set_role_state(game, RoleState::Retrainer(retrainer));
         }
     }
 }
\ No newline at end of file
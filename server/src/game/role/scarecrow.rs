@@ -1,4 +1,3 @@
-use rand::thread_rng;
 use serde::Serialize;
 
 use crate::game::chat::ChatMessageVariant;
@@ -6,7 +5,9 @@ use crate::game::grave::Grave;
 use crate::game::phase::PhaseType;
 use crate::game::player::PlayerReference;
 use crate::game::role_list::Faction;
+use crate::game::resolution_state::ResolutionState;
 use crate::game::visit::Visit;
+use crate::game::win_condition::WinCondition;
 use crate::game::Game;
 use super::{Priority, RoleStateImpl};
 use rand::prelude::SliceRandom;
@@ -21,6 +22,13 @@ pub(super) const MAXIMUM_COUNT: Option<u8> = None;
 pub(super) const DEFENSE: u8 = 0;
 
 impl RoleStateImpl for Scarecrow {
+    fn win_condition(self, _game: &Game, _actor_ref: PlayerReference) -> WinCondition {
+        WinCondition::wins_with(
+            ResolutionState::all().into_iter()
+                .filter(|resolution_state| !matches!(resolution_state, ResolutionState::Town | ResolutionState::Draw))
+                .collect()
+        )
+    }
     fn do_night_action(self, game: &mut Game, actor_ref: PlayerReference, priority: Priority) {
         if priority != Priority::Ward {return;}
         
@@ -28,7 +36,7 @@ impl RoleStateImpl for Scarecrow {
             let target_ref = visit.target;
 
             let mut blocked_players = target_ref.ward(game);
-            blocked_players.shuffle(&mut thread_rng());
+            blocked_players.shuffle(&mut game.rng);
 
             let message = ChatMessageVariant::ScarecrowResult { players:
                 PlayerReference::ref_vec_to_index(blocked_players.as_slice())
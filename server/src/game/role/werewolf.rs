@@ -1,6 +1,5 @@
-use std::collections::HashSet;
+use std::collections::BTreeSet;
 
-use rand::thread_rng;
 use rand::seq::SliceRandom;
 
 use serde::Serialize;
@@ -93,7 +92,7 @@ impl RoleStateImpl for Werewolf {
 
                     //this should remove duplicates
                     newly_tracked_players.append(&mut self.tracked_players.clone());
-                    let tracked_players: HashSet<PlayerReference> = newly_tracked_players.into_iter().collect();
+                    let tracked_players: BTreeSet<PlayerReference> = newly_tracked_players.into_iter().collect();
                     let tracked_players: Vec<PlayerReference> = tracked_players.into_iter().collect();
 
                     //send the list to the werewolf using tags
@@ -117,7 +116,7 @@ impl RoleStateImpl for Werewolf {
                 tracked_players.into_iter().for_each(|player_ref|{
 
                     let mut players: Vec<PlayerIndex> = player_ref.tracker_seen_visits(game).into_iter().map(|p|p.target.index()).collect();
-                    players.shuffle(&mut thread_rng());
+                    players.shuffle(&mut game.rng);
 
                     actor_ref.push_night_message(game, 
                         ChatMessageVariant::WerewolfTrackingResult{
@@ -4,6 +4,7 @@ use crate::game::chat::ChatMessageVariant;
 use crate::game::phase::PhaseType;
 use crate::game::player::PlayerReference;
 use crate::game::role_list::{role_can_generate, Faction};
+use crate::game::win_condition::WinCondition;
 use crate::game::Game;
 
 use super::{RoleStateImpl, Role};
@@ -26,6 +27,9 @@ pub(super) const MAXIMUM_COUNT: Option<u8> = None;
 pub(super) const DEFENSE: u8 = 0;
 
 impl RoleStateImpl for Wildcard {
+    fn win_condition(self, _game: &Game, _actor_ref: PlayerReference) -> WinCondition {
+        WinCondition::Undecided
+    }
     fn on_phase_start(self, game: &mut Game, actor_ref: PlayerReference, phase: PhaseType) {
         match phase {
             PhaseType::Night => {
@@ -46,12 +50,13 @@ impl Wildcard {
             role_can_generate(
                 self.role, 
                 &game.settings.enabled_roles, 
+                &game.settings.role_options,
                 &PlayerReference::all_players(game)
                     .map(|player_ref| player_ref.role(game))
                     .collect::<Vec<Role>>()
             )
         {
-            actor_ref.set_role(game, self.role.default_state());
+            actor_ref.set_role(game, game.settings.role_options.new_state(self.role));
         }else{
             actor_ref.add_private_chat_message(game, ChatMessageVariant::WildcardConvertFailed{role: self.role.clone()})
         }
@@ -1,13 +1,13 @@
 use std::{collections::HashSet, vec};
 
-use rand::seq::SliceRandom;
+use rand::Rng;
 use serde::{Serialize, Deserialize};
 use vec1::{
     vec1,
     Vec1
 };
 
-use super::role::Role;
+use super::{role::Role, role_list_generation::{RoleListConstraint, RoleListSolver}, role_modifier::RoleModifier, role_options::RoleOptionsMap, RejectStartReason};
 
 macro_rules! make_faction_enum {
     ($($name:ident),*)=>{
@@ -32,16 +32,15 @@ make_faction_enum!{
 #[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct RoleList(pub Vec<RoleOutline>);
 impl RoleList {
-    pub fn create_random_roles(&self, enabled_roles: &HashSet<Role>) -> Option<Vec<Role>> {
-        let mut taken_roles = Vec::new();
-        for entry in self.0.iter(){
-            if let Some(role) = entry.get_random_role(enabled_roles, &taken_roles){
-                taken_roles.push(role);
-            }else{
-                return None;
-            }
-        }
-        Some(taken_roles)
+    /// Picks a role for every outline, in the same order as the outlines
+    pub fn create_random_roles(
+        &self,
+        enabled_roles: &HashSet<Role>,
+        constraints: &[RoleListConstraint],
+        role_options: &RoleOptionsMap,
+        rng: &mut impl Rng
+    ) -> Result<Vec<Role>, RejectStartReason> {
+        RoleListSolver::new(self, enabled_roles, constraints, role_options).solve(rng)
     }
     pub fn simplify(&mut self){
         for entry in self.0.iter_mut(){
@@ -51,87 +50,162 @@ impl RoleList {
     pub fn sort(&mut self){
         self.0.sort_by_key(|r| r.get_roles().len());
     }
+    /// Points every custom role set option at the set with the same name in `custom_role_sets`,
+    /// so outlines pick up changes to the set. Options whose set no longer exists are removed
+    pub fn update_custom_role_sets(&mut self, custom_role_sets: &[CustomRoleSet]){
+        for entry in self.0.iter_mut(){
+            entry.update_custom_role_sets(custom_role_sets);
+        }
+    }
+    pub fn custom_role_sets(&self) -> impl Iterator<Item = &CustomRoleSet> {
+        self.0.iter().flat_map(|entry| match entry {
+            RoleOutline::Any { .. } => Vec::new(),
+            RoleOutline::RoleOutlineOptions { options, .. } => options.iter().filter_map(|option| match &option.roles {
+                RoleOutlineOptionRoles::CustomRoleSet { custom_role_set } => Some(custom_role_set),
+                _ => None
+            }).collect()
+        })
+    }
 }
 
 
 
-#[derive(Default, Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize, PartialOrd, Ord)]
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize, PartialOrd, Ord)]
 #[serde(tag = "type", rename_all = "camelCase")]
 pub enum RoleOutline{
-    #[default]
-    Any,
+    Any{
+        /// Given to whoever gets this outline's role
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        modifiers: Vec<RoleModifier>
+    },
     RoleOutlineOptions{
-        options: Vec1<RoleOutlineOption>
+        options: Vec1<RoleOutlineOption>,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        modifiers: Vec<RoleModifier>
     },
 }
+impl Default for RoleOutline{
+    fn default() -> Self {
+        RoleOutline::Any{modifiers: Vec::new()}
+    }
+}
 impl RoleOutline{
     pub fn new_exact(role: Role)->RoleOutline{
-        RoleOutline::RoleOutlineOptions{options: vec1![RoleOutlineOption::Role{role}]}
+        RoleOutline::RoleOutlineOptions{options: vec1![RoleOutlineOptionRoles::Role{role}.into()], modifiers: Vec::new()}
+    }
+    pub fn modifiers(&self) -> &[RoleModifier] {
+        match self {
+            RoleOutline::Any{modifiers} | RoleOutline::RoleOutlineOptions{modifiers, ..} => modifiers
+        }
     }
     pub fn get_roles(&self) -> Vec<Role> {
         match self {
-            RoleOutline::RoleOutlineOptions{options} => 
+            RoleOutline::RoleOutlineOptions{options, ..} => 
                 options.iter().flat_map(|r| r.get_roles()).collect(),
-            RoleOutline::Any => 
+            RoleOutline::Any{..} => 
                 Role::values(),
         }
     }
-    pub fn get_random_role(&self, enabled_roles: &HashSet<Role>, taken_roles: &[Role]) -> Option<Role> {
-        let options = self.get_roles().into_iter().filter(|r|role_can_generate(*r, enabled_roles, taken_roles)).collect::<Vec<_>>();
-        options.choose(&mut rand::thread_rng()).cloned()
+    /// Every role this outline can become, and how likely it is compared to the others.
+    /// A role's weight is the total weight of the options it's in
+    pub fn get_weighted_roles(&self) -> Vec<(Role, u32)> {
+        let mut weighted_roles: Vec<(Role, u32)> = Vec::new();
+        for (role, weight) in match self {
+            RoleOutline::RoleOutlineOptions{options, ..} => 
+                options.iter().flat_map(|option| option.get_roles().into_iter().map(|role| (role, option.weight as u32))).collect(),
+            RoleOutline::Any{..} => 
+                Role::values().into_iter().map(|role| (role, 1)).collect::<Vec<_>>(),
+        } {
+            match weighted_roles.iter_mut().find(|(other, _)| *other == role) {
+                Some((_, total)) => *total += weight,
+                None => weighted_roles.push((role, weight)),
+            }
+        }
+        weighted_roles
     }
     pub fn simplify(&mut self){
-        if let RoleOutline::RoleOutlineOptions{options} = self {
-            let mut new_options = options.to_vec();
-
-            new_options = new_options.into_iter().collect::<HashSet<_>>().into_iter().collect();
-
-            for option_a in options.iter(){
-                for option_b in options.iter(){
-                    if option_a.is_subset(option_b) && option_a != option_b{
-                        new_options.retain(|r| r != option_a);
-                    }
+        if let RoleOutline::RoleOutlineOptions{options, modifiers} = self {
+            let mut unique_options: Vec<RoleOutlineOption> = Vec::new();
+            for option in options.iter(){
+                if !unique_options.contains(option) {
+                    unique_options.push(option.clone());
                 }
             }
 
+            //Different options can have the same roles, like a custom role set that copies a faction.
+            //Only the first of those is kept.
+            //Options with their own weight change how likely their roles are, so they're never removed
+            let new_options = unique_options.iter().enumerate()
+                .filter(|(index_a, option_a)| !option_a.has_default_weight() || !unique_options.iter().enumerate().any(|(index_b, option_b)|
+                    index_a != &index_b && option_a.is_subset(option_b) && (!option_b.is_subset(option_a) || index_b < *index_a)
+                ))
+                .map(|(_, option)| option.clone())
+                .collect();
+
             let mut new_options = Vec1::try_from_vec(new_options)
-                .expect("It is impossible to have two sets that are not equal but are subsets of each other, role_list.rs: RoleOutline::simplify");
+                .expect("The first option with the most roles is never removed, role_list.rs: RoleOutline::simplify");
 
             new_options.sort();
 
-            *self = RoleOutline::RoleOutlineOptions{options: new_options};
+            *self = RoleOutline::RoleOutlineOptions{options: new_options, modifiers: modifiers.clone()};
         }
     }
+    fn update_custom_role_sets(&mut self, custom_role_sets: &[CustomRoleSet]){
+        let RoleOutline::RoleOutlineOptions{options, modifiers} = self else {return};
+
+        let new_options = options.iter().filter_map(|option| match &option.roles {
+            RoleOutlineOptionRoles::CustomRoleSet { custom_role_set } => 
+                custom_role_sets.iter()
+                    .find(|set| set.name == custom_role_set.name)
+                    .map(|set| RoleOutlineOption {
+                        roles: RoleOutlineOptionRoles::CustomRoleSet { custom_role_set: set.clone() },
+                        weight: option.weight
+                    }),
+            _ => Some(option.clone())
+        }).collect();
+
+        *self = match Vec1::try_from_vec(new_options) {
+            Ok(options) => RoleOutline::RoleOutlineOptions { options, modifiers: modifiers.clone() },
+            Err(_) => RoleOutline::Any { modifiers: modifiers.clone() },
+        };
+    }
 }
 
 
 
 #[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
-#[serde(tag = "type", rename_all = "camelCase")]
-pub enum RoleOutlineOption {
-    #[serde(rename_all = "camelCase")]
-    RoleSet{role_set: RoleSet},
-    #[serde(rename_all = "camelCase")]
-    Role{role: Role},
-    #[serde(rename_all = "camelCase")]
-    Faction{faction: Faction}
+#[serde(rename_all = "camelCase")]
+pub struct RoleOutlineOption {
+    #[serde(flatten)]
+    pub roles: RoleOutlineOptionRoles,
+    /// How likely this option is to be picked, compared to the other options in the outline
+    #[serde(default = "RoleOutlineOption::default_weight", skip_serializing_if = "RoleOutlineOption::is_default_weight")]
+    pub weight: u8,
 }
 impl RoleOutlineOption{
+    pub const DEFAULT_WEIGHT: u8 = 1;
+
+    fn default_weight() -> u8 {
+        Self::DEFAULT_WEIGHT
+    }
+    fn is_default_weight(weight: &u8) -> bool {
+        *weight == Self::DEFAULT_WEIGHT
+    }
+    pub fn has_default_weight(&self) -> bool {
+        Self::is_default_weight(&self.weight)
+    }
     pub fn get_roles(&self) -> Vec<Role> {
-        match self {
-            RoleOutlineOption::RoleSet { role_set } => {
-                role_set.get_roles()
-            }
-            RoleOutlineOption::Role { role } => 
-                vec![*role],
-            RoleOutlineOption::Faction { faction } => 
-                Role::values().into_iter().filter(|r|r.faction() == *faction).collect()
-        }
+        self.roles.get_roles()
     }
     pub fn is_subset(&self, other: &RoleOutlineOption) -> bool {
         self.get_roles().iter().all(|r|other.get_roles().contains(r))
     }
 }
+impl From<RoleOutlineOptionRoles> for RoleOutlineOption {
+    fn from(roles: RoleOutlineOptionRoles) -> Self {
+        Self { roles, weight: Self::DEFAULT_WEIGHT }
+    }
+}
 impl PartialOrd for RoleOutlineOption {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
         Some(self.cmp(other))
@@ -143,6 +217,34 @@ impl Ord for RoleOutlineOption {
     }
 }
 
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum RoleOutlineOptionRoles {
+    #[serde(rename_all = "camelCase")]
+    RoleSet{role_set: RoleSet},
+    #[serde(rename_all = "camelCase")]
+    Role{role: Role},
+    #[serde(rename_all = "camelCase")]
+    Faction{faction: Faction},
+    #[serde(rename_all = "camelCase")]
+    CustomRoleSet{custom_role_set: CustomRoleSet},
+}
+impl RoleOutlineOptionRoles{
+    pub fn get_roles(&self) -> Vec<Role> {
+        match self {
+            RoleOutlineOptionRoles::RoleSet { role_set } => {
+                role_set.get_roles()
+            }
+            RoleOutlineOptionRoles::Role { role } => 
+                vec![*role],
+            RoleOutlineOptionRoles::Faction { faction } => 
+                Role::values().into_iter().filter(|r|r.faction() == *faction).collect(),
+            RoleOutlineOptionRoles::CustomRoleSet { custom_role_set } => 
+                custom_role_set.roles.clone(),
+        }
+    }
+}
+
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -194,13 +296,53 @@ impl RoleSet{
 }
 
 
+/// A role set the host names and fills in the lobby settings.
+/// Role outlines keep a copy of the whole set, so a role list still makes sense without the settings it came from
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CustomRoleSet {
+    pub name: String,
+    pub roles: Vec<Role>,
+}
+impl CustomRoleSet {
+    pub const MAX_NAME_LENGTH: usize = 30;
+
+    /// Trims the name and removes duplicate roles. Returns None if the set has no name
+    pub fn sanitize(self) -> Option<Self> {
+        let name: String = self.name.trim().chars().take(Self::MAX_NAME_LENGTH).collect();
+        if name.is_empty() {return None}
+
+        let mut roles = Vec::new();
+        for role in self.roles {
+            if !roles.contains(&role) {
+                roles.push(role);
+            }
+        }
+        Some(Self { name, roles })
+    }
+    /// Sanitizes every set, and drops sets without a name or with the same name as an earlier set
+    pub fn sanitize_all(custom_role_sets: Vec<Self>) -> Vec<Self> {
+        let mut sanitized: Vec<Self> = Vec::new();
+        for set in custom_role_sets.into_iter().filter_map(Self::sanitize) {
+            if !sanitized.iter().any(|other| other.name == set.name) {
+                sanitized.push(set);
+            }
+        }
+        sanitized
+    }
+    /// A set can only be used in a game if at least one of its roles is enabled
+    pub fn is_valid(&self, enabled_roles: &HashSet<Role>) -> bool {
+        self.roles.iter().any(|role| enabled_roles.contains(role))
+    }
+}
+
 
-pub fn role_can_generate(role: Role, enabled_roles: &HashSet<Role>, taken_roles: &[Role]) -> bool {
+pub fn role_can_generate(role: Role, enabled_roles: &HashSet<Role>, role_options: &RoleOptionsMap, taken_roles: &[Role]) -> bool {
     if !enabled_roles.contains(&role) {
         return false;
     }
 
-    match role.maximum_count() {
+    match role_options.maximum_count(role) {
         Some(max) => taken_roles.iter().filter(|r|**r==role).count() < max.into(),
         None => true,
     }
@@ -0,0 +1,130 @@
+use std::collections::{HashMap, HashSet};
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use serde::Serialize;
+
+use crate::client_connection::ClientConnection;
+
+use super::{
+    player::{Player, PlayerIndex},
+    role::Role,
+    role_list::{role_can_generate, Faction, RoleList},
+    role_list_generation::{RoleListConstraint, RoleListSolver},
+    role_options::RoleOptionsMap,
+    settings::Settings,
+    Game, RejectStartReason
+};
+
+/// How games made from a role list tend to turn out, found by generating its roles many times.
+/// Chances other than `failure_chance` are out of the samples where roles could be generated
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RoleListAnalysis {
+    /// Fewer than were asked for if generating the roles took too long
+    pub samples: u32,
+    /// The chance that no roles can be generated, because of the enabled roles or role list constraints
+    pub failure_chance: f32,
+    /// The chance that the roles make a game that's already over, which the game has to generate again
+    pub game_ends_instantly_chance: f32,
+    /// For each faction, the chance of it having each number of players. The index is the number of players
+    pub faction_distributions: HashMap<Faction, Vec<f32>>,
+    /// The chance of each role being given to at least one player. Roles that never appear are left out
+    pub role_chances: HashMap<Role, f32>,
+    /// Outlines that none of the enabled roles can fill, so roles can never be generated
+    pub impossible_outlines: Vec<u8>,
+}
+
+impl RoleListAnalysis {
+    /// Enough samples that the chances are usually within a few percent
+    pub const DEFAULT_SAMPLES: u32 = 1000;
+    /// Bounds the whole analysis, since role lists that are slow to generate would otherwise take up to a thousand times the longest search
+    const MAX_STEPS: u32 = 200_000;
+
+    pub fn new(
+        role_list: &RoleList,
+        enabled_roles: &HashSet<Role>,
+        constraints: &[RoleListConstraint],
+        role_options: &RoleOptionsMap,
+        samples: u32,
+        rng: &mut impl Rng
+    ) -> Self {
+        let settings = Settings {
+            role_list: role_list.clone(),
+            enabled_roles: enabled_roles.clone(),
+            role_list_constraints: constraints.to_vec(),
+            role_options: role_options.clone(),
+            ..Settings::default()
+        };
+
+        let mut taken_samples = 0u32;
+        let mut steps_left = Self::MAX_STEPS;
+        let mut generated_samples = 0u32;
+        let mut instant_game_overs = 0u32;
+        let mut faction_counts: HashMap<Faction, Vec<u32>> = Faction::values().into_iter()
+            .map(|faction| (faction, vec![0; role_list.0.len() + 1]))
+            .collect();
+        let mut role_counts: HashMap<Role, u32> = HashMap::new();
+
+        for _ in 0..samples {
+            let mut solver = RoleListSolver::new(role_list, enabled_roles, constraints, role_options).with_max_steps(steps_left);
+            let result = solver.solve(rng);
+            steps_left -= solver.steps();
+
+            // The search might have found roles with more steps, so this sample doesn't count
+            if steps_left == 0 && matches!(result, Err(RejectStartReason::RoleListTooComplex)) {break}
+            taken_samples += 1;
+
+            let Ok(roles) = result else {continue};
+            generated_samples += 1;
+
+            for faction in Faction::values() {
+                let count = roles.iter().filter(|role| role.faction() == faction).count();
+                if let Some(counts) = faction_counts.get_mut(&faction) {
+                    counts[count] += 1;
+                }
+            }
+            for role in roles.iter().collect::<HashSet<_>>() {
+                *role_counts.entry(*role).or_default() += 1;
+            }
+
+            if Self::game_ends_instantly(&settings, roles, StdRng::seed_from_u64(rng.gen())) {
+                instant_game_overs += 1;
+            }
+        }
+
+        let chance = |count: u32| if generated_samples == 0 {0.0} else {count as f32 / generated_samples as f32};
+
+        Self {
+            samples: taken_samples,
+            failure_chance: if taken_samples == 0 {0.0} else {(taken_samples - generated_samples) as f32 / taken_samples as f32},
+            game_ends_instantly_chance: chance(instant_game_overs),
+            faction_distributions: faction_counts.into_iter()
+                .map(|(faction, counts)| (faction, counts.into_iter().map(chance).collect()))
+                .collect(),
+            role_chances: role_counts.into_iter()
+                .map(|(role, count)| (role, chance(count)))
+                .collect(),
+            impossible_outlines: role_list.0.iter()
+                .enumerate()
+                .filter(|(_, outline)| !outline.get_weighted_roles().into_iter()
+                    .any(|(role, weight)| weight > 0 && role_can_generate(role, enabled_roles, role_options, &[]))
+                )
+                .map(|(index, _)| index as u8)
+                .collect(),
+        }
+    }
+
+    /// The same check `Game::new` does before it accepts the roles it generated
+    fn game_ends_instantly(settings: &Settings, roles: Vec<Role>, rng: StdRng) -> bool {
+        let players = roles.iter()
+            .enumerate()
+            .map(|(index, role)| Player::new(index.to_string(), ClientConnection::Disconnected, *role))
+            .collect();
+        let roles_to_players = roles.into_iter()
+            .enumerate()
+            .map(|(index, role)| (role, index as PlayerIndex))
+            .collect();
+
+        Game::from_players(settings.clone(), players, roles_to_players, Vec::new(), 0, rng).game_is_over()
+    }
+}
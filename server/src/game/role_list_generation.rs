@@ -0,0 +1,159 @@
+use std::collections::HashSet;
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use super::{role::Role, role_list::{role_can_generate, RoleList}, role_options::RoleOptionsMap, RejectStartReason};
+
+/// Rules the generated roles have to follow, on top of the role list
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum RoleListConstraint {
+    /// No more than `count` players can have any of these roles, all together
+    #[serde(rename_all = "camelCase")]
+    AtMost{roles: Vec<Role>, count: u8},
+    /// `role` can only be generated if `required_role` is too
+    #[serde(rename_all = "camelCase")]
+    Requires{role: Role, required_role: Role},
+}
+impl RoleListConstraint {
+    /// Whether `role` can be added to `taken_roles` without breaking this constraint
+    fn allows(&self, role: Role, taken_roles: &[Role]) -> bool {
+        match self {
+            Self::AtMost { roles, count } =>
+                !roles.contains(&role) ||
+                taken_roles.iter().filter(|taken| roles.contains(taken)).count() < *count as usize,
+            Self::Requires { .. } => true,
+        }
+    }
+}
+
+/// Picks a role for every outline of a role list.
+/// Outlines with the fewest roles to pick from are filled first,
+/// and earlier picks are changed when a later outline can't be filled.
+pub struct RoleListSolver<'a> {
+    /// The roles each outline can become and their weights, in the same order as the role list
+    outlines: Vec<Vec<(Role, u32)>>,
+    enabled_roles: &'a HashSet<Role>,
+    constraints: &'a [RoleListConstraint],
+    role_options: &'a RoleOptionsMap,
+    steps: u32,
+    max_steps: u32,
+    /// The search gave up after `max_steps`, so it doesn't know which outline can't be filled, or if any can't
+    ran_out_of_steps: bool,
+    /// How many outlines were filled, and the outline that couldn't be, the furthest the search got
+    deepest_failure: Option<(usize, usize)>,
+}
+impl<'a> RoleListSolver<'a> {
+    /// Stops the search from taking forever on role lists with a huge number of ways to fail
+    const MAX_STEPS: u32 = 10_000;
+
+    pub fn new(
+        role_list: &RoleList,
+        enabled_roles: &'a HashSet<Role>,
+        constraints: &'a [RoleListConstraint],
+        role_options: &'a RoleOptionsMap
+    ) -> Self {
+        Self {
+            outlines: role_list.0.iter()
+                .map(|outline| outline.get_weighted_roles().into_iter()
+                    .filter(|(role, weight)| *weight > 0 && enabled_roles.contains(role))
+                    .collect()
+                )
+                .collect(),
+            enabled_roles,
+            constraints,
+            role_options,
+            steps: 0,
+            max_steps: Self::MAX_STEPS,
+            ran_out_of_steps: false,
+            deepest_failure: None,
+        }
+    }
+
+    /// Gives up sooner than `MAX_STEPS`, for callers that run many searches and need to bound all of them together
+    pub fn with_max_steps(mut self, max_steps: u32) -> Self {
+        self.max_steps = self.max_steps.min(max_steps);
+        self
+    }
+
+    /// How many roles the search has tried so far
+    pub fn steps(&self) -> u32 {
+        self.steps
+    }
+
+    pub fn solve(&mut self, rng: &mut impl Rng) -> Result<Vec<Role>, RejectStartReason> {
+        let mut order: Vec<usize> = (0..self.outlines.len()).collect();
+        order.sort_by_key(|outline| self.outlines[*outline].len());
+
+        let mut chosen = vec![None; self.outlines.len()];
+        if self.search(&order, &mut chosen, rng) {
+            return Ok(chosen.into_iter().map(|role| role.expect("Every outline is filled once the search succeeds")).collect());
+        }
+
+        if self.ran_out_of_steps {
+            return Err(RejectStartReason::RoleListTooComplex);
+        }
+        match self.deepest_failure {
+            Some((_, outline)) => Err(RejectStartReason::RoleOutlineCannotBeSatisfied { outline: outline as u8 }),
+            None => Err(RejectStartReason::RoleListCannotCreateRoles),
+        }
+    }
+
+    fn search(&mut self, order: &[usize], chosen: &mut Vec<Option<Role>>, rng: &mut impl Rng) -> bool {
+        let depth = self.filled(chosen);
+        let Some(&outline) = order.get(depth) else {return true};
+
+        for role in self.candidates(outline, chosen, rng) {
+            if self.steps >= self.max_steps {
+                self.ran_out_of_steps = true;
+                return false;
+            }
+            self.steps += 1;
+
+            chosen[outline] = Some(role);
+            if self.requirements_can_be_met(chosen) && self.search(order, chosen, rng) {
+                return true;
+            }
+            chosen[outline] = None;
+        }
+
+        if self.deepest_failure.is_none_or(|(deepest, _)| depth > deepest) {
+            self.deepest_failure = Some((depth, outline));
+        }
+        false
+    }
+
+    fn filled(&self, chosen: &[Option<Role>]) -> usize {
+        chosen.iter().filter(|role| role.is_some()).count()
+    }
+
+    /// The roles this outline can become without breaking any rules, in a random order picked by weight
+    fn candidates(&self, outline: usize, chosen: &[Option<Role>], rng: &mut impl Rng) -> Vec<Role> {
+        let taken_roles: Vec<Role> = chosen.iter().flatten().copied().collect();
+
+        let mut candidates: Vec<(Role, f64)> = self.outlines[outline].iter()
+            .filter(|(role, _)|
+                role_can_generate(*role, self.enabled_roles, self.role_options, &taken_roles) &&
+                self.constraints.iter().all(|constraint| constraint.allows(*role, &taken_roles))
+            )
+            // Sorting by random^(1/weight) shuffles the roles so that heavier ones tend to come first
+            .map(|(role, weight)| (*role, rng.gen::<f64>().powf(1.0 / *weight as f64)))
+            .collect();
+        candidates.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+        candidates.into_iter().map(|(role, _)| role).collect()
+    }
+
+    /// False if some role's required role isn't picked and no empty outline can become it
+    fn requirements_can_be_met(&self, chosen: &[Option<Role>]) -> bool {
+        self.constraints.iter().all(|constraint| match constraint {
+            RoleListConstraint::Requires { role, required_role } =>
+                !chosen.contains(&Some(*role)) ||
+                chosen.contains(&Some(*required_role)) ||
+                chosen.iter().zip(self.outlines.iter()).any(|(chosen_role, outline)|
+                    chosen_role.is_none() && outline.iter().any(|(role, _)| role == required_role)
+                ),
+            RoleListConstraint::AtMost { .. } => true,
+        })
+    }
+}
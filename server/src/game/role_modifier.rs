@@ -0,0 +1,54 @@
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use super::{player::PlayerReference, role::Priority, visit::Visit, Game};
+
+/// A trait a player has on top of their role, no matter what that role is.
+/// Modifiers come from the player's outline in the role list, and stay with the player if their role changes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RoleModifier {
+    /// One more defense than their role has
+    Bulletproof,
+    /// Roleblocked every other night, starting on night 2
+    Lazy,
+    /// Appears to visit nobody, to anyone who sees visits
+    Blind,
+    /// Roleblocks only work half the time
+    Lucky,
+}
+
+/// What a blind player's visits look like
+pub static BLIND_APPEARED_VISITS: Option<Vec<Visit>> = Some(Vec::new());
+
+impl RoleModifier {
+    pub const LUCKY_ROLEBLOCK_CHANCE: f64 = 0.5;
+
+    pub fn values() -> Vec<Self> {
+        vec![Self::Bulletproof, Self::Lazy, Self::Blind, Self::Lucky]
+    }
+
+    /// Gives every player the modifiers of the outline their role came from
+    pub fn on_game_start(game: &mut Game) {
+        for (index, (_, player_ref)) in game.roles_to_players.clone().into_iter().enumerate() {
+            let Some(outline) = game.settings.role_list.0.get(index) else {continue};
+            let modifiers = outline.modifiers().to_vec();
+            player_ref.set_modifiers(game, modifiers);
+        }
+    }
+
+    pub fn on_night_priority(game: &mut Game, priority: Priority) {
+        if priority != Priority::Roleblock || !game.day_number().is_multiple_of(2) {return}
+
+        for player_ref in PlayerReference::all_players(game) {
+            if player_ref.alive(game) && player_ref.has_modifier(game, RoleModifier::Lazy) {
+                player_ref.roleblock(game, true);
+            }
+        }
+    }
+
+    /// Whether a lucky player shakes off this roleblock. Uses the game's rng, so replays play out the same
+    pub fn lucky_roleblock_fails(game: &mut Game, player_ref: PlayerReference) -> bool {
+        player_ref.has_modifier(game, RoleModifier::Lucky) && game.rng.gen_bool(Self::LUCKY_ROLEBLOCK_CHANCE)
+    }
+}
@@ -0,0 +1,91 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::role::{Role, RoleState};
+
+/// What the host changed about one role. Anything left as None keeps the role's default
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RoleOptions {
+    /// How many times the role can use its ability in a game. Only for roles with limited uses
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uses: Option<u8>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub defense: Option<u8>,
+    /// How many players can have this role
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub maximum_count: Option<u8>,
+    /// Whether the journal is read to everyone at the start. Only for the journalist
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub starts_public: Option<bool>,
+}
+
+/// Why the options the host set can't be used for a role
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RoleOptionsError {
+    /// The role doesn't have limited uses
+    UsesNotSupported,
+    StartsPublicNotSupported,
+    DefenseTooHigh,
+    /// Use the enabled roles to stop a role from generating
+    MaximumCountIsZero,
+}
+
+impl RoleOptions {
+    pub const MAX_DEFENSE: u8 = 3;
+
+    /// Checks that every option that is set makes sense for this role
+    pub fn check(&self, role: Role) -> Result<(), RoleOptionsError> {
+        if self.uses.is_some() && role.default_uses().is_none() {
+            return Err(RoleOptionsError::UsesNotSupported);
+        }
+        if self.starts_public.is_some() && role != Role::Journalist {
+            return Err(RoleOptionsError::StartsPublicNotSupported);
+        }
+        if self.defense.is_some_and(|defense| defense > Self::MAX_DEFENSE) {
+            return Err(RoleOptionsError::DefenseTooHigh);
+        }
+        if self.maximum_count == Some(0) {
+            return Err(RoleOptionsError::MaximumCountIsZero);
+        }
+        Ok(())
+    }
+}
+
+/// The options the host set, for every role they changed
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct RoleOptionsMap(pub HashMap<Role, RoleOptions>);
+
+impl RoleOptionsMap {
+    /// The first role whose options don't make sense for it
+    pub fn check(&self) -> Result<(), (Role, RoleOptionsError)> {
+        let mut roles: Vec<&Role> = self.0.keys().collect();
+        roles.sort();
+        for role in roles {
+            self.0[role].check(*role).map_err(|err| (*role, err))?;
+        }
+        Ok(())
+    }
+
+    /// A role's state at the start of the game, or when a player becomes it
+    pub fn new_state(&self, role: Role) -> RoleState {
+        let state = role.default_state();
+        match self.0.get(&role) {
+            Some(options) => state.with_options(options),
+            None => state,
+        }
+    }
+    /// How many times the role can use its main limited ability, or None if it doesn't have one
+    pub fn uses(&self, role: Role) -> Option<u8> {
+        self.0.get(&role).and_then(|options| options.uses).or(role.default_uses())
+    }
+    pub fn defense(&self, role: Role) -> u8 {
+        self.0.get(&role).and_then(|options| options.defense).unwrap_or(role.defense())
+    }
+    pub fn maximum_count(&self, role: Role) -> Option<u8> {
+        self.0.get(&role).and_then(|options| options.maximum_count).or(role.maximum_count())
+    }
+}
@@ -2,7 +2,7 @@ use std::{collections::HashSet, time::Duration};
 
 use serde::{Serialize, Deserialize};
 
-use super::{phase::PhaseType, role::Role, role_list::RoleList};
+use super::{chat::TeamChats, phase::PhaseType, role::Role, role_list::{CustomRoleSet, RoleList}, role_list_generation::RoleListConstraint, role_options::RoleOptionsMap};
 
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -10,8 +10,109 @@ pub struct Settings{
     pub role_list: RoleList,
     pub phase_times: PhaseTimeSettings,
     pub enabled_roles: HashSet<Role>,
+    /// Seed for the game's random number generator.
+    /// If this is None, a random seed is chosen when the game starts.
+    #[serde(default)]
+    pub seed: Option<u64>,
+    /// Pause the game whenever a living player loses connection
+    #[serde(default)]
+    pub auto_pause: bool,
+    #[serde(default)]
+    pub rules: GameRules,
+    /// Role sets the host made, which role outlines can use alongside the built in ones
+    #[serde(default)]
+    pub custom_role_sets: Vec<CustomRoleSet>,
+    #[serde(default)]
+    pub role_list_constraints: Vec<RoleListConstraint>,
+    /// Changes to the roles' uses, defense and other constants
+    #[serde(default)]
+    pub role_options: RoleOptionsMap,
 }
-#[derive(Debug, Clone, Serialize, Deserialize)]
+
+/// Rules that change how a game plays out, on top of its roles and phase times
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct GameRules {
+    /// Phases are sped up once this many players or fewer are alive. 0 never speeds them up
+    pub speed_up_living_players: u8,
+    /// How many times shorter phases are once they're sped up
+    pub speed_up_factor: u32,
+    /// How many votes a revealed mayor counts for, in nominations and in judgement
+    pub mayor_voting_power: u8,
+    pub nomination_threshold: NominationThreshold,
+    pub trials_per_day: u8,
+    #[serde(default)]
+    pub trial_system: TrialSystem,
+    /// Records how each night was resolved, for the hosts to look at after the game
+    #[serde(default)]
+    pub trace_night_actions: bool,
+    /// Which teams get their own chat at night
+    #[serde(default)]
+    pub team_chats: TeamChats,
+}
+impl GameRules {
+    pub const MAX_SPEED_UP_FACTOR: u32 = 10;
+    pub const MAX_MAYOR_VOTING_POWER: u8 = 10;
+    pub const MAX_TRIALS_PER_DAY: u8 = 10;
+
+    pub fn is_valid(&self) -> bool {
+        (1..=Self::MAX_SPEED_UP_FACTOR).contains(&self.speed_up_factor) &&
+        (1..=Self::MAX_MAYOR_VOTING_POWER).contains(&self.mayor_voting_power) &&
+        (1..=Self::MAX_TRIALS_PER_DAY).contains(&self.trials_per_day)
+    }
+}
+impl Default for GameRules {
+    fn default() -> Self {
+        Self {
+            speed_up_living_players: 3,
+            speed_up_factor: 2,
+            mayor_voting_power: 3,
+            nomination_threshold: NominationThreshold::Majority,
+            trials_per_day: 3,
+            trial_system: TrialSystem::Majority,
+            trace_night_actions: false,
+            team_chats: TeamChats::default(),
+        }
+    }
+}
+
+/// How the town decides who is executed during the day
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "camelCase")]
+pub enum TrialSystem {
+    /// Reaching the nomination threshold puts someone on trial right away
+    #[default]
+    Majority,
+    /// Whoever has the most votes when nomination ends is put on trial.
+    /// Nobody is put on trial if the most votes are tied
+    Plurality,
+    /// Reaching the nomination threshold skips the trial, and goes straight to final words before the execution
+    DirectLynch,
+}
+
+/// How many votes it takes to put someone on trial,
+/// out of the living players who haven't forfeited their vote
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum NominationThreshold {
+    /// More than half
+    Majority,
+    /// At least half
+    Half,
+    /// At least two thirds
+    TwoThirds,
+}
+impl NominationThreshold {
+    pub fn votes_required(&self, voters: usize) -> usize {
+        let required = match self {
+            Self::Majority => voters / 2 + 1,
+            Self::Half => voters.div_ceil(2),
+            Self::TwoThirds => (voters * 2).div_ceil(3),
+        };
+        required.max(1)
+    }
+}
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub struct PhaseTimeSettings{
     pub briefing: u64,
@@ -0,0 +1,159 @@
+use std::{collections::HashMap, panic::{self, AssertUnwindSafe}};
+
+use rand::Rng;
+use serde::Serialize;
+
+use crate::client_connection::ClientConnection;
+
+use super::{
+    bot::Bot,
+    event::on_game_ending::OnGameEnding,
+    invariants::InvariantViolation,
+    phase::PhaseStateMachine,
+    player::{PlayerIndex, PlayerInitializeParameters},
+    replay::GameLogEntry,
+    resolution_state::ResolutionState,
+    settings::Settings,
+    Game
+};
+
+/// Plays whole games with bots in every seat, without a lobby or any connections.
+/// Used to see how often each faction wins with some settings, and to find role interactions that panic.
+pub struct Simulation {
+    settings: Settings,
+}
+
+/// How one simulated game ended
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum SimulatedGameResult {
+    Ended{resolution: ResolutionState, days: u8},
+    /// The game reached the last day without ending, usually because nobody left can kill
+    /// and the bots never agree on who to vote for
+    ReachedMaxDay,
+    /// Roles couldn't be generated from the settings
+    CouldNotStart{reason: String},
+    Panicked{message: String},
+    InvariantViolated{day: u8, violations: Vec<InvariantViolation>},
+}
+
+/// A game that panicked, broke an invariant or couldn't start, with the seed to play it again
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulationFailure {
+    pub seed: u64,
+    pub result: SimulatedGameResult,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulationReport {
+    pub games: u32,
+    /// How many games ended with each resolution. Games that failed are left out
+    pub resolutions: HashMap<ResolutionState, u32>,
+    pub reached_max_day: u32,
+    total_days: u64,
+    pub failures: Vec<SimulationFailure>,
+}
+
+impl SimulationReport {
+    pub fn ended_games(&self) -> u32 {
+        self.resolutions.values().sum()
+    }
+    /// The chance of each resolution, out of the games that ended
+    pub fn win_rate(&self, resolution: &ResolutionState) -> f32 {
+        match self.ended_games() {
+            0 => 0.0,
+            ended => self.resolutions.get(resolution).copied().unwrap_or(0) as f32 / ended as f32,
+        }
+    }
+    pub fn average_days(&self) -> f32 {
+        match self.ended_games() {
+            0 => 0.0,
+            ended => self.total_days as f32 / ended as f32,
+        }
+    }
+}
+
+impl Simulation {
+    pub fn new(settings: Settings) -> Self {
+        Self { settings }
+    }
+
+    /// Plays every game with a seed taken from `rng`, so a whole run can be repeated from one seed
+    pub fn run(&self, games: u32, rng: &mut impl Rng) -> SimulationReport {
+        let mut report = SimulationReport::default();
+
+        for _ in 0..games {
+            let seed = rng.gen();
+            let result = self.play(seed);
+
+            report.games += 1;
+            match result {
+                SimulatedGameResult::Ended { resolution, days } => {
+                    *report.resolutions.entry(resolution).or_default() += 1;
+                    report.total_days += days as u64;
+                }
+                SimulatedGameResult::ReachedMaxDay => report.reached_max_day += 1,
+                result => report.failures.push(SimulationFailure { seed, result }),
+            }
+        }
+
+        report
+    }
+
+    /// Plays one game. The same seed always plays the same game
+    pub fn play(&self, seed: u64) -> SimulatedGameResult {
+        let settings = Settings { seed: Some(seed), ..self.settings.clone() };
+
+        match catch_panic(|| Self::play_game(settings, seed)) {
+            Ok(result) => result,
+            Err(message) => SimulatedGameResult::Panicked { message },
+        }
+    }
+
+    fn play_game(settings: Settings, seed: u64) -> SimulatedGameResult {
+        // Started the same way a lobby starts a game, so the same settings are rejected and the same role lists are rerolled
+        let players = (0..settings.role_list.0.len()).map(|index| PlayerInitializeParameters {
+            connection: ClientConnection::Bot(Bot::for_player(seed, index as PlayerIndex)),
+            name: format!("Bot {}", index + 1),
+            host: false,
+        }).collect();
+        let mut game = match Game::new(settings, players, Vec::new()) {
+            Ok(game) => game,
+            Err(reason) => return SimulatedGameResult::CouldNotStart { reason: format!("{reason:?}") },
+        };
+
+        while game.day_number() < u8::MAX {
+            if let Some(resolution) = ResolutionState::game_is_over(&game) {
+                Self::end_game(&mut game);
+                return SimulatedGameResult::Ended { resolution, days: game.day_number() };
+            }
+            PhaseStateMachine::next_phase(&mut game, None);
+
+            if let Err(violations) = game.check_invariants() {
+                return SimulatedGameResult::InvariantViolated { day: game.day_number(), violations };
+            }
+        }
+
+        SimulatedGameResult::ReachedMaxDay
+    }
+
+    /// Ends the game the same way `Game::tick` does, so game ending listeners are also tested
+    fn end_game(game: &mut Game) {
+        game.log_entry(GameLogEntry::GameEnded);
+        OnGameEnding::invoke(game);
+    }
+}
+
+fn catch_panic<T>(f: impl FnOnce() -> T) -> Result<T, String> {
+    panic::catch_unwind(AssertUnwindSafe(f)).map_err(|payload| {
+        if let Some(message) = payload.downcast_ref::<&str>() {
+            message.to_string()
+        } else if let Some(message) = payload.downcast_ref::<String>() {
+            message.clone()
+        } else {
+            "Unknown panic".to_string()
+        }
+    })
+}
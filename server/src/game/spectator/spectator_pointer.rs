@@ -1,7 +1,7 @@
 use std::time::Duration;
 
 use crate::{
-    client_connection::ClientConnection, game::{chat::{ChatGroup, ChatMessage}, phase::PhaseState, player::PlayerReference, Game, GameOverReason}, packet::ToClientPacket
+    client_connection::ClientConnection, game::{chat::{ChatGroup, ChatMessage}, components::game_history::GameHistory, phase::PhaseState, player::PlayerReference, Game, GameOverReason}, packet::ToClientPacket
 };
 
 use super::Spectator;
@@ -72,16 +72,19 @@ impl SpectatorPointer {
             },
             ToClientPacket::EnabledRoles { roles: game.settings.enabled_roles.clone().into_iter().collect() },
             ToClientPacket::RoleList {role_list: game.settings.role_list.clone()},
+            ToClientPacket::RoleOptions {role_options: game.settings.role_options.clone()},
             ToClientPacket::PlayerAlive{
                 alive: PlayerReference::all_players(game).map(|p|p.alive(game)).collect()
             },
             ToClientPacket::PhaseTimes {
                 phase_time_settings: game.settings.phase_times.clone()
-            }
+            },
+            ToClientPacket::GameRules { rules: game.settings.rules.clone() }
         ]);
 
         if !game.ticking {
-            self.send_packet(game, ToClientPacket::GameOver { reason: GameOverReason::Draw })
+            self.send_packet(game, ToClientPacket::GameOver { reason: GameOverReason::Draw });
+            self.send_packet(game, ToClientPacket::GameSummary { summary: GameHistory::summary(game) });
         }
 
         if let PhaseState::Testimony { player_on_trial, .. }
@@ -102,7 +105,8 @@ impl SpectatorPointer {
                 phase: game.current_phase().clone(),
                 day_number: game.phase_machine.day_number 
             },
-            ToClientPacket::PhaseTimeLeft { seconds_left: game.phase_machine.time_remaining.as_secs() }
+            ToClientPacket::PhaseTimeLeft { deadline: game.phase_deadline() },
+            ToClientPacket::GamePaused { paused: game.paused().is_some() }
         ]);
 
         self.requeue_chat_messages(game);
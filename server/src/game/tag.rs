@@ -1,7 +1,7 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 
-#[derive(PartialOrd, Ord, Debug, Clone, PartialEq, Eq, Serialize, Copy)]
+#[derive(PartialOrd, Ord, Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Copy)]
 #[serde(rename_all = "camelCase")]
 pub enum Tag{
     GodfatherBackup,
@@ -1,6 +1,9 @@
+use serde::Serialize;
+
 use super::player::PlayerReference;
 
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct Visit {
     pub target: PlayerReference,
 
@@ -0,0 +1,59 @@
+use std::collections::HashSet;
+
+use super::{player::PlayerReference, resolution_state::ResolutionState, role_list::Faction, Game};
+
+/// How a player wins. Every role declares its own through `RoleStateImpl::win_condition`,
+/// so the game over check never needs to know about specific roles.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WinCondition {
+    /// Wins if the game ends in any of these resolution states
+    ResolutionStateReached{win_if_any: HashSet<ResolutionState>},
+    /// Wins by doing something the role keeps track of itself, like a jester getting lynched.
+    /// These roles decide if they won by overriding `RoleStateImpl::get_won_game`
+    RoleStateWon,
+    /// Wins if alive when the game ends, however it ends
+    AliveAtEnd,
+    /// Hasn't decided how to win yet, like a wildcard that hasn't picked a role.
+    /// Wins however the game ends, and the game can't end while only undecided players are left
+    Undecided,
+}
+
+impl WinCondition {
+    /// Wins when the faction does. Neutral isn't a team, so neutral roles win on their own
+    pub fn wins_with_faction(faction: Faction) -> Self {
+        let resolution_state = match faction {
+            Faction::Mafia => ResolutionState::Mafia,
+            Faction::Cult => ResolutionState::Cult,
+            Faction::Town => ResolutionState::Town,
+            Faction::Fiends => ResolutionState::Fiends,
+            Faction::Neutral => return Self::RoleStateWon,
+        };
+        Self::wins_with(vec![resolution_state])
+    }
+    pub fn wins_with(resolution_states: Vec<ResolutionState>) -> Self {
+        Self::ResolutionStateReached { win_if_any: resolution_states.into_iter().collect() }
+    }
+
+    /// None if this doesn't depend on how the game ends
+    pub fn required_resolution_states_for_win(&self) -> Option<&HashSet<ResolutionState>> {
+        match self {
+            Self::ResolutionStateReached { win_if_any } => Some(win_if_any),
+            _ => None
+        }
+    }
+    /// Whether a player with this win condition would let the game end in this resolution state
+    pub fn can_win_with(&self, resolution_state: &ResolutionState) -> bool {
+        self.required_resolution_states_for_win().is_none_or(|win_if_any| win_if_any.contains(resolution_state))
+    }
+
+    /// Whether the player won, for win conditions that depend on how the game ended.
+    /// Roles that win by themselves decide this in `RoleStateImpl::get_won_game`
+    pub fn won(&self, game: &Game, actor_ref: PlayerReference, resolution_state: &ResolutionState) -> bool {
+        match self {
+            Self::ResolutionStateReached { win_if_any } => win_if_any.contains(resolution_state),
+            Self::RoleStateWon => false,
+            Self::AliveAtEnd => actor_ref.alive(game),
+            Self::Undecided => true,
+        }
+    }
+}
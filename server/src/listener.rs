@@ -1,17 +1,46 @@
-use std::{net::SocketAddr, collections::HashMap, sync::{Mutex, Arc}, time::Duration};
+use std::{cell::Cell, net::SocketAddr, collections::HashMap, fs, panic::{self, AssertUnwindSafe}, sync::{Mutex, Arc}};
 
 use rand::random;
+use tokio::{sync::mpsc::{self, UnboundedSender}, task::JoinHandle};
 use tokio_tungstenite::tungstenite::Message;
 
 use crate::{
-    lobby::{lobby_client::LobbyClientID, Lobby}, 
-    log, 
-    packet::{LobbyPreviewData, RejectJoinReason, ToClientPacket, ToServerPacket}, 
-    websocket_connections::connection::Connection
+    lobby::{lobby_task::{LobbyMessage, LobbyTask}, snapshot::{LobbySnapshot, SNAPSHOT_DIRECTORY}, Lobby},
+    log,
+    packet::{LobbyPreviewData, RejectJoinReason, ToClientPacket, ToServerPacket},
+    websocket_connections::{connection::Connection, ForceLock}
 };
 
 pub type RoomCode = usize;
 
+thread_local! {
+    static CATCHING_LOBBY_PANIC: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Whether this thread is running lobby code that will have its panics caught.
+/// The panic hook uses this to avoid restarting the whole server for a panic that only affects one lobby.
+pub fn is_catching_lobby_panic() -> bool {
+    CATCHING_LOBBY_PANIC.with(|catching| catching.get())
+}
+
+/// Runs lobby code, catching any panic so that only that lobby is affected.
+/// Returns the panic message if it panicked.
+pub(crate) fn catch_lobby_panic<T>(f: impl FnOnce() -> T) -> Result<T, String> {
+    let was_catching = CATCHING_LOBBY_PANIC.with(|catching| catching.replace(true));
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    CATCHING_LOBBY_PANIC.with(|catching| catching.set(was_catching));
+
+    result.map_err(|payload| {
+        if let Some(message) = payload.downcast_ref::<&str>() {
+            message.to_string()
+        } else if let Some(message) = payload.downcast_ref::<String>() {
+            message.clone()
+        } else {
+            "Unknown panic".to_string()
+        }
+    })
+}
+
 struct ListenerClient {
     connection: Connection,
     location: ListenerClientLocation,
@@ -26,18 +55,31 @@ impl ListenerClient{
 
 }
 
+/// Which lobby a client's messages are sent to.
+/// The lobby itself keeps track of which of its clients each connection is.
 #[derive(Debug, PartialEq, Eq)]
 enum ListenerClientLocation {
     InLobby{
         room_code: RoomCode,
-        lobby_client_id: LobbyClientID,
     },
     OutsideLobby
 }
 
+/// A lobby running on its own task
+struct LobbyHandle {
+    sender: UnboundedSender<LobbyMessage>,
+    task: JoinHandle<()>,
+    /// Kept up to date by the lobby's task, so the lobby list doesn't have to wait on every lobby
+    preview: LobbyPreviewData,
+}
+
+/// Routes messages from connections to the lobby they're in.
+/// Every lobby runs on its own task, so this routing table is the only thing they share.
 pub struct Listener {
-    lobbies: HashMap<RoomCode, Lobby>,
+    lobbies: HashMap<RoomCode, LobbyHandle>,
     clients: HashMap<SocketAddr, ListenerClient>,
+    /// Set once the server is restarting, so old connections can't make lobbies the next server doesn't know about
+    shut_down: bool,
 }
 impl Listener{
     #[allow(clippy::new_without_default)]
@@ -45,188 +87,177 @@ impl Listener{
         Self {
             lobbies: HashMap::new(),
             clients: HashMap::new(),
+            shut_down: false,
         }
     }
 
-    pub fn start(listener: Arc<Mutex<Self>>) {
-        const DESIRED_FRAME_TIME: Duration = Duration::from_millis(1000);
-
-        tokio::spawn(async move {
-            let mut frame_start_time = tokio::time::Instant::now();
-            loop {
-                let delta_time = frame_start_time.elapsed();
-                frame_start_time = tokio::time::Instant::now();
-
-                if let Ok(mut listener) = listener.lock() {
-                    let mut closed_lobbies = Vec::new();
-                    
-                    let Listener { ref mut lobbies, clients: ref _players} = *listener;
-
-                    // log!(info "Listener"; "lobbies: {:?} players: {:?}", lobbies.keys(), _players.len());
-
-                    for (room_code, lobby) in lobbies.iter_mut() {
-                        if lobby.is_closed() {
-                            closed_lobbies.push(*room_code);
-                        } else {
-                            lobby.tick(delta_time);
-                        }
-                    }
-
-                    for key in closed_lobbies {
-                        log!(important "Lobby"; "Closed {key}");
-                        listener.delete_lobby(key);
-                    }
-                } else { 
-                    return;
-                }
+    /// Stops routing messages to every lobby, which makes their tasks stop without deleting their snapshots.
+    /// Returns the tasks, so the server can wait for them before the next one restores the snapshots
+    pub fn shut_down_lobbies(&mut self) -> Vec<JoinHandle<()>> {
+        self.shut_down = true;
+        for client in self.clients.values_mut() {
+            client.location = ListenerClientLocation::OutsideLobby;
+        }
+        self.lobbies.drain().map(|(_, lobby)| lobby.task).collect()
+    }
 
-                tokio::time::sleep(DESIRED_FRAME_TIME.saturating_sub(tokio::time::Instant::now() - frame_start_time)).await;
+    /// Brings back every lobby from the last snapshots.
+    /// Lobbies that fail to restore are skipped, and their snapshot is deleted.
+    pub fn restore_snapshot(listener: &Arc<Mutex<Self>>) {
+        let Ok(entries) = fs::read_dir(SNAPSHOT_DIRECTORY) else {return};
+
+        let paths = entries.filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|extension| extension == "json"));
+
+        for path in paths {
+            let snapshot = match fs::read_to_string(&path)
+                .map_err(|err| err.to_string())
+                .and_then(|json| serde_json::from_str::<LobbySnapshot>(&json).map_err(|err| err.to_string()))
+            {
+                Ok(snapshot) => snapshot,
+                Err(err) => {
+                    log!(error "Listener"; "Failed to read lobby snapshot {}: {}", path.display(), err);
+                    let _ = fs::remove_file(&path);
+                    continue;
+                }
+            };
+            let room_code = snapshot.room_code();
+
+            // Restoring a game re-simulates it, which runs role code that might panic
+            match catch_lobby_panic(|| Lobby::from_snapshot(snapshot)) {
+                Ok(Ok(lobby)) => {
+                    log!(important "Lobby"; "Restored {room_code}");
+                    listener.force_lock().add_lobby(listener, room_code, lobby);
+                    continue;
+                },
+                Ok(Err(reason)) => log!(error "Lobby"; "Failed to restore {}: {:?}", room_code, reason),
+                Err(message) => log!(error "Lobby"; "Failed to restore {}: panicked while rebuilding the game: {}", room_code, message),
             }
-        });
+            let _ = fs::remove_file(&path);
+        }
     }
 
-    fn create_lobby(&mut self) -> Option<RoomCode>{
+    fn create_lobby(&mut self, listener: &Arc<Mutex<Self>>) -> Option<RoomCode>{
+        if self.shut_down {return None}
+
         let Some(room_code) = ((random::<u16>() as usize)..usize::MAX).find(
             |code| !self.lobbies.contains_key(code)
         ) else {
             return None;
         };
 
-        let lobby = Lobby::new(room_code);
-        self.lobbies.insert(room_code, lobby);
+        self.add_lobby(listener, room_code, Lobby::new(room_code));
         Some(room_code)
     }
-    fn delete_lobby(&mut self, room_code: RoomCode){
-        let players_to_remove: Vec<_> = self.clients.iter().filter(|p| 
-            if let ListenerClientLocation::InLobby{room_code: player_room_code, ..} = p.1.location {
-                player_room_code == room_code 
-            }else{
-                false
-            }
-        ).map(|f|*f.0).collect();
+    fn add_lobby(&mut self, listener: &Arc<Mutex<Self>>, room_code: RoomCode, lobby: Lobby){
+        let (sender, receiver) = mpsc::unbounded_channel();
+
+        let preview = lobby.preview();
+        let task = tokio::spawn(LobbyTask::new(room_code, lobby, Arc::downgrade(listener)).run(receiver));
+        self.lobbies.insert(room_code, LobbyHandle { sender, task, preview });
+    }
 
-        for player in players_to_remove{
-            self.set_player_outside_lobby(&player, false);
+    /// Called by a lobby's task when its entry in the lobby list changes
+    pub(crate) fn set_lobby_preview(&mut self, room_code: RoomCode, preview: LobbyPreviewData){
+        if let Some(lobby) = self.lobbies.get_mut(&room_code) {
+            lobby.preview = preview;
         }
-        self.lobbies.remove(&room_code);
     }
+    /// Called by a lobby's task when it stops, so nothing is sent to it anymore
+    pub(crate) fn remove_lobby(&mut self, room_code: RoomCode){
+        self.lobbies.remove(&room_code);
 
-    fn set_player_in_lobby_initial_connect(&mut self, connection: &Connection, room_code: RoomCode){
-        let Some(lobby) = self.lobbies.get_mut(&room_code) else {
-            connection.send(ToClientPacket::RejectJoin { reason: RejectJoinReason::RoomDoesntExist });
-            return;
-        };
+        for client in self.clients.values_mut() {
+            if client.location == (ListenerClientLocation::InLobby { room_code }) {
+                client.location = ListenerClientLocation::OutsideLobby;
+            }
+        }
+    }
+    /// Called by a lobby's task when it panics
+    pub(crate) fn close_crashed_lobby(&mut self, room_code: RoomCode, message: String){
+        for client in self.clients.values() {
+            if client.location == (ListenerClientLocation::InLobby { room_code }) {
+                client.connection.send(ToClientPacket::LobbyCrashed { message: message.clone() });
+            }
+        }
+        self.remove_lobby(room_code);
+        log!(important "Lobby"; "Closed {room_code} after it crashed");
+    }
+    /// Called by a lobby's task when it kicks someone, or turns away someone who asked to join
+    pub(crate) fn set_client_outside_lobby(&mut self, address: &SocketAddr, room_code: RoomCode){
+        if let Some(client) = self.clients.get_mut(address) {
+            if client.location == (ListenerClientLocation::InLobby { room_code }) {
+                client.location = ListenerClientLocation::OutsideLobby;
+            }
+        }
+    }
 
-        let Some(sender_player_location) = self.clients
-            .get_mut(connection.get_address())
-            .map(|p|&mut p.location)
-        else{
+    /// Sends a join or rejoin message to a lobby. The lobby tells the client if they got in.
+    fn send_join_to_lobby(&mut self, connection: &Connection, room_code: RoomCode, message: LobbyMessage){
+        let Some(client) = self.clients.get_mut(connection.get_address()) else {
             log!(error "Listener"; "{} {}", "Received packet from unconnected player!", connection.get_address());
             connection.send(ToClientPacket::RejectJoin { reason: RejectJoinReason::ServerBusy });
             return;
         };
 
-        if let Ok(lobby_client_id) = lobby.join_player(&connection.get_sender()) {
-            *sender_player_location = ListenerClientLocation::InLobby { room_code, lobby_client_id };
-        }
-        
-        connection.send(ToClientPacket::LobbyName { name: lobby.name.clone() })
-    }
-    fn set_player_in_lobby_reconnect(&mut self, connection: &Connection, room_code: RoomCode, lobby_client_id: LobbyClientID){
-
-        let Some(lobby) = self.lobbies.get_mut(&room_code) else {
+        let Some(lobby) = self.lobbies.get(&room_code) else {
             connection.send(ToClientPacket::RejectJoin { reason: RejectJoinReason::RoomDoesntExist });
             return;
         };
 
-        let Some(sender_player_location) = 
-            self.clients
-            .get_mut(connection.get_address())
-            .map(|p|&mut p.location)
-        else{
-            log!(error "Listener"; "{} {}", "Received packet from unconnected player!", connection.get_address());
-            connection.send(ToClientPacket::RejectJoin { reason: RejectJoinReason::ServerBusy });
+        if lobby.sender.send(message).is_err() {
+            // The lobby closed, but its task hasn't removed it yet
+            connection.send(ToClientPacket::RejectJoin { reason: RejectJoinReason::RoomDoesntExist });
             return;
-        };
-
-        if lobby.rejoin_player(&connection.get_sender(), lobby_client_id).is_ok() {
-            *sender_player_location = ListenerClientLocation::InLobby { room_code, lobby_client_id };
-        }
-        
-        connection.send(ToClientPacket::LobbyName { name: lobby.name.clone() })
-    }
-    //returns if player was in the lobby
-    fn set_player_outside_lobby(&mut self, address: &SocketAddr, rejoinable: bool) -> bool {
-        let Some(sender_player_location) = self.clients
-            .get_mut(address)
-            .map(|p|&mut p.location)
-        else{
-            log!(error "Listener"; "{} {}", "Attempted leave a non player that isn't in the map", address);
-            return false;
-        };
-        let ListenerClientLocation::InLobby { room_code, lobby_client_id } = sender_player_location else {return false};
-        if let Some(lobby) = self.lobbies.get_mut(room_code) {
-            if rejoinable {
-                lobby.remove_player_rejoinable(*lobby_client_id);
-            }else{
-                lobby.remove_player(*lobby_client_id)
-            }
         }
-        *sender_player_location = ListenerClientLocation::OutsideLobby;
-        true
+        client.location = ListenerClientLocation::InLobby { room_code };
     }
-    
-    pub fn create_player(&mut self, connection: &Connection) {
-        self.clients.insert(*connection.get_address(), ListenerClient::new(connection.clone()));
-    }
-
-    pub fn delete_player(&mut self, address: &SocketAddr) -> Result<(), &'static str> {
-        let Some(disconnected_player_location) = self.clients
-            .remove(address)
-            .map(|p|p.location)
-        else{
-            return Err("Player doesn't exist");
+    /// Sends a message to the lobby the client is in, if they're in one
+    fn send_to_clients_lobby(&mut self, address: &SocketAddr, message: LobbyMessage){
+        let Some(client) = self.clients.get_mut(address) else {
+            log!(error "Listener"; "{} {}", "Received lobby/game packet from unconnected player!", address);
+            return;
         };
+        let ListenerClientLocation::InLobby { room_code } = client.location else {return};
 
-        if let ListenerClientLocation::InLobby { room_code, lobby_client_id } = disconnected_player_location {
-            if let Some(lobby) = self.lobbies.get_mut(&room_code) {
-                lobby.remove_player(lobby_client_id)
-            }
+        if self.lobbies.get(&room_code).map(|lobby| lobby.sender.send(message).is_ok()) != Some(true) {
+            log!(error "Listener"; "{} {}", "Received a message from a player in a lobby that doesn't exist", address);
+            client.location = ListenerClientLocation::OutsideLobby;
         }
-
-        Ok(())
     }
+    fn leave_lobby(&mut self, address: &SocketAddr, rejoinable: bool){
+        self.send_to_clients_lobby(address, LobbyMessage::Leave { address: *address, rejoinable });
 
-    fn get_address_from_location(&self, location: ListenerClientLocation) -> Option<SocketAddr> {
-        for (address, player) in self.clients.iter() {
-            if location == player.location{
-                return Some(*address);
-            }
+        if let Some(client) = self.clients.get_mut(address) {
+            client.location = ListenerClientLocation::OutsideLobby;
         }
-        None
     }
 
     pub fn on_connect(&mut self, connection: &Connection) {
-        self.create_player(connection);
+        self.clients.insert(*connection.get_address(), ListenerClient::new(connection.clone()));
     }
 
     pub fn on_disconnect(&mut self, connection: Connection) -> Result<(), &'static str> {
-        self.set_player_outside_lobby(connection.get_address(), true);
+        if !self.clients.contains_key(connection.get_address()) {
+            return Err("Player doesn't exist");
+        }
+        self.leave_lobby(connection.get_address(), true);
+        self.clients.remove(connection.get_address());
         Ok(())
-        // self.delete_player(connection.get_address())
     }
 
-    pub fn on_message(&mut self, connection: &Connection, message: &Message) {
+    pub fn on_message(&mut self, listener: &Arc<Mutex<Self>>, connection: &Connection, message: &Message) {
         if message.is_empty() { return }
 
-        log!(info "Listener"; "{}: {}", &connection.get_address().to_string(), message);
-        if let Err(k) = self.handle_message(connection, message){
+        if let Err(k) = self.handle_message(listener, connection, message){
             log!(error "Listener"; "Serde error when receiving message from {}: {}\n{}", &connection.get_address().to_string(), k, message);
         }
     }
 
-    fn handle_message(&mut self, connection: &Connection, message: &Message) -> Result<(), serde_json::Error> {
+    fn handle_message(&mut self, listener: &Arc<Mutex<Self>>, connection: &Connection, message: &Message) -> Result<(), serde_json::Error> {
         let incoming_packet = serde_json::from_str::<ToServerPacket>(message.to_string().as_str())?;
+        log!(info "Listener"; "{}: {:?}", &connection.get_address().to_string(), incoming_packet);
 
         match incoming_packet {
             ToServerPacket::Ping => {
@@ -234,87 +265,36 @@ impl Listener{
             },
             ToServerPacket::LobbyListRequest => {
                 connection.send(ToClientPacket::LobbyList{lobbies: self.lobbies.iter()
-                    .map(|(room_code, lobby)|
-                        (*room_code, LobbyPreviewData { 
-                            name: lobby.name.clone(),
-                            in_game: lobby.is_in_game(),
-                            players: lobby.get_player_list() 
-                        }
-                    ))
+                    .map(|(room_code, lobby)| (*room_code, lobby.preview.clone()))
                     .collect::<HashMap<RoomCode, LobbyPreviewData>>()});
             },
             ToServerPacket::ReJoin {room_code, player_id } => {
-                self.set_player_in_lobby_reconnect(connection, room_code, player_id);
+                self.send_join_to_lobby(connection, room_code, LobbyMessage::ReJoin { connection: connection.clone(), lobby_client_id: player_id });
             }
             ToServerPacket::Join{ room_code } => {
-                self.set_player_in_lobby_initial_connect(connection, room_code);
+                self.send_join_to_lobby(connection, room_code, LobbyMessage::Join { connection: connection.clone() });
             },
             ToServerPacket::Host => {
-                let Some(room_code) = self.create_lobby() else {
+                let Some(room_code) = self.create_lobby(listener) else {
                     connection.send(ToClientPacket::RejectJoin { reason: RejectJoinReason::ServerBusy });
                     return Ok(());
                 };
-                
-                self.set_player_in_lobby_initial_connect(connection, room_code);
+
+                self.send_join_to_lobby(connection, room_code, LobbyMessage::Join { connection: connection.clone() });
 
                 log!(important "Lobby"; "Created {room_code}");
             },
             ToServerPacket::Leave => {
-                self.set_player_outside_lobby(connection.get_address(), false);
+                self.leave_lobby(connection.get_address(), false);
             },
             ToServerPacket::Kick { player_id: kicked_player_id } => {
-                let Some(host_location) = self.clients
-                    .get(connection.get_address())
-                    .map(|p|&p.location)
-                else{
-                    log!(error "Listener"; "{} {}", "Received lobby/game packet from unconnected player!", connection.get_address());
-                    return Ok(());
-                };
-
-                let ListenerClientLocation::InLobby{room_code, lobby_client_id: host_id} = host_location else {
-                    log!(error "Listener"; "{} {}", "Received lobby/game packet from player not in a lobby!", connection.get_address());
-                    return Ok(());
-                };
-
-                if let Some(lobby) = self.lobbies.get_mut(room_code){
-                    if !lobby.is_host(*host_id) {return Ok(());}
-
-                    let kicked_player = self.get_address_from_location(ListenerClientLocation::InLobby { room_code: *room_code, lobby_client_id: kicked_player_id });
-                    if let Some(kicked_player_address) = kicked_player {
-                        if let Some(connection) = self.clients.get(&kicked_player_address).map(|p|p.connection.clone()) {
-                            connection.send(ToClientPacket::RejectJoin { reason: RejectJoinReason::ServerBusy });
-                            self.set_player_outside_lobby(&kicked_player_address, false);
-                        }
-                    }else{
-                        //Nobody is connected to that lobby with that id,
-                        //Maybe they already left
-
-                        if let Some(lobby) = self.lobbies.get_mut(room_code){
-                            lobby.remove_player(kicked_player_id);
-                        }
-                    }
-                }
+                self.send_to_clients_lobby(connection.get_address(), LobbyMessage::Kick { address: *connection.get_address(), kicked_player_id });
             },
             _ => {
-                let Some(sender_player_location) = self.clients
-                    .get_mut(connection.get_address())
-                    .map(|p|&mut p.location)
-                else{
-                    log!(error "Listener"; "{} {}", "Received lobby/game packet from unconnected player!", connection.get_address());
-                    return Ok(());
-                };
-
-                if let ListenerClientLocation::InLobby { room_code, lobby_client_id } = sender_player_location {
-                    if let Some(lobby) = self.lobbies.get_mut(room_code){
-                        lobby.on_client_message(&connection.get_sender(), *lobby_client_id, incoming_packet);
-                    } else {
-                        //Player is in a lobby that doesn't exist
-                        panic!("Recieved a message from a player in a lobby that doesnt exist")
-                    }
-                }
+                self.send_to_clients_lobby(connection.get_address(), LobbyMessage::ClientMessage { connection: connection.clone(), packet: incoming_packet });
             }
         }
-    
+
         Ok(())
     }
-}
\ No newline at end of file
+}
@@ -6,6 +6,8 @@ use crate::game::{player::PlayerIndex, spectator::spectator_pointer::SpectatorIn
 pub struct GameClient{
     pub client_location: GameClientLocation,
     pub host: bool,
+    /// Spectators who joined after the game started play in the next game
+    pub joined_mid_game: bool,
 
     pub last_message_times: VecDeque<Instant>,
 }
@@ -15,10 +17,12 @@ pub enum GameClientLocation {
     Spectator(SpectatorIndex)
 }
 impl GameClient {
+    /// A spectator who joined after the game started
     pub fn new_spectator(index: SpectatorIndex, host: bool)->Self{
         GameClient{
             client_location: GameClientLocation::Spectator(index),
             host,
+            joined_mid_game: true,
             last_message_times: VecDeque::new(),
         }
     }
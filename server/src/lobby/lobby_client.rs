@@ -1,5 +1,6 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
+use crate::game::bot::Bot;
 use crate::game::player::PlayerReference;
 use crate::game::spectator::spectator_pointer::SpectatorPointer;
 use crate::game::Game;
@@ -16,7 +17,7 @@ pub struct LobbyClient{
     pub host: bool,
     pub client_type: LobbyClientType,
 }
-#[derive(Clone, Debug, Serialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(tag = "type")]
 #[serde(rename_all = "camelCase")]
 pub enum LobbyClientType{
@@ -32,6 +33,11 @@ impl LobbyClient {
            connection: ClientConnection::Connected(connection), host, client_type: LobbyClientType::Player{name}
         }
     }
+    pub fn new_bot(name: String, bot: Bot)->Self{
+        LobbyClient{
+            connection: ClientConnection::Bot(bot), host: false, client_type: LobbyClientType::Player{name}
+        }
+    }
     pub fn new_from_game_client(game: &Game, game_client: GameClient)->Self{
 
         match game_client.client_location {
@@ -54,6 +60,9 @@ impl LobbyClient {
         }
 
         
+    }
+    pub fn is_bot(&self) -> bool {
+        matches!(self.connection, ClientConnection::Bot(_))
     }
     pub fn set_host(&mut self) {
         self.host = true;
@@ -0,0 +1,198 @@
+use std::{collections::HashMap, net::SocketAddr, sync::{Mutex, Weak}, time::Duration};
+
+use tokio::{sync::mpsc::UnboundedReceiver, time::{self, Instant, MissedTickBehavior}};
+
+use crate::{
+    listener::{catch_lobby_panic, Listener, RoomCode},
+    log,
+    packet::{LobbyPreviewData, RejectJoinReason, ToClientPacket, ToServerPacket},
+    websocket_connections::{connection::Connection, ForceLock}
+};
+
+use super::{lobby_client::LobbyClientID, snapshot::LobbySnapshot, Lobby};
+
+const SNAPSHOT_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Something the listener routed to a lobby
+pub enum LobbyMessage {
+    Join{connection: Connection},
+    ReJoin{connection: Connection, lobby_client_id: LobbyClientID},
+    Leave{address: SocketAddr, rejoinable: bool},
+    Kick{address: SocketAddr, kicked_player_id: LobbyClientID},
+    ClientMessage{connection: Connection, packet: ToServerPacket},
+}
+impl LobbyMessage {
+    fn description(&self) -> String {
+        match self {
+            Self::Join { connection } => format!("{} joining", connection.get_address()),
+            Self::ReJoin { connection, lobby_client_id } => format!("{} rejoining as {}", connection.get_address(), lobby_client_id),
+            Self::Leave { address, .. } => format!("{} leaving", address),
+            Self::Kick { address, kicked_player_id } => format!("{} kicking {}", address, kicked_player_id),
+            Self::ClientMessage { connection, packet } => format!("{} sending {:?}", connection.get_address(), packet),
+        }
+    }
+}
+
+/// Owns a lobby and runs it on its own task, so lobbies never wait on each other.
+/// It only talks to the listener to keep its lobby list entry up to date and when it closes.
+pub struct LobbyTask {
+    room_code: RoomCode,
+    lobby: Lobby,
+    /// Which lobby client each connection is
+    clients: HashMap<SocketAddr, (LobbyClientID, Connection)>,
+    /// Weak so the task stops once the server it belongs to shuts down
+    listener: Weak<Mutex<Listener>>,
+    preview: LobbyPreviewData,
+    last_tick: Instant,
+}
+
+impl LobbyTask {
+    pub fn new(room_code: RoomCode, lobby: Lobby, listener: Weak<Mutex<Listener>>) -> Self {
+        Self {
+            room_code,
+            preview: lobby.preview(),
+            lobby,
+            clients: HashMap::new(),
+            listener,
+            last_tick: Instant::now(),
+        }
+    }
+
+    pub async fn run(mut self, mut receiver: UnboundedReceiver<LobbyMessage>) {
+        let mut snapshot_interval = time::interval_at(Instant::now() + SNAPSHOT_INTERVAL, SNAPSHOT_INTERVAL);
+        snapshot_interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        loop {
+            // Sleep until the phase ends or a message arrives, whichever is first
+            let result = tokio::select! {
+                _ = time::sleep(self.lobby.time_until_next_tick()) => self.tick(),
+                _ = snapshot_interval.tick() => {
+                    self.save_snapshot();
+                    Ok(())
+                },
+                message = receiver.recv() => {
+                    // The listener is gone, so the server is shutting down
+                    let Some(message) = message else {return};
+                    let description = message.description();
+
+                    // Catch up first, so the message sees (and any timers sent are based on) the current time
+                    self.tick().and_then(|_| catch_lobby_panic(|| self.on_message(message))
+                        .map_err(|message| format!("{} panicked while handling {}: {}", self.room_code, description, message))
+                    )
+                }
+            };
+
+            // Keep the snapshot around so the lobby comes back when the server restarts
+            if self.listener.strong_count() == 0 {
+                return;
+            }
+
+            if let Err(message) = result {
+                log!(fatal "Lobby"; "{}", message);
+                LobbySnapshot::delete(self.room_code);
+                if let Some(listener) = self.listener.upgrade() {
+                    listener.force_lock().close_crashed_lobby(self.room_code, message);
+                }
+                return;
+            }
+
+            if self.lobby.is_closed() {
+                break;
+            }
+
+            let preview = self.lobby.preview();
+            if preview != self.preview {
+                self.preview = preview.clone();
+                if let Some(listener) = self.listener.upgrade() {
+                    listener.force_lock().set_lobby_preview(self.room_code, preview);
+                }
+            }
+        }
+
+        log!(important "Lobby"; "Closed {}", self.room_code);
+        LobbySnapshot::delete(self.room_code);
+        if let Some(listener) = self.listener.upgrade() {
+            listener.force_lock().remove_lobby(self.room_code);
+        }
+    }
+
+    fn tick(&mut self) -> Result<(), String> {
+        let time_passed = self.last_tick.elapsed();
+        self.last_tick = Instant::now();
+
+        catch_lobby_panic(|| self.lobby.tick(time_passed))
+            .map_err(|message| format!("{} panicked while ticking: {}", self.room_code, message))
+    }
+
+    fn save_snapshot(&self) {
+        let Some(snapshot) = self.lobby.snapshot() else {return};
+        if let Err(err) = snapshot.save() {
+            log!(error "Lobby"; "Failed to save snapshot of {}: {}", self.room_code, err);
+        }
+    }
+
+    /// The listener sends a client's messages here as soon as it asks to join, so it has to be told if they didn't get in
+    fn set_client_outside_lobby(&self, connection: &Connection) {
+        if let Some(listener) = self.listener.upgrade() {
+            listener.force_lock().set_client_outside_lobby(connection.get_address(), self.room_code);
+        }
+    }
+
+    fn on_message(&mut self, message: LobbyMessage) {
+        match message {
+            LobbyMessage::Join { connection } => {
+                match self.lobby.join_player(&connection.get_sender()) {
+                    Ok(lobby_client_id) => {
+                        self.clients.insert(*connection.get_address(), (lobby_client_id, connection.clone()));
+                    },
+                    Err(_) => self.set_client_outside_lobby(&connection),
+                }
+
+                connection.send(ToClientPacket::LobbyName { name: self.lobby.name.clone() })
+            },
+            LobbyMessage::ReJoin { connection, lobby_client_id } => {
+                match self.lobby.rejoin_player(&connection.get_sender(), lobby_client_id) {
+                    Ok(()) => {
+                        self.clients.insert(*connection.get_address(), (lobby_client_id, connection.clone()));
+                    },
+                    Err(_) => self.set_client_outside_lobby(&connection),
+                }
+
+                connection.send(ToClientPacket::LobbyName { name: self.lobby.name.clone() })
+            },
+            LobbyMessage::Leave { address, rejoinable } => {
+                let Some((lobby_client_id, _)) = self.clients.remove(&address) else {return};
+
+                if rejoinable {
+                    self.lobby.remove_player_rejoinable(lobby_client_id);
+                }else{
+                    self.lobby.remove_player(lobby_client_id);
+                }
+            },
+            LobbyMessage::Kick { address, kicked_player_id } => {
+                let Some((host_id, _)) = self.clients.get(&address) else {return};
+                if !self.lobby.is_host(*host_id) {return}
+
+                let kicked_address = self.clients.iter()
+                    .find(|(_, (lobby_client_id, _))| *lobby_client_id == kicked_player_id)
+                    .map(|(address, _)| *address);
+
+                // If nobody is connected with that id, they might have already left.
+                // They still need to be removed so they can't rejoin
+                if let Some((_, connection)) = kicked_address.and_then(|address| self.clients.remove(&address)) {
+                    connection.send(ToClientPacket::RejectJoin { reason: RejectJoinReason::ServerBusy });
+                    self.set_client_outside_lobby(&connection);
+                }
+                self.lobby.remove_player(kicked_player_id);
+            },
+            LobbyMessage::ClientMessage { connection, packet } => {
+                let Some((lobby_client_id, _)) = self.clients.get(connection.get_address()) else {
+                    log!(error "Lobby"; "{} {}", "Received lobby/game packet from player not in the lobby!", connection.get_address());
+                    return;
+                };
+
+                self.lobby.on_client_message(&connection.get_sender(), *lobby_client_id, packet);
+            },
+        }
+    }
+}
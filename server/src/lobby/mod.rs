@@ -2,14 +2,17 @@
 pub mod lobby_client;
 pub mod game_client;
 pub mod on_client_message;
+pub mod snapshot;
+pub mod lobby_task;
 mod name_validation;
 
-use std::{collections::HashMap, time::Duration,};
+use std::{collections::{HashMap, HashSet}, time::{Duration, Instant}};
 
 use crate::{
     client_connection::ClientConnection, game::{
-        player::PlayerReference, role_list::RoleOutline, settings::Settings, spectator::{spectator_pointer::SpectatorIndex, SpectatorInitializeParameters}, Game
-    }, listener::RoomCode, lobby::game_client::GameClientLocation, packet::{
+        player::PlayerReference, preset::{Preset, PresetError, SERVER_PRESETS}, replay::Replay, role::Role, role_list::{RoleList, RoleOutline}, role_list_analysis::RoleListAnalysis, role_list_generation::RoleListConstraint, role_options::RoleOptionsMap, settings::Settings, spectator::{spectator_pointer::{SpectatorIndex, SpectatorPointer}, SpectatorInitializeParameters}, Game
+    }, listener::RoomCode, lobby::game_client::GameClientLocation, log, packet::{
+        LobbyPreviewData,
         RejectJoinReason,
         ToClientPacket,
     }, websocket_connections::connection::ClientSender
@@ -22,22 +25,55 @@ pub struct Lobby {
     room_code: RoomCode,
     pub name: String,
     lobby_state: LobbyState,
+    /// Analyzing a role list generates its roles many times, so it's kept until the settings change
+    last_role_list_analysis: Option<LastRoleListAnalysis>,
 }
 
 enum LobbyState {
     Lobby {
-        settings: Settings,
+        settings: Box<Settings>,
         clients: HashMap<LobbyClientID, LobbyClient>,
     },
     Game {
-        game: Game,
+        game: Box<Game>,
         clients: HashMap<LobbyClientID, GameClient>,
     },
     Closed
 }
 
+struct LastRoleListAnalysis {
+    inputs: RoleListAnalysisInputs,
+    made_at: Instant,
+    analysis: RoleListAnalysis,
+}
+
+/// The settings a role list analysis is made from
+#[derive(PartialEq)]
+struct RoleListAnalysisInputs {
+    role_list: RoleList,
+    enabled_roles: HashSet<Role>,
+    constraints: Vec<RoleListConstraint>,
+    role_options: RoleOptionsMap,
+    seed: Option<u64>,
+}
+impl RoleListAnalysisInputs {
+    fn new(settings: &Settings) -> Self {
+        Self {
+            role_list: settings.role_list.clone(),
+            enabled_roles: settings.enabled_roles.clone(),
+            constraints: settings.role_list_constraints.clone(),
+            role_options: settings.role_options.clone(),
+            seed: settings.seed,
+        }
+    }
+}
+
 pub const LOBBY_DISCONNECT_TIMER_SECS: u64 = 5;
 pub const GAME_DISCONNECT_TIMER_SECS: u64 = 60 * 2;
+/// Disconnect timers and repeating data don't need to be more precise than this
+pub const MAX_TICK_INTERVAL: Duration = Duration::from_secs(1);
+/// How long the host has to wait to analyze a changed role list again
+pub const ROLE_LIST_ANALYSIS_COOLDOWN: Duration = Duration::from_secs(3);
 
 
 impl Lobby {
@@ -47,9 +83,10 @@ impl Lobby {
             room_code,
             name: name_validation::DEFAULT_SERVER_NAME.to_string(),
             lobby_state: LobbyState::Lobby{
-                settings: Settings::default(),
+                settings: Box::default(),
                 clients: HashMap::new()
-            }
+            },
+            last_role_list_analysis: None,
         }
     }
 
@@ -62,7 +99,34 @@ impl Lobby {
             .filter(|p| matches!(p.1.client_type, LobbyClientType::Player{..}))
             .count();
 
-        settings.role_list.0.resize(length, RoleOutline::Any);
+        settings.role_list.0.resize(length, RoleOutline::default());
+    }
+
+    /// Replaces the lobby's settings with the preset's, keeping the lobby's seed.
+    /// Only the host can do this, and whoever asked is told if the preset can't be used
+    fn load_preset(&mut self, send: &ClientSender, lobby_client_id: LobbyClientID, preset: Result<Preset, PresetError>) {
+        let LobbyState::Lobby{ settings, clients } = &mut self.lobby_state else {
+            log!(error "Lobby"; "{} {}", "Can't modify game settings outside of the lobby menu", lobby_client_id);
+            return;
+        };
+        if let Some(player) = clients.get(&lobby_client_id){
+            if !player.host {return}
+        }
+
+        let preset = match preset {
+            Ok(preset) => preset,
+            Err(reason) => {
+                send.send(ToClientPacket::RejectPreset { reason });
+                return;
+            }
+        };
+
+        **settings = Settings { seed: settings.seed, ..preset.settings };
+        Lobby::set_rolelist_length(settings, clients);
+
+        for client in clients.values() {
+            Self::send_settings(client, settings, self.name.clone());
+        }
     }
 
     pub fn join_player(&mut self, send: &ClientSender) -> Result<LobbyClientID, RejectJoinReason>{
@@ -137,12 +201,13 @@ impl Lobby {
             LobbyState::Lobby { clients, settings } => {
                 let player = clients.remove(&lobby_client_id);
         
-                if clients.is_empty() {
+                // Bots can't keep a lobby open on their own
+                if clients.values().all(LobbyClient::is_bot) {
                     self.lobby_state = LobbyState::Closed;
                     return;
                 }
                 if !clients.iter().any(|p|p.1.host) {
-                    if let Some(new_host) = clients.values_mut().next(){
+                    if let Some(new_host) = clients.values_mut().find(|client| !client.is_bot()){
                         new_host.set_host();
                     }
                 }
@@ -166,6 +231,16 @@ impl Lobby {
                     },
                     GameClientLocation::Spectator(idx) => {
                         game.remove_spectator(idx);
+                        clients.remove(&lobby_client_id);
+
+                        // Removing a spectator shifts every spectator after them down by one
+                        for client in clients.values_mut() {
+                            if let GameClientLocation::Spectator(other_idx) = &mut client.client_location {
+                                if *other_idx > idx {
+                                    *other_idx -= 1;
+                                }
+                            }
+                        }
                     }
                 }
             },
@@ -250,16 +325,87 @@ impl Lobby {
         }
     }
 
+    /// Ends the game and brings everyone back to the lobby, keeping the game's settings.
+    /// Players who left during the game are removed, and spectators who joined during it become players.
+    pub fn back_to_lobby(&mut self) {
+        let LobbyState::Game { game, clients: game_clients } = &self.lobby_state else {return};
+
+        let mut clients: HashMap<LobbyClientID, LobbyClient> = HashMap::new();
+        let mut joined_mid_game = Vec::new();
+
+        for (id, game_client) in game_clients.iter() {
+            let mut client = LobbyClient::new_from_game_client(game, game_client.clone());
+
+            match client.connection {
+                ClientConnection::Connected(_) | ClientConnection::Bot(_) => {},
+                ClientConnection::CouldReconnect { disconnect_timer } => {
+                    client.connection = ClientConnection::CouldReconnect {
+                        disconnect_timer: disconnect_timer.min(Duration::from_secs(LOBBY_DISCONNECT_TIMER_SECS))
+                    };
+                },
+                ClientConnection::Disconnected => continue,
+            }
+
+            if game_client.joined_mid_game {
+                joined_mid_game.push(*id);
+            }
+            clients.insert(*id, client);
+        }
+
+        // Named after everyone else, so they can't take the name of someone who was already playing
+        joined_mid_game.sort();
+        for id in joined_mid_game {
+            let name = name_validation::sanitize_name(String::new(), &clients);
+            if let Some(client) = clients.get_mut(&id) {
+                client.client_type = LobbyClientType::Player { name };
+            }
+        }
+
+        if clients.values().all(LobbyClient::is_bot) {
+            self.lobby_state = LobbyState::Closed;
+            return;
+        }
+        if !clients.values().any(|client| client.host) {
+            if let Some(new_host) = clients.values_mut()
+                .filter(|client| !client.is_bot())
+                .min_by_key(|client| !matches!(client.connection, ClientConnection::Connected(_)))
+            {
+                new_host.set_host();
+            }
+        }
+
+        let mut settings = game.settings.clone();
+        Self::set_rolelist_length(&mut settings, &clients);
+
+        self.lobby_state = LobbyState::Lobby { settings: Box::new(settings), clients };
+        self.send_to_all(ToClientPacket::BackToLobby);
+
+        let LobbyState::Lobby { clients, settings } = &self.lobby_state else {
+            unreachable!("LobbyState::Lobby was set to be to LobbyState::Lobby in the previous line")
+        };
+        for (id, client) in clients {
+            client.send(ToClientPacket::YourId { player_id: *id });
+            Self::send_settings(client, settings, self.name.clone());
+        }
+        Self::send_players_lobby(clients);
+    }
+
     pub fn is_closed(&self) -> bool {
         matches!(self.lobby_state, LobbyState::Closed)
     }
 
     pub fn tick(&mut self, time_passed: Duration){
         match &mut self.lobby_state {
-            LobbyState::Game { game, .. } => {
+            LobbyState::Game { game, clients } => {
+                let was_ticking = game.ticking;
                 game.tick(time_passed);
+
+                if was_ticking && !game.ticking {
+                    Self::save_replay(game, self.room_code);
+                    Self::send_night_trace(game, clients);
+                }
                 
-                if !PlayerReference::all_players(game).any(|p| p.is_connected(game)) {
+                if !PlayerReference::all_players(game).any(|p| p.is_connected(game) || p.could_reconnect(game)) {
                     self.lobby_state = LobbyState::Closed;
                 }
             }
@@ -287,6 +433,41 @@ impl Lobby {
         }
     }
 
+    /// How long this lobby can go without being ticked if no messages arrive
+    pub fn time_until_next_tick(&self) -> Duration {
+        match &self.lobby_state {
+            LobbyState::Game { game, .. } if game.ticking && game.paused().is_none() => game.phase_time_remaining().min(MAX_TICK_INTERVAL),
+            _ => MAX_TICK_INTERVAL
+        }
+    }
+
+    fn save_replay(game: &Game, room_code: RoomCode){
+        let name = format!("{}_{}_{}", chrono::Local::now().format("%Y-%m-%d_%H-%M-%S"), room_code, game.seed);
+        match Replay::from_game(game).save(&name) {
+            Ok(path) => log!(info "Lobby"; "Saved replay of game in {} to {}", room_code, path.display()),
+            Err(err) => log!(error "Lobby"; "Failed to save replay of game in {}: {}", room_code, err),
+        }
+    }
+
+    /// The game doesn't know who the hosts are, so the lobby sends them the trace
+    fn send_night_trace(game: &Game, clients: &HashMap<LobbyClientID, GameClient>){
+        if !game.settings.rules.trace_night_actions {return}
+
+        let packet = ToClientPacket::NightTrace{ nights: game.night_trace().nights().to_vec() };
+        for client in clients.values().filter(|client| client.host) {
+            match client.client_location {
+                GameClientLocation::Player(player_index) => {
+                    if let Ok(player_ref) = PlayerReference::new(game, player_index) {
+                        player_ref.send_packet(game, packet.clone());
+                    }
+                },
+                GameClientLocation::Spectator(spectator_index) => {
+                    SpectatorPointer::new(spectator_index).send_packet(game, packet.clone());
+                }
+            }
+        }
+    }
+
     pub fn get_player_list(&self)->Vec<(LobbyClientID, String)>{
         match &self.lobby_state {
             LobbyState::Lobby { settings:_, clients: players } => {
@@ -314,6 +495,14 @@ impl Lobby {
             LobbyState::Closed => Vec::new(),
         }
     }
+    /// What this lobby looks like in the lobby list
+    pub fn preview(&self) -> LobbyPreviewData {
+        LobbyPreviewData {
+            name: self.name.clone(),
+            in_game: self.is_in_game(),
+            players: self.get_player_list()
+        }
+    }
     pub fn is_host(&self, lobby_client_id: LobbyClientID)->bool{
         match &self.lobby_state {
             LobbyState::Lobby { clients: players, .. } => {
@@ -338,8 +527,14 @@ impl Lobby {
     pub fn send_settings(client: &LobbyClient, settings: &Settings, name: String) {
         client.send(ToClientPacket::LobbyName { name });
         client.send(ToClientPacket::PhaseTimes { phase_time_settings: settings.phase_times.clone() });
+        client.send(ToClientPacket::AutoPause { auto_pause: settings.auto_pause });
+        client.send(ToClientPacket::GameRules { rules: settings.rules.clone() });
+        client.send(ToClientPacket::CustomRoleSets { custom_role_sets: settings.custom_role_sets.clone() });
         client.send(ToClientPacket::RoleList { role_list: settings.role_list.clone() });
+        client.send(ToClientPacket::RoleListConstraints { role_list_constraints: settings.role_list_constraints.clone() });
+        client.send(ToClientPacket::RoleOptions { role_options: settings.role_options.clone() });
         client.send(ToClientPacket::EnabledRoles { roles: settings.enabled_roles.clone().into_iter().collect() });
+        client.send(ToClientPacket::Presets { presets: SERVER_PRESETS.iter().map(|preset| preset.name.clone()).collect() });
     }
 
     //send the list of players to all players while in the lobby
@@ -1,8 +1,10 @@
 use std::{collections::{HashMap, VecDeque}, time::{Duration, Instant}};
 
-use crate::{game::{chat::{ChatMessage, ChatMessageVariant}, phase::PhaseType, player::{PlayerIndex, PlayerInitializeParameters}, spectator::{spectator_pointer::SpectatorIndex, SpectatorInitializeParameters}, Game}, lobby::game_client::{GameClient, GameClientLocation}, log, packet::{ToClientPacket, ToServerPacket}, strings::TidyableString, websocket_connections::connection::ClientSender};
+use rand::{rngs::StdRng, SeedableRng};
 
-use super::{lobby_client::{LobbyClient, LobbyClientID, LobbyClientType}, name_validation::{self, sanitize_server_name}, Lobby, LobbyState};
+use crate::{game::{bot::Bot, chat::{ChatMessage, ChatMessageVariant}, phase::{PauseReason, PhaseType}, player::{PlayerIndex, PlayerInitializeParameters}, preset::Preset, role_list::CustomRoleSet, role_list_analysis::RoleListAnalysis, spectator::{spectator_pointer::SpectatorIndex, SpectatorInitializeParameters}, Game}, lobby::game_client::{GameClient, GameClientLocation}, log, packet::{ToClientPacket, ToServerPacket}, strings::TidyableString, websocket_connections::connection::ClientSender};
+
+use super::{lobby_client::{LobbyClient, LobbyClientID, LobbyClientType}, name_validation::{self, sanitize_server_name}, LastRoleListAnalysis, Lobby, LobbyState, RoleListAnalysisInputs, ROLE_LIST_ANALYSIS_COOLDOWN};
 
 pub const MESSAGE_PER_SECOND_LIMIT: u64 = 2;
 pub const MESSAGE_PER_SECOND_LIMIT_TIME: Duration = Duration::from_secs(2);
@@ -169,12 +171,14 @@ impl Lobby {
                             GameClient {
                                 client_location: GameClientLocation::Spectator(next_spectator_index),
                                 host: lobby_client.host,
+                                joined_mid_game: false,
                                 last_message_times: VecDeque::new(),
                             }
                         } else {
                             GameClient {
                                 client_location: GameClientLocation::Player(next_player_index),
                                 host: lobby_client.host,
+                                joined_mid_game: false,
                                 last_message_times: VecDeque::new(),
                             }
                         }
@@ -199,7 +203,7 @@ impl Lobby {
                     }
                 }
 
-                let game = match Game::new(settings.clone(), game_player_params, game_spectator_params){
+                let game = match Game::new(settings.as_ref().clone(), game_player_params, game_spectator_params){
                     Ok(game) => game,
                     Err(err) => {
                         send.send(ToClientPacket::RejectStart { reason: err });
@@ -211,7 +215,7 @@ impl Lobby {
                 log!(info "Lobby"; "Game started with room code {}", self.room_code);
 
                 self.lobby_state = LobbyState::Game{
-                    game,
+                    game: Box::new(game),
                     clients: game_clients,
                 };
                 let LobbyState::Game { game, clients: _player } = &mut self.lobby_state else {
@@ -222,6 +226,27 @@ impl Lobby {
                 
                 self.send_to_all(ToClientPacket::LobbyName { name: self.name.clone() })
             },
+            ToServerPacket::AddBot => {
+                let LobbyState::Lobby { clients, settings } = &mut self.lobby_state else {
+                    log!(error "Lobby"; "{} {}", "ToServerPacket::AddBot can not be used outside of LobbyState::Lobby", lobby_client_id);
+                    return
+                };
+                if let Some(player) = clients.get(&lobby_client_id){
+                    if !player.host {return}
+                }
+
+                let name = name_validation::sanitize_name("".to_string(), clients);
+                let bot_id: LobbyClientID = clients.keys().copied().fold(0u32, u32::max) + 1;
+                // The game gives every bot a new one from its seed when it starts
+                let bot = Bot::for_player(settings.seed.unwrap_or_default(), clients.len() as PlayerIndex);
+                clients.insert(bot_id, LobbyClient::new_bot(name, bot));
+
+                Lobby::set_rolelist_length(settings, clients);
+                Self::send_players_lobby(clients);
+                for player in clients.values() {
+                    Self::send_settings(player, settings, self.name.clone());
+                }
+            }
             ToServerPacket::SetPhaseTime{phase, time} => {
                 let LobbyState::Lobby{ settings, clients  } = &mut self.lobby_state else {
                     log!(error "Lobby"; "{} {}", "Attempted to change phase time outside of the lobby menu!", lobby_client_id);
@@ -258,6 +283,32 @@ impl Lobby {
 
                 self.send_to_all(ToClientPacket::PhaseTimes { phase_time_settings });
             }
+            ToServerPacket::SetAutoPause { auto_pause } => {
+                let LobbyState::Lobby{ settings, clients } = &mut self.lobby_state else {
+                    log!(error "Lobby"; "{} {}", "Can't modify game settings outside of the lobby menu", lobby_client_id);
+                    return;
+                };
+                if let Some(player) = clients.get(&lobby_client_id){
+                    if !player.host {return}
+                }
+
+                settings.auto_pause = auto_pause;
+
+                self.send_to_all(ToClientPacket::AutoPause { auto_pause });
+            }
+            ToServerPacket::SetGameRules { rules } => {
+                let LobbyState::Lobby{ settings, clients } = &mut self.lobby_state else {
+                    log!(error "Lobby"; "{} {}", "Can't modify game settings outside of the lobby menu", lobby_client_id);
+                    return;
+                };
+                if let Some(player) = clients.get(&lobby_client_id){
+                    if !player.host {return}
+                }
+
+                settings.rules = rules.clone();
+
+                self.send_to_all(ToClientPacket::GameRules { rules });
+            }
             ToServerPacket::SetRoleList { role_list } => {
                 let LobbyState::Lobby{ settings, clients } = &mut self.lobby_state else {
                     log!(error "Lobby"; "{} {}", "Can't modify game settings outside of the lobby menu", lobby_client_id);
@@ -268,6 +319,7 @@ impl Lobby {
                 }
 
                 settings.role_list = role_list;
+                settings.role_list.update_custom_role_sets(&settings.custom_role_sets);
                 Lobby::set_rolelist_length(settings, clients);
                 
                 let role_list = settings.role_list.clone();
@@ -285,10 +337,56 @@ impl Lobby {
 
                 if settings.role_list.0.len() <= index as usize {return}
                 let Some(unset_outline) = settings.role_list.0.get_mut(index as usize) else {return};
-                *unset_outline = role_outline.clone();
+                *unset_outline = role_outline;
+                settings.role_list.update_custom_role_sets(&settings.custom_role_sets);
+                let role_outline = settings.role_list.0[index as usize].clone();
                 
                 self.send_to_all(ToClientPacket::RoleOutline { index, role_outline });
             }
+            ToServerPacket::SetCustomRoleSets { custom_role_sets } => {
+                let LobbyState::Lobby{ settings, clients } = &mut self.lobby_state else {
+                    log!(error "Lobby"; "{} {}", "Can't modify game settings outside of the lobby menu", lobby_client_id);
+                    return;
+                };
+                if let Some(player) = clients.get(&lobby_client_id){
+                    if !player.host {return}
+                }
+
+                settings.custom_role_sets = CustomRoleSet::sanitize_all(custom_role_sets);
+                settings.role_list.update_custom_role_sets(&settings.custom_role_sets);
+
+                let custom_role_sets = settings.custom_role_sets.clone();
+                let role_list = settings.role_list.clone();
+
+                self.send_to_all(ToClientPacket::CustomRoleSets { custom_role_sets });
+                self.send_to_all(ToClientPacket::RoleList { role_list });
+            }
+            ToServerPacket::SetRoleListConstraints { role_list_constraints } => {
+                let LobbyState::Lobby{ settings, clients } = &mut self.lobby_state else {
+                    log!(error "Lobby"; "{} {}", "Can't modify game settings outside of the lobby menu", lobby_client_id);
+                    return;
+                };
+                if let Some(player) = clients.get(&lobby_client_id){
+                    if !player.host {return}
+                }
+
+                settings.role_list_constraints = role_list_constraints.clone();
+
+                self.send_to_all(ToClientPacket::RoleListConstraints { role_list_constraints });
+            }
+            ToServerPacket::SetRoleOptions { role_options } => {
+                let LobbyState::Lobby{ settings, clients } = &mut self.lobby_state else {
+                    log!(error "Lobby"; "{} {}", "Can't modify game settings outside of the lobby menu", lobby_client_id);
+                    return;
+                };
+                if let Some(player) = clients.get(&lobby_client_id){
+                    if !player.host {return}
+                }
+
+                settings.role_options = role_options.clone();
+
+                self.send_to_all(ToClientPacket::RoleOptions { role_options });
+            }
             ToServerPacket::SimplifyRoleList => {
                 let LobbyState::Lobby{ settings, clients } = &mut self.lobby_state else {
                     log!(error "Lobby"; "{} {}", "Can't modify game settings outside of the lobby menu", lobby_client_id);
@@ -303,6 +401,49 @@ impl Lobby {
                 
                 self.send_to_all(ToClientPacket::RoleList { role_list });
             }
+            ToServerPacket::LoadPreset { name } => {
+                self.load_preset(send, lobby_client_id, Preset::find_server_preset(&name));
+            }
+            ToServerPacket::ImportPreset { code } => {
+                self.load_preset(send, lobby_client_id, Preset::from_code(&code));
+            }
+            ToServerPacket::ExportPreset { name } => {
+                let LobbyState::Lobby{ settings, .. } = &self.lobby_state else {
+                    log!(error "Lobby"; "{} {}", "Can't export game settings outside of the lobby menu", lobby_client_id);
+                    return;
+                };
+
+                send.send(ToClientPacket::PresetCode { code: Preset::new(name, settings).to_code() });
+            }
+            ToServerPacket::AnalyzeRoleList => {
+                let LobbyState::Lobby{ settings, clients } = &self.lobby_state else {
+                    log!(error "Lobby"; "{} {}", "Can't analyze the role list outside of the lobby menu", lobby_client_id);
+                    return;
+                };
+                if let Some(player) = clients.get(&lobby_client_id){
+                    if !player.host {return}
+                }
+
+                let inputs = RoleListAnalysisInputs::new(settings);
+                if let Some(last) = &self.last_role_list_analysis {
+                    if last.inputs == inputs {
+                        send.send(ToClientPacket::RoleListAnalysis { analysis: last.analysis.clone() });
+                        return;
+                    }
+                    if last.made_at.elapsed() < ROLE_LIST_ANALYSIS_COOLDOWN {return}
+                }
+
+                let analysis = RoleListAnalysis::new(
+                    &settings.role_list,
+                    &settings.enabled_roles,
+                    &settings.role_list_constraints,
+                    &settings.role_options,
+                    RoleListAnalysis::DEFAULT_SAMPLES,
+                    &mut StdRng::seed_from_u64(settings.seed.unwrap_or_else(rand::random))
+                );
+                send.send(ToClientPacket::RoleListAnalysis { analysis: analysis.clone() });
+                self.last_role_list_analysis = Some(LastRoleListAnalysis { inputs, made_at: Instant::now(), analysis });
+            }
             ToServerPacket::SetEnabledRoles {roles } => {
                 let LobbyState::Lobby{ settings, .. } = &mut self.lobby_state else {
                     log!(error "Lobby"; "{} {}", "Can't modify game settings outside of the lobby menu", lobby_client_id);
@@ -317,8 +458,21 @@ impl Lobby {
             ToServerPacket::Leave => {
                 self.remove_player(lobby_client_id);
             }
-            ToServerPacket::BackToLobby => {
+            ToServerPacket::Pause | ToServerPacket::Resume => {
                 let LobbyState::Game { game, clients } = &mut self.lobby_state else {
+                    log!(error "Lobby"; "{} {}", "Can't pause or resume while in lobby", lobby_client_id);
+                    return;
+                };
+                if !clients.get(&lobby_client_id).is_some_and(|client| client.host) {return}
+
+                if incoming_packet == ToServerPacket::Pause {
+                    game.pause(PauseReason::Host);
+                } else {
+                    game.resume();
+                }
+            }
+            ToServerPacket::BackToLobby => {
+                let LobbyState::Game { clients, .. } = &self.lobby_state else {
                     log!(error "Lobby"; "{} {}", "Can't go back to lobby from while in lobby", lobby_client_id);
                     return;
                 };
@@ -326,29 +480,7 @@ impl Lobby {
                     if !player.host {return;}
                 }
 
-                let mut new_clients = HashMap::new();
-                for (lobby_client_id, game_client) in clients.clone().into_iter() {
-                    new_clients.insert(lobby_client_id, LobbyClient::new_from_game_client(&game, game_client));
-                }
-
-
-                self.lobby_state = LobbyState::Lobby {
-                    settings: game.settings.clone(),
-                    clients: new_clients,
-                };
-
-                Self::send_to_all(&self, ToClientPacket::BackToLobby);
-
-                match &self.lobby_state {
-                    LobbyState::Lobby { clients, settings } => {
-                        for (id, client) in clients {
-                            client.send(ToClientPacket::YourId { player_id: id.clone() });
-                            Self::send_settings(client, settings, self.name.clone());
-                        }
-                        Self::send_players_lobby(clients);
-                    }
-                    _ => unreachable!("LobbyState::Lobby was set to be to LobbyState::Lobby in the previous line")
-                }
+                self.back_to_lobby();
             }
             _ => {
                 let LobbyState::Game { game, clients } = &mut self.lobby_state else {
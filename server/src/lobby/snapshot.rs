@@ -0,0 +1,182 @@
+use std::{collections::{HashMap, VecDeque}, fs, io, path::PathBuf, time::Duration};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    client_connection::ClientConnection,
+    game::{bot::Bot, player::{PlayerIndex, PlayerReference}, replay::Replay, settings::Settings, RejectStartReason},
+    listener::RoomCode,
+};
+
+use super::{
+    game_client::{GameClient, GameClientLocation},
+    lobby_client::{LobbyClient, LobbyClientID, LobbyClientType},
+    Lobby, LobbyState, GAME_DISCONNECT_TIMER_SECS
+};
+
+pub const SNAPSHOT_DIRECTORY: &str = "./lobbies";
+
+/// Everything needed to bring a lobby back after the server restarts.
+/// Games are stored as replays and rebuilt by re-simulating them, so they come back exactly as they were.
+/// Nobody is connected to a restored lobby, everyone has to rejoin with their old room code and player id.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LobbySnapshot {
+    room_code: RoomCode,
+    name: String,
+    state: LobbyStateSnapshot,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+enum LobbyStateSnapshot {
+    #[serde(rename_all = "camelCase")]
+    Lobby {
+        settings: Settings,
+        clients: Vec<LobbyClientSnapshot>,
+    },
+    /// Spectators aren't part of the replay, so only players are kept
+    #[serde(rename_all = "camelCase")]
+    Game {
+        replay: Replay,
+        phase_time_remaining: Duration,
+        players: Vec<GamePlayerSnapshot>,
+    },
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LobbyClientSnapshot {
+    id: LobbyClientID,
+    host: bool,
+    client_type: LobbyClientType,
+    #[serde(default)]
+    bot: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GamePlayerSnapshot {
+    id: LobbyClientID,
+    host: bool,
+    player_index: PlayerIndex,
+    /// Bots come back as bots with a new rng, since they don't need to rejoin
+    #[serde(default)]
+    bot: bool,
+}
+
+impl LobbySnapshot {
+    pub fn room_code(&self) -> RoomCode {
+        self.room_code
+    }
+
+    fn path(room_code: RoomCode) -> PathBuf {
+        PathBuf::from(SNAPSHOT_DIRECTORY).join(format!("{room_code}.json"))
+    }
+    /// Writes this snapshot to the snapshot directory, replacing the lobby's last one
+    pub fn save(&self) -> io::Result<()> {
+        fs::create_dir_all(SNAPSHOT_DIRECTORY)?;
+
+        // Write to a temporary file first so a crash mid write doesn't lose the last good snapshot
+        let path = Self::path(self.room_code);
+        let temporary_path = path.with_extension("json.tmp");
+        fs::write(&temporary_path, serde_json::to_string(self)?)?;
+        fs::rename(temporary_path, path)
+    }
+    /// Deletes the lobby's snapshot, so it isn't restored after a restart
+    pub fn delete(room_code: RoomCode) {
+        let _ = fs::remove_file(Self::path(room_code));
+    }
+}
+
+impl Lobby {
+    /// Returns None if the lobby is closed
+    pub fn snapshot(&self) -> Option<LobbySnapshot> {
+        let state = match &self.lobby_state {
+            LobbyState::Lobby { settings, clients } => LobbyStateSnapshot::Lobby {
+                settings: settings.as_ref().clone(),
+                clients: clients.iter().map(|(id, client)| LobbyClientSnapshot {
+                    id: *id,
+                    host: client.host,
+                    client_type: client.client_type.clone(),
+                    bot: client.is_bot(),
+                }).collect(),
+            },
+            LobbyState::Game { game, clients } => LobbyStateSnapshot::Game {
+                replay: Replay::from_game(game),
+                phase_time_remaining: game.phase_time_remaining(),
+                players: clients.iter().filter_map(|(id, client)| match client.client_location {
+                    GameClientLocation::Player(player_index) => Some(GamePlayerSnapshot {
+                        id: *id,
+                        host: client.host,
+                        player_index,
+                        bot: PlayerReference::new_unchecked(player_index).is_bot(game),
+                    }),
+                    GameClientLocation::Spectator(_) => None,
+                }).collect(),
+            },
+            LobbyState::Closed => return None,
+        };
+
+        Some(LobbySnapshot {
+            room_code: self.room_code,
+            name: self.name.clone(),
+            state
+        })
+    }
+
+    pub fn from_snapshot(snapshot: LobbySnapshot) -> Result<Self, RejectStartReason> {
+        // Give everyone as long to come back as they would get for losing connection mid game
+        let connection = ClientConnection::CouldReconnect { disconnect_timer: Duration::from_secs(GAME_DISCONNECT_TIMER_SECS) };
+
+        let lobby_state = match snapshot.state {
+            LobbyStateSnapshot::Lobby { settings, clients } => LobbyState::Lobby {
+                clients: clients.into_iter().enumerate().map(|(index, client)| (client.id, LobbyClient {
+                    connection: if client.bot {
+                        ClientConnection::Bot(Bot::for_player(settings.seed.unwrap_or_default(), index as PlayerIndex))
+                    } else {connection.clone()},
+                    host: client.host,
+                    client_type: client.client_type,
+                })).collect(),
+                settings: Box::new(settings),
+            },
+            LobbyStateSnapshot::Game { replay, phase_time_remaining, players } => {
+                let mut game = replay.simulate()?;
+                game.set_phase_time_remaining(phase_time_remaining);
+                for player in players.iter().filter(|player| player.bot) {
+                    if let Ok(player_ref) = PlayerReference::new(&game, player.player_index) {
+                        // Starts its rng over, but still only depends on the game's seed
+                        let bot = Bot::for_player(game.seed, player.player_index);
+                        player_ref.set_bot(&mut game, bot);
+                    }
+                }
+
+                let mut clients: HashMap<LobbyClientID, GameClient> = players.into_iter().map(|player| (player.id, GameClient {
+                    client_location: GameClientLocation::Player(player.player_index),
+                    host: player.host,
+                    joined_mid_game: false,
+                    last_message_times: VecDeque::new(),
+                })).collect();
+
+                // The host might have been a spectator
+                if !clients.values().any(|client| client.host) {
+                    if let Some(client) = clients.values_mut().min_by_key(|client| match client.client_location {
+                        GameClientLocation::Player(index) => index,
+                        GameClientLocation::Spectator(_) => PlayerIndex::MAX,
+                    }) {
+                        client.set_host();
+                    }
+                }
+
+                LobbyState::Game { game: Box::new(game), clients }
+            },
+        };
+
+        Ok(Self {
+            room_code: snapshot.room_code,
+            name: snapshot.name,
+            lobby_state,
+            last_role_list_analysis: None,
+        })
+    }
+}
@@ -22,10 +22,10 @@ use serde::{Deserialize, Serialize};
 use vec1::Vec1;
 
 use crate::{game::{
-    available_buttons::AvailableButtons, chat::{ChatGroup, ChatMessage}, grave::Grave, phase::{PhaseState, PhaseType}, player::{PlayerIndex, PlayerReference}, role::{counterfeiter::CounterfeiterAction, doomsayer::DoomsayerGuess, eros::ErosAction, kira::KiraGuess, ojo::OjoAction, puppeteer::PuppeteerAction, Role, RoleState}, role_list::{RoleList, RoleOutline}, settings::PhaseTimeSettings, tag::Tag, verdict::Verdict, Game, GameOverReason, RejectStartReason
+    available_buttons::AvailableButtons, components::{ability_charges::{Ability, Charges}, game_history::GameSummary, night_trace::TracedNight}, chat::{ChatGroup, ChatMessage}, grave::Grave, phase::{PhaseState, PhaseType}, player::{PlayerIndex, PlayerReference}, preset::PresetError, role::{counterfeiter::CounterfeiterAction, doomsayer::DoomsayerGuess, eros::ErosAction, kira::KiraGuess, ojo::OjoAction, puppeteer::PuppeteerAction, Role, RoleState}, role_modifier::RoleModifier, role_list::{CustomRoleSet, RoleList, RoleOutline}, role_list_analysis::RoleListAnalysis, role_list_generation::RoleListConstraint, role_options::RoleOptionsMap, settings::{GameRules, PhaseTimeSettings}, tag::Tag, verdict::Verdict, Game, GameOverReason, RejectStartReason
 }, listener::RoomCode, lobby::lobby_client::{LobbyClient, LobbyClientID}, log};
 
-#[derive(Serialize, Debug, Clone)]
+#[derive(Serialize, Debug, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct LobbyPreviewData {
     pub name: String,
@@ -48,6 +48,8 @@ pub enum ToClientPacket{
     #[serde(rename_all = "camelCase")]
     AcceptJoin{room_code: RoomCode, in_game: bool, player_id: LobbyClientID, spectator: bool},
     RejectJoin{reason: RejectJoinReason},
+    /// The lobby had to be closed because something went wrong on the server
+    LobbyCrashed{message: String},
     
     // Lobby
     #[serde(rename_all = "camelCase")]
@@ -74,7 +76,21 @@ pub enum ToClientPacket{
     #[serde(rename_all = "camelCase")]
     PhaseTimes{phase_time_settings: PhaseTimeSettings},
     #[serde(rename_all = "camelCase")]
+    AutoPause{auto_pause: bool},
+    GameRules{rules: GameRules},
+    #[serde(rename_all = "camelCase")]
+    CustomRoleSets{custom_role_sets: Vec<CustomRoleSet>},
+    #[serde(rename_all = "camelCase")]
+    RoleListConstraints{role_list_constraints: Vec<RoleListConstraint>},
+    #[serde(rename_all = "camelCase")]
+    RoleOptions{role_options: RoleOptionsMap},
+    #[serde(rename_all = "camelCase")]
     EnabledRoles{roles: Vec<Role>},
+    /// The names of the presets this server has
+    Presets{presets: Vec<String>},
+    PresetCode{code: String},
+    RejectPreset{reason: PresetError},
+    RoleListAnalysis{analysis: RoleListAnalysis},
 
     // Game
     
@@ -82,8 +98,9 @@ pub enum ToClientPacket{
     YourPlayerIndex{player_index: PlayerIndex},
     #[serde(rename_all = "camelCase")]
     Phase{phase: PhaseState, day_number: u8},
-    #[serde(rename_all = "camelCase")]
-    PhaseTimeLeft{seconds_left: u64},
+    /// The deadline is when the phase ends, in milliseconds since the unix epoch
+    PhaseTimeLeft{deadline: u64},
+    GamePaused{paused: bool},
     #[serde(rename_all = "camelCase")]
     PlayerOnTrial{player_index: PlayerIndex},
 
@@ -106,7 +123,8 @@ pub enum ToClientPacket{
     #[serde(rename_all = "camelCase")]
     YourDeathNote{death_note: Option<String>},
     #[serde(rename_all = "camelCase")]
-    YourRoleState{role_state: RoleState},
+    YourRoleState{role_state: RoleState, modifiers: Vec<RoleModifier>},
+    YourAbilityCharges{charges: HashMap<Ability, Charges>},
     #[serde(rename_all = "camelCase")]
     YourSelection{player_indices: Vec<PlayerIndex>},
     #[serde(rename_all = "camelCase")]
@@ -122,6 +140,10 @@ pub enum ToClientPacket{
     AddGrave{grave: Grave},
 
     GameOver{reason: GameOverReason},
+    /// Everything that happened in the game, sent once it ends
+    GameSummary{summary: GameSummary},
+    /// How every night was resolved, sent to the hosts once the game ends if they turned tracing on
+    NightTrace{nights: Vec<TracedNight>},
 }
 impl ToClientPacket {
     pub fn to_json_string(&self) -> Result<String, serde_json::Error> {
@@ -162,7 +184,7 @@ pub enum RejectJoinReason {
     PlayerDoesntExist,
 }
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(tag = "type", rename_all = "camelCase")]
 pub enum ToServerPacket{
     Ping,
@@ -183,6 +205,8 @@ pub enum ToServerPacket{
     SetName{name: String},
     SetLobbyName{name: String},
     StartGame,
+    /// Adds a player played by the server. Bots are removed with `Kick`
+    AddBot,
     #[serde(rename_all = "camelCase")]
     SetRoleList{role_list: RoleList},
     #[serde(rename_all = "camelCase")]
@@ -195,7 +219,24 @@ pub enum ToServerPacket{
     SetPhaseTimes{phase_time_settings: PhaseTimeSettings},
     #[serde(rename_all = "camelCase")]
     SetEnabledRoles{roles: Vec<Role>},
+    #[serde(rename_all = "camelCase")]
+    SetAutoPause{auto_pause: bool},
+    SetGameRules{rules: GameRules},
+    #[serde(rename_all = "camelCase")]
+    SetCustomRoleSets{custom_role_sets: Vec<CustomRoleSet>},
+    #[serde(rename_all = "camelCase")]
+    SetRoleListConstraints{role_list_constraints: Vec<RoleListConstraint>},
+    #[serde(rename_all = "camelCase")]
+    SetRoleOptions{role_options: RoleOptionsMap},
+    LoadPreset{name: String},
+    ImportPreset{code: String},
+    /// Turns the lobby's settings into a code, which is sent back to whoever asked for it
+    ExportPreset{name: String},
+    /// Samples the lobby's role list, and sends the host a `RoleListAnalysis`
+    AnalyzeRoleList,
     BackToLobby,
+    Pause,
+    Resume,
 
     // Game
     #[serde(rename_all = "camelCase")]
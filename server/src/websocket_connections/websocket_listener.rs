@@ -1,11 +1,15 @@
-use crate::{websocket_connections::{connection::Connection, ForceLock}, listener::Listener, log};
+use crate::{websocket_connections::{connection::Connection, ForceLock}, listener::{is_catching_lobby_panic, Listener}, log};
 use tokio_tungstenite::tungstenite::Message;
-use std::{net::SocketAddr, sync::{Arc, Mutex}, pin::pin};
+use std::{net::SocketAddr, sync::{Arc, Mutex}, pin::pin, time::Duration};
 
 use futures_util::{future::{self, Either}, StreamExt, SinkExt};
 
 use tokio::sync::{mpsc, broadcast};
 use tokio::net::{TcpListener, TcpStream};
+use tokio::time;
+
+/// How long a lobby gets to finish what it's doing when the server restarts
+const LOBBY_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(5);
 
 pub async fn create_ws_server(address: &str) {
     let tcp_listener = TcpListener::bind(&address).await.unwrap_or_else(|err| {
@@ -21,13 +25,16 @@ pub async fn create_ws_server(address: &str) {
         let panic_crash_signal_sender = crash_signal.0.clone();
         let original_hook = std::panic::take_hook();
         std::panic::set_hook(Box::new(move |info| {
-            let _ = panic_crash_signal_sender.send(());
+            // Lobbies catch their own panics, so only that lobby has to close
+            if !is_catching_lobby_panic() {
+                let _ = panic_crash_signal_sender.send(());
+            }
             original_hook(info)
         }))
     }
 
     let event_listener = Arc::new(Mutex::new(Listener::new()));
-    Listener::start(event_listener.clone());
+    Listener::restore_snapshot(&event_listener);
 
     log!(important "Server"; "Started listening on {address}");
 
@@ -56,6 +63,16 @@ pub async fn create_ws_server(address: &str) {
 
     log!(fatal "Server"; "The server panicked!");
     log!(important "Server"; "Shutting down...");
+
+    // Connections from this server can outlive it, so its lobbies have to be stopped here.
+    // Otherwise they would keep saving snapshots over the lobbies the next server restores
+    let lobby_tasks = event_listener.force_lock().shut_down_lobbies();
+    for mut task in lobby_tasks {
+        if time::timeout(LOBBY_SHUTDOWN_TIMEOUT, &mut task).await.is_err() {
+            log!(error "Server"; "A lobby didn't stop in time, so it was aborted");
+            task.abort();
+        }
+    }
 }
 
 struct ConnectionError;
@@ -123,12 +140,12 @@ async fn handle_connection(
 
         tokio::spawn(async move {
             while let Some(Ok(message)) = tcp_receiver.next().await {
-                let Ok(mut listener) = listener.lock() else {
+                let Ok(mut locked_listener) = listener.lock() else {
                     let _ = crash_signal.0.send(());
                     return;
                 };
         
-                listener.on_message(&connection, &message);
+                locked_listener.on_message(&listener, &connection, &message);
             }
         })
     };
@@ -0,0 +1,435 @@
+use std::{net::SocketAddr, ops::Range, time::Duration};
+
+use mafia_server::{
+    client_connection::ClientConnection,
+    game::{
+        bot::Bot,
+        components::{ability_charges::Ability, game_history::GameHistory},
+        invariants::InvariantViolation,
+        phase::{PauseReason, PhaseState, PhaseStateMachine, PhaseType},
+        player::{PlayerIndex, PlayerInitializeParameters, PlayerReference},
+        replay::{GameLogEntry, Replay, ReplayMismatch, REPLAY_VERSION},
+        role::{Role, RoleState},
+        role_list::{RoleList, RoleOutline},
+        role_modifier::RoleModifier,
+        role_options::{RoleOptions, RoleOptionsError, RoleOptionsMap},
+        settings::{GameRules, NominationThreshold, Settings, TrialSystem},
+        test::mock_game,
+        verdict::Verdict,
+        Game,
+        PluralityVoteResult,
+        RejectStartReason
+    },
+    packet::ToServerPacket,
+    websocket_connections::connection::Connection
+};
+use tokio::sync::mpsc::unbounded_channel;
+
+fn seeded_settings(seed: u64, players: usize) -> Settings {
+    Settings {
+        role_list: RoleList(vec![RoleOutline::default(); players]),
+        enabled_roles: Role::values().into_iter().collect(),
+        seed: Some(seed),
+        ..Default::default()
+    }
+}
+
+fn connected_player(name: String) -> PlayerInitializeParameters {
+    // The receiver is dropped right away, so everything sent to this player is thrown out
+    let (sender, _) = unbounded_channel();
+    PlayerInitializeParameters {
+        connection: ClientConnection::Connected(Connection::new(sender, SocketAddr::from(([127, 0, 0, 1], 0))).get_sender()),
+        name,
+        host: false,
+    }
+}
+
+/// Plays a game of only bots through `Game::new` and `Game::tick`, and returns everything they did.
+/// The bots start with random seeds, which the game should replace with ones from its own seed
+fn play_bot_game(seed: u64) -> Vec<GameLogEntry> {
+    let players = (0..7).map(|i| PlayerInitializeParameters {
+        connection: ClientConnection::Bot(Bot::new(rand::random())),
+        name: i.to_string(),
+        host: false,
+    }).collect();
+    let mut game = Game::new(seeded_settings(seed, 7), players, Vec::new()).expect("Game should be created");
+
+    for _ in 0..50 {
+        if !game.ticking { break }
+        game.tick(Duration::from_secs(1000));
+    }
+    game.game_log().clone()
+}
+
+/// Plays a whole game through `Game::new` and `Game::tick`, the same way a lobby would.
+/// Everyone targets the next living player at night and votes the first living player guilty.
+fn play_game(seed: u64) -> Game {
+    let players = (0..10).map(|i| connected_player(i.to_string())).collect();
+    let mut game = Game::new(seeded_settings(seed, 10), players, Vec::new()).expect("Game should be created");
+
+    for _ in 0..200 {
+        if !game.ticking { break }
+
+        let living: Vec<PlayerIndex> = PlayerReference::all_players(&game)
+            .filter(|p|p.alive(&game))
+            .map(|p|p.index())
+            .collect();
+
+        for (i, player) in living.iter().enumerate() {
+            let next = living[(i + 1) % living.len()];
+            let packet = match game.current_phase().phase() {
+                PhaseType::Discussion => ToServerPacket::SendMessage { text: format!("I'm suspicious of {next}") },
+                PhaseType::Nomination => ToServerPacket::Vote { player_index: Some(if i == 0 { next } else { living[0] }) },
+                PhaseType::Judgement => ToServerPacket::Judgement { verdict: Verdict::Guilty },
+                PhaseType::Night => ToServerPacket::Target { player_index_list: vec![next] },
+                _ => continue
+            };
+            game.on_client_message(*player, packet);
+        }
+
+        game.tick(Duration::from_secs(1000));
+    }
+    game
+}
+
+fn roles(game: &Game) -> Vec<Role> {
+    PlayerReference::all_players(game).map(|p|p.role(game)).collect()
+}
+
+#[test]
+fn same_seed_generates_same_roles() {
+    for seed in 0..20 {
+        let (Ok(a), Ok(b)) = (mock_game(seeded_settings(seed, 10), 10), mock_game(seeded_settings(seed, 10), 10)) else {
+            continue;
+        };
+        assert_eq!(a.seed, seed);
+        assert_eq!(roles(&a), roles(&b));
+    }
+}
+
+#[test]
+fn different_seeds_generate_different_roles() {
+    let role_lists: Vec<Vec<Role>> = (0..20)
+        .filter_map(|seed| mock_game(seeded_settings(seed, 10), 10).ok())
+        .map(|game| roles(&game))
+        .collect();
+
+    assert!(role_lists.iter().any(|r| *r != role_lists[0]));
+}
+
+#[test]
+fn replay_records_packets_and_phases() {
+    let mut game = mock_game(seeded_settings(3, 6), 6).expect("Game should be created");
+    let mut starting_roles = game.roles_to_players.clone();
+    starting_roles.sort_by_key(|(_, player)| *player);
+
+    game.on_client_message(0, ToServerPacket::SaveWill { will: "I'm town".to_string() });
+    PhaseStateMachine::next_phase(&mut game, None);
+
+    let replay = Replay::from_game(&game);
+    let replay: Replay = serde_json::from_str(&serde_json::to_string(&replay).unwrap()).unwrap();
+
+    assert_eq!(replay.version, REPLAY_VERSION);
+    assert_eq!(replay.seed, 3);
+    assert_eq!(replay.starting_roles, starting_roles.into_iter().map(|(role, _)| role).collect::<Vec<_>>());
+    assert!(matches!(
+        replay.log.as_slice(),
+        [
+            GameLogEntry::PlayerPacket { player: 0, packet: ToServerPacket::SaveWill { .. } },
+            GameLogEntry::PhaseStart { phase: PhaseState::Dusk, day_number: 1 }
+        ]
+    ));
+}
+
+#[test]
+fn replay_reproduces_recorded_game() {
+    for seed in 0..10 {
+        let replay = Replay::from_game(&play_game(seed));
+        let replay: Replay = serde_json::from_str(&serde_json::to_string(&replay).unwrap()).unwrap();
+
+        assert!(!replay.outcome.graves.is_empty());
+        if let Err(mismatch) = replay.verify() {
+            panic!("Replay with seed {seed} did not match: {mismatch:?}");
+        }
+    }
+}
+
+#[test]
+fn replay_detects_changed_outcome() {
+    let mut replay = Replay::from_game(&play_game(0));
+    replay.outcome.graves.pop();
+    assert!(matches!(replay.verify().map_err(|mismatch| *mismatch), Err(ReplayMismatch::Graves { .. })));
+
+    let mut replay = Replay::from_game(&play_game(0));
+    replay.outcome.winners.push(replay.player_names.len() as PlayerIndex);
+    assert!(matches!(replay.verify().map_err(|mismatch| *mismatch), Err(ReplayMismatch::Winners { .. })));
+}
+
+#[test]
+fn phase_ends_in_the_tick_its_time_runs_out() {
+    let players = (0..7).map(|i| connected_player(i.to_string())).collect();
+    let mut game = Game::new(seeded_settings(0, 7), players, Vec::new()).expect("Game should be created");
+    assert_eq!(game.current_phase().phase(), PhaseType::Briefing);
+
+    let briefing_time = game.phase_time_remaining();
+    game.tick(briefing_time - Duration::from_millis(1));
+    assert_eq!(game.current_phase().phase(), PhaseType::Briefing);
+    assert_eq!(game.phase_time_remaining(), Duration::from_millis(1));
+
+    game.tick(Duration::from_millis(1));
+    assert_ne!(game.current_phase().phase(), PhaseType::Briefing);
+}
+
+#[test]
+fn paused_game_keeps_its_phase_time() {
+    let players = (0..7).map(|i| connected_player(i.to_string())).collect();
+    let mut game = Game::new(seeded_settings(0, 7), players, Vec::new()).expect("Game should be created");
+    let briefing_time = game.phase_time_remaining();
+
+    game.pause(PauseReason::Host);
+    game.tick(briefing_time * 2);
+    assert_eq!(game.current_phase().phase(), PhaseType::Briefing);
+    assert_eq!(game.phase_time_remaining(), briefing_time);
+
+    game.resume();
+    game.tick(briefing_time);
+    assert_ne!(game.current_phase().phase(), PhaseType::Briefing);
+
+    assert!(Replay::from_game(&game).verify().is_ok());
+}
+
+#[test]
+fn auto_pause_waits_for_players_to_reconnect() {
+    let players = (0..7).map(|i| connected_player(i.to_string())).collect();
+    let mut game = Game::new(Settings { auto_pause: true, ..seeded_settings(0, 7) }, players, Vec::new()).expect("Game should be created");
+    let player = PlayerReference::new(&game, 3).unwrap();
+
+    player.lose_connection(&mut game);
+    assert_eq!(game.paused(), Some(PauseReason::LostConnection));
+
+    let (sender, _) = unbounded_channel();
+    player.connect(&mut game, Connection::new(sender, SocketAddr::from(([127, 0, 0, 1], 0))).get_sender());
+    assert_eq!(game.paused(), None);
+}
+
+#[test]
+fn nomination_thresholds() {
+    assert_eq!(NominationThreshold::Majority.votes_required(7), 4);
+    assert_eq!(NominationThreshold::Majority.votes_required(8), 5);
+    assert_eq!(NominationThreshold::Half.votes_required(7), 4);
+    assert_eq!(NominationThreshold::Half.votes_required(8), 4);
+    assert_eq!(NominationThreshold::TwoThirds.votes_required(9), 6);
+    assert_eq!(NominationThreshold::TwoThirds.votes_required(0), 1);
+}
+
+#[test]
+fn game_rules_are_used() {
+    let rules = GameRules { trials_per_day: 1, ..Default::default() };
+    let players = (0..7).map(|i| connected_player(i.to_string())).collect();
+    let mut game = Game::new(Settings { rules, ..seeded_settings(0, 7) }, players, Vec::new()).expect("Game should be created");
+
+    while game.current_phase().phase() != PhaseType::Nomination {
+        PhaseStateMachine::next_phase(&mut game, None);
+    }
+    assert_eq!(*game.current_phase(), PhaseState::Nomination { trials_left: 1 });
+
+    let rules = GameRules { trials_per_day: 0, ..Default::default() };
+    let players = (0..7).map(|i| connected_player(i.to_string())).collect();
+    assert!(matches!(
+        Game::new(Settings { rules, ..seeded_settings(0, 7) }, players, Vec::new()),
+        Err(RejectStartReason::InvalidGameRules)
+    ));
+}
+
+#[test]
+fn game_rules_have_upper_bounds() {
+    assert!(GameRules { trials_per_day: GameRules::MAX_TRIALS_PER_DAY, ..Default::default() }.is_valid());
+    assert!(!GameRules { trials_per_day: 255, ..Default::default() }.is_valid());
+    assert!(!GameRules { speed_up_factor: 255, ..Default::default() }.is_valid());
+    assert!(!GameRules { mayor_voting_power: 255, ..Default::default() }.is_valid());
+}
+
+/// Seven players, six of them detectives, in the first nomination of the day
+fn game_at_nomination(trial_system: TrialSystem) -> Game {
+    let mut role_list = vec![RoleOutline::new_exact(Role::Detective); 6];
+    role_list.push(RoleOutline::new_exact(Role::Mafioso));
+    let settings = Settings {
+        role_list: RoleList(role_list),
+        rules: GameRules { trial_system, ..Default::default() },
+        ..seeded_settings(0, 7)
+    };
+    let players = (0..7).map(|i| connected_player(i.to_string())).collect();
+    let mut game = Game::new(settings, players, Vec::new()).expect("Game should be created");
+
+    while game.current_phase().phase() != PhaseType::Nomination {
+        PhaseStateMachine::next_phase(&mut game, None);
+    }
+    game
+}
+
+fn vote(game: &mut Game, voters: Range<PlayerIndex>, player_index: PlayerIndex) {
+    for voter in voters {
+        game.on_client_message(voter, ToServerPacket::Vote { player_index: Some(player_index) });
+    }
+}
+
+#[test]
+fn plurality_puts_most_voted_on_trial() {
+    let mut game = game_at_nomination(TrialSystem::Plurality);
+
+    // A majority doesn't start the trial early
+    vote(&mut game, 0..4, 6);
+    vote(&mut game, 4..6, 5);
+    assert_eq!(game.current_phase().phase(), PhaseType::Nomination);
+
+    PhaseStateMachine::next_phase(&mut game, None);
+    assert_eq!(*game.current_phase(), PhaseState::Testimony { trials_left: 2, player_on_trial: PlayerReference::new(&game, 6).unwrap() });
+}
+
+#[test]
+fn plurality_tie_skips_trial() {
+    let mut game = game_at_nomination(TrialSystem::Plurality);
+    assert_eq!(game.plurality_vote_winner(), PluralityVoteResult::NoVotes);
+
+    vote(&mut game, 0..2, 6);
+    vote(&mut game, 2..4, 5);
+    assert_eq!(game.plurality_vote_winner(), PluralityVoteResult::Tie(vec![
+        PlayerReference::new(&game, 5).unwrap(),
+        PlayerReference::new(&game, 6).unwrap()
+    ]));
+
+    PhaseStateMachine::next_phase(&mut game, None);
+    assert_eq!(*game.current_phase(), PhaseState::Dusk);
+}
+
+#[test]
+fn direct_lynch_skips_judgement() {
+    let mut game = game_at_nomination(TrialSystem::DirectLynch);
+    let player_on_trial = PlayerReference::new(&game, 6).unwrap();
+
+    vote(&mut game, 0..4, 6);
+    assert_eq!(*game.current_phase(), PhaseState::FinalWords { player_on_trial });
+
+    PhaseStateMachine::next_phase(&mut game, None);
+    assert!(!player_on_trial.alive(&game));
+}
+
+#[test]
+fn game_summary_covers_whole_game() {
+    let game = play_game(0);
+    let summary = GameHistory::summary(&game);
+
+    assert!(summary.resolution.is_some());
+    assert_eq!(summary.graves.len(), game.graves.len());
+    assert_eq!(summary.nights.len() as u8, game.day_number() - 1);
+    assert!(summary.nights.iter().all(|night| !night.actions.is_empty()));
+    assert!(summary.trials.iter().all(|trial| !trial.verdicts.is_empty()));
+
+    for (player, player_summary) in PlayerReference::all_players(&game).zip(summary.players) {
+        assert_eq!(player_summary.final_role, player.role(&game));
+        if player_summary.role_switches.is_empty() {
+            assert_eq!(player_summary.starting_role, player_summary.final_role);
+        }
+    }
+}
+
+#[test]
+fn invariant_violations_are_reported() {
+    let mut game = mock_game(seeded_settings(3, 6), 6).expect("Game should be created");
+    assert_eq!(game.check_invariants(), Ok(()));
+
+    let (_, player) = game.roles_to_players[0];
+    game.roles_to_players.push(game.roles_to_players[0]);
+
+    let violations = game.check_invariants().unwrap_err();
+    assert!(violations.contains(&InvariantViolation::PlayerNotInRolesToPlayers { player, count: 2 }));
+}
+
+#[test]
+fn live_games_keep_going_after_an_invariant_violation() {
+    let players = (0..6).map(|i| connected_player(i.to_string())).collect();
+    let mut game = Game::new(seeded_settings(3, 6), players, Vec::new()).expect("Game should be created");
+    assert!(!game.panic_on_invariant_violation);
+
+    game.roles_to_players.push(game.roles_to_players[0]);
+    let phase = game.current_phase().phase();
+    PhaseStateMachine::next_phase(&mut game, None);
+
+    assert_ne!(game.current_phase().phase(), phase);
+    assert!(game.check_invariants().is_err());
+}
+
+#[test]
+fn outline_modifiers_go_to_the_player_with_its_role() {
+    let mut role_list = vec![RoleOutline::new_exact(Role::Detective), RoleOutline::new_exact(Role::Mafioso), RoleOutline::new_exact(Role::Jester)];
+    role_list[0] = RoleOutline::RoleOutlineOptions {
+        options: vec1::vec1![mafia_server::game::role_list::RoleOutlineOptionRoles::Role { role: Role::Detective }.into()],
+        modifiers: vec![RoleModifier::Bulletproof, RoleModifier::Blind]
+    };
+    let settings = Settings { role_list: RoleList(role_list), enabled_roles: Role::values().into_iter().collect(), seed: Some(4), ..Default::default() };
+
+    let game = mock_game(settings, 3).expect("Game should be created");
+
+    for player in PlayerReference::all_players(&game) {
+        if player.role(&game) == Role::Detective {
+            assert_eq!(*player.modifiers(&game), vec![RoleModifier::Bulletproof, RoleModifier::Blind]);
+            assert_eq!(player.defense(&game), Role::Detective.defense() + 1);
+        } else {
+            assert!(player.modifiers(&game).is_empty());
+        }
+    }
+}
+
+fn role_options_settings(role_options: RoleOptionsMap) -> Settings {
+    Settings {
+        role_list: RoleList(vec![
+            RoleOutline::new_exact(Role::Medium),
+            RoleOutline::new_exact(Role::Journalist),
+            RoleOutline::new_exact(Role::Mafioso)
+        ]),
+        role_options,
+        ..seeded_settings(0, 3)
+    }
+}
+
+#[test]
+fn role_options_change_role_states() {
+    let role_options = RoleOptionsMap([
+        (Role::Medium, RoleOptions { uses: Some(5), defense: Some(2), ..Default::default() }),
+        (Role::Journalist, RoleOptions { starts_public: Some(false), ..Default::default() }),
+    ].into_iter().collect());
+    let game = mock_game(role_options_settings(role_options), 3).expect("Game should be created");
+
+    for player in PlayerReference::all_players(&game) {
+        match player.role_state(&game) {
+            RoleState::Medium(_) => {
+                assert_eq!(player.ability_charges(&game, Ability::Seance), 5);
+                assert_eq!(player.defense(&game), 2);
+            }
+            RoleState::Journalist(journalist) => assert!(!journalist.public),
+            _ => assert_eq!(player.defense(&game), player.role(&game).defense()),
+        }
+    }
+}
+
+#[test]
+fn role_options_are_checked_for_their_role() {
+    assert_eq!(RoleOptions { uses: Some(1), ..Default::default() }.check(Role::Detective), Err(RoleOptionsError::UsesNotSupported));
+    assert_eq!(RoleOptions { starts_public: Some(true), ..Default::default() }.check(Role::Mayor), Err(RoleOptionsError::StartsPublicNotSupported));
+    assert_eq!(RoleOptions { maximum_count: Some(0), ..Default::default() }.check(Role::Mayor), Err(RoleOptionsError::MaximumCountIsZero));
+    assert_eq!(RoleOptions { uses: Some(0), defense: Some(3), ..Default::default() }.check(Role::Medium), Ok(()));
+
+    let role_options = RoleOptionsMap([(Role::Mafioso, RoleOptions { uses: Some(2), ..Default::default() })].into_iter().collect());
+    let players = (0..3).map(|i| connected_player(i.to_string())).collect();
+    assert!(matches!(
+        Game::new(role_options_settings(role_options), players, Vec::new()),
+        Err(RejectStartReason::InvalidRoleOptions { role: Role::Mafioso, error: RoleOptionsError::UsesNotSupported })
+    ));
+}
+
+#[test]
+fn games_with_bots_are_recreated_from_the_seed() {
+    let log = play_bot_game(5);
+    assert!(log.iter().any(|entry| matches!(entry, GameLogEntry::PlayerPacket { .. })), "Bots should have played");
+    assert_eq!(log, play_bot_game(5));
+}
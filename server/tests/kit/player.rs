@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use mafia_server::{game::{chat::ChatMessageVariant, phase::PhaseState, player::{PlayerIndex, PlayerReference}, role::{Role, RoleState}, tag::Tag, verdict::Verdict, Game}, packet::ToServerPacket};
+use mafia_server::{game::{chat::ChatMessageVariant, components::ability_charges::Ability, phase::PhaseState, player::{PlayerIndex, PlayerReference}, role::{Role, RoleState}, role_modifier::RoleModifier, tag::Tag, verdict::Verdict, Game}, packet::ToServerPacket};
 use vec1::Vec1;
 
 #[derive(Clone, Copy, Debug)]
@@ -118,6 +118,18 @@ impl TestPlayer {
         self.0.set_role_state(game!(self), new_role_data);
     }
 
+    pub fn set_role(&self, new_role_data: RoleState){
+        self.0.set_role(game!(self), new_role_data);
+    }
+
+    pub fn ability_charges(&self, ability: Ability) -> u8 {
+        self.0.ability_charges(game!(self), ability)
+    }
+
+    pub fn set_modifiers(&self, modifiers: Vec<RoleModifier>){
+        self.0.set_modifiers(game!(self), modifiers);
+    }
+
     pub fn get_player_tags(&self) -> &HashMap<PlayerReference, Vec1<Tag>> {
         self.0.player_tags(game!(self))
     }
@@ -0,0 +1,81 @@
+use std::{sync::{Arc, Mutex}, time::Duration};
+
+use mafia_server::{
+    listener::{Listener, RoomCode},
+    packet::{ToClientPacket, ToServerPacket},
+    websocket_connections::connection::Connection
+};
+use tokio::{sync::mpsc::{unbounded_channel, UnboundedReceiver}, time::timeout};
+use tokio_tungstenite::tungstenite::Message;
+
+fn connect(listener: &Arc<Mutex<Listener>>, port: u16) -> (Connection, UnboundedReceiver<ToClientPacket>) {
+    let (sender, receiver) = unbounded_channel();
+    let connection = Connection::new(sender, ([127, 0, 0, 1], port).into());
+    listener.lock().unwrap().on_connect(&connection);
+    (connection, receiver)
+}
+
+fn send(listener: &Arc<Mutex<Listener>>, connection: &Connection, packet: ToServerPacket) {
+    let message = Message::text(serde_json::to_string(&packet).unwrap());
+    listener.lock().unwrap().on_message(listener, connection, &message);
+}
+
+/// Waits for the lobby's task to accept the client, returning the room code
+async fn accepted(receiver: &mut UnboundedReceiver<ToClientPacket>) -> RoomCode {
+    loop {
+        match timeout(Duration::from_secs(5), receiver.recv()).await {
+            Ok(Some(ToClientPacket::AcceptJoin { room_code, .. })) => return room_code,
+            Ok(Some(ToClientPacket::RejectJoin { .. })) => panic!("Client was rejected"),
+            Ok(Some(_)) => {},
+            _ => panic!("Client was never accepted"),
+        }
+    }
+}
+
+#[tokio::test]
+async fn lobby_task_handles_joins() {
+    let listener = Arc::new(Mutex::new(Listener::new()));
+
+    let (host, mut host_receiver) = connect(&listener, 1);
+    send(&listener, &host, ToServerPacket::Host);
+    let room_code = accepted(&mut host_receiver).await;
+
+    let (player, mut player_receiver) = connect(&listener, 2);
+    send(&listener, &player, ToServerPacket::Join { room_code });
+    assert_eq!(accepted(&mut player_receiver).await, room_code);
+
+    // The lobby list is updated by the lobby's task after it handles the join
+    let (browser, mut browser_receiver) = connect(&listener, 3);
+    for _ in 0..50 {
+        send(&listener, &browser, ToServerPacket::LobbyListRequest);
+        let Some(ToClientPacket::LobbyList { lobbies }) = browser_receiver.recv().await else {
+            panic!("Expected the lobby list");
+        };
+        if lobbies.get(&room_code).is_some_and(|lobby| lobby.players.len() == 2) {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    panic!("The lobby list never showed both players");
+}
+
+#[tokio::test]
+async fn shut_down_lobbies_stop_and_no_more_are_made() {
+    let listener = Arc::new(Mutex::new(Listener::new()));
+
+    let (host, mut host_receiver) = connect(&listener, 1);
+    send(&listener, &host, ToServerPacket::Host);
+    accepted(&mut host_receiver).await;
+
+    let tasks = listener.lock().unwrap().shut_down_lobbies();
+    assert_eq!(tasks.len(), 1);
+    for task in tasks {
+        timeout(Duration::from_secs(5), task).await
+            .expect("The lobby's task should stop once the listener stops routing to it")
+            .unwrap();
+    }
+
+    let (other_host, mut other_host_receiver) = connect(&listener, 2);
+    send(&listener, &other_host, ToServerPacket::Host);
+    assert!(matches!(other_host_receiver.recv().await, Some(ToClientPacket::RejectJoin { .. })));
+}
@@ -0,0 +1,223 @@
+use std::time::Duration;
+
+use mafia_server::{
+    game::{preset::Preset, replay::Replay, role::Role, role_list::{CustomRoleSet, RoleList, RoleOutline, RoleOutlineOptionRoles}, settings::{GameRules, Settings}},
+    lobby::{lobby_client::LobbyClientID, snapshot::LobbySnapshot, Lobby},
+    packet::{RejectJoinReason, ToClientPacket, ToServerPacket},
+    websocket_connections::connection::{ClientSender, Connection}
+};
+use tokio::sync::mpsc::unbounded_channel;
+use vec1::vec1;
+
+fn sender() -> ClientSender {
+    // The receiver is dropped right away, so everything sent to this client is thrown out
+    let (sender, _) = unbounded_channel();
+    Connection::new(sender, ([127, 0, 0, 1], 0).into()).get_sender()
+}
+
+fn lobby_with_players(players: usize) -> (Lobby, Vec<LobbyClientID>) {
+    let mut lobby = Lobby::new(1234);
+    let ids = (0..players).map(|_| lobby.join_player(&sender()).expect("Player should join")).collect();
+    (lobby, ids)
+}
+
+fn round_trip(lobby: &Lobby) -> Lobby {
+    let snapshot = lobby.snapshot().expect("Lobby should be open");
+    let snapshot: LobbySnapshot = serde_json::from_str(&serde_json::to_string(&snapshot).unwrap()).unwrap();
+    Lobby::from_snapshot(snapshot).expect("Lobby should be restored")
+}
+
+fn sorted_player_list(lobby: &Lobby) -> Vec<(LobbyClientID, String)> {
+    let mut players = lobby.get_player_list();
+    players.sort();
+    players
+}
+
+#[test]
+fn restored_lobby_keeps_clients() {
+    let (lobby, ids) = lobby_with_players(4);
+
+    let mut restored = round_trip(&lobby);
+
+    assert!(!restored.is_in_game());
+    assert_eq!(sorted_player_list(&restored), sorted_player_list(&lobby));
+    assert!(restored.is_host(ids[0]));
+    assert!(restored.rejoin_player(&sender(), ids[2]).is_ok());
+}
+
+#[test]
+fn restored_game_matches_snapshot() {
+    let (mut lobby, ids) = lobby_with_players(7);
+    lobby.on_client_message(&sender(), ids[0], ToServerPacket::SetEnabledRoles { roles: Role::values() });
+    lobby.on_client_message(&sender(), ids[0], ToServerPacket::StartGame);
+    assert!(lobby.is_in_game());
+
+    for _ in 0..10 {
+        lobby.tick(Duration::from_secs(7));
+    }
+    lobby.on_client_message(&sender(), ids[3], ToServerPacket::SaveWill { will: "I was here".to_string() });
+
+    let mut restored = round_trip(&lobby);
+
+    let snapshot = serde_json::to_value(lobby.snapshot()).unwrap();
+    let restored_snapshot = serde_json::to_value(restored.snapshot()).unwrap();
+    for field in ["log", "outcome", "startingRoles"] {
+        assert_eq!(snapshot["state"]["replay"][field], restored_snapshot["state"]["replay"][field]);
+    }
+    assert_eq!(snapshot["state"]["phaseTimeRemaining"], restored_snapshot["state"]["phaseTimeRemaining"]);
+
+    assert!(restored.is_in_game());
+    assert_eq!(sorted_player_list(&restored), sorted_player_list(&lobby));
+    assert!(restored.rejoin_player(&sender(), ids[3]).is_ok());
+    assert!(matches!(restored.rejoin_player(&sender(), ids[3]), Err(RejectJoinReason::PlayerTaken)));
+}
+
+#[test]
+fn back_to_lobby_keeps_settings_and_players() {
+    let (mut lobby, ids) = lobby_with_players(7);
+    lobby.on_client_message(&sender(), ids[0], ToServerPacket::SetEnabledRoles { roles: Role::values() });
+    lobby.on_client_message(&sender(), ids[0], ToServerPacket::StartGame);
+    assert!(lobby.is_in_game());
+
+    let spectator = lobby.join_player(&sender()).expect("Spectator should join");
+    lobby.remove_player(ids[6]);
+    lobby.on_client_message(&sender(), ids[0], ToServerPacket::BackToLobby);
+    assert!(!lobby.is_in_game());
+
+    let players: Vec<LobbyClientID> = sorted_player_list(&lobby).into_iter().map(|(id, _)| id).collect();
+    assert_eq!(players, [&ids[..6], &[spectator]].concat());
+    assert!(lobby.is_host(ids[0]));
+
+    let snapshot = serde_json::to_value(lobby.snapshot()).unwrap();
+    assert_eq!(snapshot["state"]["settings"]["enabledRoles"].as_array().unwrap().len(), Role::values().len());
+    assert_eq!(snapshot["state"]["settings"]["roleList"].as_array().unwrap().len(), 7);
+}
+
+#[test]
+fn role_outlines_follow_custom_role_sets() {
+    let (mut lobby, ids) = lobby_with_players(7);
+    let town_power = |roles: Vec<Role>| CustomRoleSet { name: "Town Power".to_string(), roles };
+    lobby.on_client_message(&sender(), ids[0], ToServerPacket::SetEnabledRoles {
+        roles: Role::values().into_iter().filter(|role| *role != Role::Deputy).collect()
+    });
+
+    lobby.on_client_message(&sender(), ids[0], ToServerPacket::SetCustomRoleSets {
+        custom_role_sets: vec![town_power(vec![Role::Mayor, Role::Veteran]), town_power(vec![Role::Jester])]
+    });
+    lobby.on_client_message(&sender(), ids[0], ToServerPacket::SetRoleOutline {
+        index: 0,
+        role_outline: RoleOutline::RoleOutlineOptions { options: vec1![RoleOutlineOptionRoles::CustomRoleSet { custom_role_set: town_power(vec![]) }.into()], modifiers: Vec::new() }
+    });
+    let snapshot = serde_json::to_value(lobby.snapshot()).unwrap();
+    assert_eq!(snapshot["state"]["settings"]["customRoleSets"].as_array().unwrap().len(), 1);
+    assert_eq!(snapshot["state"]["settings"]["roleList"][0]["options"][0]["customRoleSet"]["roles"], serde_json::json!(["mayor", "veteran"]));
+
+    lobby.on_client_message(&sender(), ids[0], ToServerPacket::SetCustomRoleSets { custom_role_sets: vec![town_power(vec![Role::Deputy])] });
+    let snapshot = serde_json::to_value(lobby.snapshot()).unwrap();
+    assert_eq!(snapshot["state"]["settings"]["roleList"][0]["options"][0]["customRoleSet"]["roles"], serde_json::json!(["deputy"]));
+
+    lobby.on_client_message(&sender(), ids[0], ToServerPacket::StartGame);
+    assert!(!lobby.is_in_game(), "Deputy isn't enabled, so the custom role set can't be used");
+
+    lobby.on_client_message(&sender(), ids[0], ToServerPacket::SetCustomRoleSets { custom_role_sets: vec![] });
+    let snapshot = serde_json::to_value(lobby.snapshot()).unwrap();
+    assert_eq!(snapshot["state"]["settings"]["roleList"][0]["type"], "any");
+}
+
+#[test]
+fn imported_preset_replaces_settings() {
+    let (mut lobby, ids) = lobby_with_players(5);
+    let settings = Settings {
+        role_list: RoleList(vec![RoleOutline::new_exact(Role::Mayor), RoleOutline::new_exact(Role::Jester)]),
+        seed: Some(7),
+        rules: GameRules { trials_per_day: 1, ..GameRules::default() },
+        ..Settings::default()
+    };
+    let code = Preset::new("Mayor and Jester".to_string(), &settings).to_code();
+
+    lobby.on_client_message(&sender(), ids[1], ToServerPacket::ImportPreset { code: code.clone() });
+    let snapshot = serde_json::to_value(lobby.snapshot()).unwrap();
+    assert_eq!(snapshot["state"]["settings"]["rules"]["trialsPerDay"], 3, "Only the host can load presets");
+
+    lobby.on_client_message(&sender(), ids[0], ToServerPacket::ImportPreset { code });
+    let snapshot = serde_json::to_value(lobby.snapshot()).unwrap();
+    assert_eq!(snapshot["state"]["settings"]["rules"]["trialsPerDay"], 1);
+    assert_eq!(snapshot["state"]["settings"]["roleList"][0]["options"][0]["role"], "mayor");
+    assert_eq!(snapshot["state"]["settings"]["roleList"].as_array().unwrap().len(), 5);
+    assert_eq!(snapshot["state"]["settings"]["seed"], serde_json::Value::Null);
+}
+
+#[test]
+fn bots_fill_the_game_and_play() {
+    let (mut lobby, ids) = lobby_with_players(2);
+    lobby.on_client_message(&sender(), ids[1], ToServerPacket::AddBot);
+    assert_eq!(lobby.get_player_list().len(), 2, "Only the host can add bots");
+
+    for _ in 0..3 {
+        lobby.on_client_message(&sender(), ids[0], ToServerPacket::AddBot);
+    }
+    assert_eq!(lobby.get_player_list().len(), 5);
+
+    lobby.on_client_message(&sender(), ids[0], ToServerPacket::SetEnabledRoles { roles: Role::values() });
+    lobby.on_client_message(&sender(), ids[0], ToServerPacket::SetRoleList { role_list: RoleList(
+        [Role::Godfather, Role::Doctor, Role::Detective, Role::Lookout, Role::Vigilante].into_iter().map(RoleOutline::new_exact).collect()
+    )});
+    lobby.on_client_message(&sender(), ids[0], ToServerPacket::StartGame);
+    assert!(lobby.is_in_game());
+
+    for _ in 0..12 {
+        lobby.tick(Duration::from_secs(1000));
+    }
+
+    let snapshot = serde_json::to_value(lobby.snapshot()).unwrap();
+    let bots: Vec<&serde_json::Value> = snapshot["state"]["players"].as_array().unwrap().iter()
+        .filter(|player| player["bot"] == true)
+        .map(|player| &player["playerIndex"])
+        .collect();
+    assert_eq!(bots.len(), 3);
+
+    let bot_packets: Vec<&serde_json::Value> = snapshot["state"]["replay"]["log"].as_array().unwrap().iter()
+        .filter(|entry| entry["type"] == "playerPacket" && bots.contains(&&entry["player"]))
+        .map(|entry| &entry["packet"])
+        .collect();
+    assert_eq!(bot_packets.iter().filter(|packet| packet["type"] == "saveWill").count(), 3);
+    assert!(bot_packets.iter().any(|packet| packet["type"] == "target"), "Bots should pick night targets");
+
+    let replay: Replay = serde_json::from_value(snapshot["state"]["replay"].clone()).unwrap();
+    assert!(replay.verify().is_ok(), "Games with bots should replay the same without them");
+}
+
+#[test]
+fn lobby_with_only_bots_closes() {
+    let (mut lobby, ids) = lobby_with_players(2);
+    lobby.on_client_message(&sender(), ids[0], ToServerPacket::AddBot);
+
+    lobby.remove_player(ids[0]);
+    assert!(!lobby.is_closed());
+    assert!(lobby.is_host(ids[1]), "The host should be passed to a player, not a bot");
+
+    lobby.remove_player(ids[1]);
+    assert!(lobby.is_closed());
+}
+
+#[test]
+fn role_list_analysis_is_kept_until_the_settings_change() {
+    let (mut lobby, ids) = lobby_with_players(2);
+    let (host_sender, mut host_packets) = unbounded_channel();
+    let host_sender = Connection::new(host_sender, ([127, 0, 0, 1], 0).into()).get_sender();
+    let mut analyses = || {
+        let mut count = 0;
+        while let Ok(packet) = host_packets.try_recv() {
+            if matches!(packet, ToClientPacket::RoleListAnalysis { .. }) {count += 1}
+        }
+        count
+    };
+
+    lobby.on_client_message(&host_sender, ids[0], ToServerPacket::AnalyzeRoleList);
+    lobby.on_client_message(&host_sender, ids[0], ToServerPacket::AnalyzeRoleList);
+    assert_eq!(analyses(), 2, "Asking again without changing anything should send the same analysis");
+
+    lobby.on_client_message(&sender(), ids[0], ToServerPacket::SetRoleList { role_list: RoleList(vec![RoleOutline::new_exact(Role::Mayor); 2]) });
+    lobby.on_client_message(&host_sender, ids[0], ToServerPacket::AnalyzeRoleList);
+    assert_eq!(analyses(), 0, "A changed role list can't be analyzed again right away");
+}
@@ -0,0 +1,70 @@
+use std::path::Path;
+
+use mafia_server::game::{
+    preset::{Preset, PresetError, PRESET_DIRECTORY, PRESET_VERSION},
+    role::Role,
+    role_list::{RoleList, RoleOutline},
+    settings::Settings
+};
+
+fn preset() -> Preset {
+    Preset::new("  Test preset  ".to_string(), &Settings {
+        role_list: RoleList(vec![RoleOutline::new_exact(Role::Mayor), RoleOutline::default()]),
+        enabled_roles: [Role::Mayor, Role::Jester].into_iter().collect(),
+        seed: Some(1),
+        ..Settings::default()
+    })
+}
+
+#[test]
+fn code_round_trip() {
+    let code = preset().to_code();
+    assert!(code.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'), "{code} isn't URL safe");
+
+    let loaded = Preset::from_code(&code).unwrap();
+    assert_eq!(loaded.name, "Test preset");
+    assert_eq!(loaded.version, PRESET_VERSION);
+    assert_eq!(loaded.settings.role_list, preset().settings.role_list);
+    assert_eq!(loaded.settings.enabled_roles, preset().settings.enabled_roles);
+    assert_eq!(loaded.settings.seed, None);
+
+    assert_eq!(Preset::from_code("not a preset").unwrap_err(), PresetError::InvalidCode);
+}
+
+#[test]
+fn presets_are_checked_against_this_server() {
+    let mut json = serde_json::to_value(preset()).unwrap();
+    json["settings"]["enabledRoles"] = serde_json::json!(["mayor", "ghostOfTheWeek"]);
+    assert_eq!(
+        Preset::from_json(&json.to_string()).unwrap_err(),
+        PresetError::UnknownRole { role: "ghostOfTheWeek".to_string() }
+    );
+
+    let mut json = serde_json::to_value(preset()).unwrap();
+    json["settings"]["roleOptions"] = serde_json::json!({"ghostOfTheWeek": {"uses": 1}});
+    assert_eq!(
+        Preset::from_json(&json.to_string()).unwrap_err(),
+        PresetError::UnknownRole { role: "ghostOfTheWeek".to_string() }
+    );
+
+    let mut json = serde_json::to_value(preset()).unwrap();
+    json["settings"]["roleOptions"] = serde_json::json!({"mayor": {"uses": 1}});
+    assert_eq!(Preset::from_json(&json.to_string()).unwrap_err(), PresetError::InvalidSettings);
+
+    let mut json = serde_json::to_value(preset()).unwrap();
+    json["version"] = (PRESET_VERSION + 1).into();
+    assert_eq!(
+        Preset::from_json(&json.to_string()).unwrap_err(),
+        PresetError::UnsupportedVersion { version: PRESET_VERSION + 1 }
+    );
+}
+
+#[test]
+fn bundled_presets_load() {
+    let presets = Preset::load_directory(Path::new(PRESET_DIRECTORY));
+    assert_eq!(
+        presets.len(),
+        std::fs::read_dir(PRESET_DIRECTORY).unwrap().count(),
+        "Every bundled preset should load"
+    );
+}
@@ -3,9 +3,9 @@ use std::vec;
 
 pub(crate) use kit::{assert_contains, assert_not_contains};
 
-use mafia_server::game::{components::cult::CultAbility, role::{armorsmith::Armorsmith, flower_girl::FlowerGirl, scarecrow::Scarecrow}};
+use mafia_server::game::{components::{ability_charges::Ability, cult::CultAbility, game_history::GameHistory, night_trace::TraceEvent, love_linked::LoveLinked}, visit::Visit, role::{armorsmith::Armorsmith, flower_girl::FlowerGirl, scarecrow::Scarecrow}, role_modifier::RoleModifier};
 pub use mafia_server::game::{
-    chat::{ChatMessageVariant, MessageSender, ChatGroup}, 
+    chat::{ChatMessageVariant, MessageSender, ChatGroup, TeamChat}, 
     grave::*, 
     role_list::Faction,
     player::PlayerReference,
@@ -13,6 +13,7 @@ pub use mafia_server::game::{
     verdict::Verdict,
     role::{
         Role,
+        Priority,
         RoleState,
 
         jailor::Jailor,
@@ -45,6 +46,7 @@ pub use mafia_server::game::{
 
         godfather::Godfather,
         mafioso::Mafioso,
+        counterfeiter::{Counterfeiter, CounterfeiterAction},
         
         framer::Framer,
         hypnotist::Hypnotist,
@@ -1045,6 +1047,7 @@ fn grave_contains_multiple_killers() {
                 death_cause: GraveDeathCause::Killers(vec![GraveKiller::Faction(Faction::Mafia), GraveKiller::Role(Role::Vigilante)]),
                 will: "".to_string(),
                 death_notes: vec![],
+                modifiers: vec![],
             }
         }
     )
@@ -1084,6 +1087,7 @@ fn grave_contains_multiple_killers_roles() {
                 death_cause: GraveDeathCause::Killers(vec![GraveKiller::Role(Role::Doomsayer), GraveKiller::Faction(Faction::Mafia), GraveKiller::Role(Role::Vigilante)]),
                 will: "".to_string(),
                 death_notes: vec![],
+                modifiers: vec![],
             }
         }
     );
@@ -1203,6 +1207,27 @@ fn godfathers_backup_tag_works() {
     assert!(blackmailer.get_player_tags().get(&blackmailer.player_ref()).is_none());
 }
 
+#[test]
+fn godfathers_backup_tag_is_removed_when_the_backup_dies_after_the_godfather_switched_roles() {
+    kit::scenario!(game in Discussion 2 where
+        godfather: Godfather,
+        blackmailer: Blackmailer,
+        hypnotist: Hypnotist,
+        _vigi: Vigilante,
+        _doctor: Doctor
+    );
+
+    assert!(godfather.day_target(hypnotist));
+    assert!(blackmailer.get_player_tags().get(&hypnotist.player_ref()).expect("hypnotist doesnt have tag").contains(&Tag::GodfatherBackup));
+
+    godfather.set_role(RoleState::Mafioso(Mafioso));
+    let grave = Grave::from_player_lynch(&game, hypnotist.player_ref());
+    hypnotist.player_ref().die(&mut game, grave);
+
+    assert!(blackmailer.get_player_tags().get(&hypnotist.player_ref()).is_none());
+    assert!(godfather.get_player_tags().get(&hypnotist.player_ref()).is_none());
+}
+
 #[test]
 fn gossip_basic_friends() {
     kit::scenario!(game in Night 1 where
@@ -1460,6 +1485,23 @@ fn godfather_wardblock_still_kills() {
     assert!(townie_a.alive());
 }
 
+#[test]
+fn game_history_records_the_cult_role_a_player_ends_up_with() {
+    kit::scenario!(game in Discussion 2 where
+        _apostle: Apostle,
+        _zealot: Zealot,
+        townie: Detective,
+        _doctor: Doctor,
+        _lookout: Lookout
+    );
+
+    // The cult already has an apostle, so this player is moved to the end of the cult as its zealot
+    townie.set_role(RoleState::Apostle(Apostle));
+
+    assert_eq!(townie.role(), Role::Zealot);
+    assert_eq!(GameHistory::recorded_role(&game, townie.player_ref()), Some(Role::Zealot));
+}
+
 #[test]
 fn cult_alternates() {
     kit::scenario!(game in Night 1 where
@@ -1921,6 +1963,24 @@ fn deputy_shoots_townie(){
     assert!(game.game_is_over());
 }
 
+#[test]
+fn deputy_shot_players_vote_is_cleared(){
+    kit::scenario!(game in Nomination 2 where
+        deputy: Deputy,
+        mafioso: Mafioso,
+        townie: Detective,
+        _doctor: Doctor,
+        _lookout: Lookout
+    );
+
+    townie.vote_for_player(Some(mafioso));
+    assert_eq!(townie.player_ref().chosen_vote(&game), Some(mafioso.player_ref()));
+
+    assert!(deputy.day_target(townie));
+    assert!(!townie.alive());
+    assert_eq!(townie.player_ref().chosen_vote(&game), None);
+}
+
 #[test]
 fn ojo_transporter(){
     kit::scenario!(game in Night 2 where
@@ -2132,4 +2192,305 @@ fn fiends_wildcard_defense_upgrade(){
     assert!(mafia.alive());
 
     assert!(game.game_is_over());
-}
\ No newline at end of file
+}
+
+#[test]
+fn bulletproof_survives_basic_attack() {
+    kit::scenario!(game in Night 2 where
+        townie: Detective,
+        mafioso: Mafioso
+    );
+    townie.set_modifiers(vec![RoleModifier::Bulletproof]);
+
+    assert!(mafioso.set_night_selection_single(townie));
+    game.next_phase();
+
+    assert!(townie.alive());
+    assert_contains!(townie.get_messages_after_night(2), ChatMessageVariant::YouSurvivedAttack);
+}
+
+#[test]
+fn lazy_is_roleblocked_every_other_night() {
+    kit::scenario!(game in Night 2 where
+        lazy: Detective,
+        mafioso: Mafioso
+    );
+    lazy.set_modifiers(vec![RoleModifier::Lazy]);
+
+    assert!(lazy.set_night_selection_single(mafioso));
+    game.next_phase();
+    assert!(lazy.was_roleblocked());
+    assert_not_contains!(lazy.get_messages_after_night(2), ChatMessageVariant::SheriffResult { suspicious: true });
+
+    game.skip_to(Night, 3);
+    assert!(lazy.set_night_selection_single(mafioso));
+    game.next_phase();
+    assert!(!lazy.was_roleblocked());
+    assert_contains!(lazy.get_messages_after_night(3), ChatMessageVariant::SheriffResult { suspicious: true });
+}
+
+#[test]
+fn blind_visits_are_not_seen() {
+    kit::scenario!(game in Night 2 where
+        lookout: Lookout,
+        blind: Doctor,
+        townie: Detective,
+        _mafioso: Mafioso
+    );
+    blind.set_modifiers(vec![RoleModifier::Blind]);
+
+    assert!(lookout.set_night_selection_single(townie));
+    assert!(blind.set_night_selection_single(townie));
+    game.next_phase();
+
+    assert_contains!(lookout.get_messages_after_night(2), ChatMessageVariant::LookoutResult { players: vec![] });
+}
+
+#[test]
+fn modifiers_are_revealed_on_grave() {
+    kit::scenario!(game in Night 2 where
+        townie: Detective,
+        mafioso: Mafioso,
+        _jester: Jester
+    );
+    townie.set_modifiers(vec![RoleModifier::Lucky]);
+
+    assert!(mafioso.set_night_selection_single(townie));
+    game.next_phase();
+
+    assert!(!townie.alive());
+    let grave = game.graves.iter().find(|grave| grave.player == townie.player_ref()).unwrap();
+    let GraveInformation::Normal { modifiers, .. } = &grave.information else {panic!("The grave should not be obscured")};
+    assert_eq!(*modifiers, vec![RoleModifier::Lucky]);
+}
+
+#[test]
+fn ability_charges_come_with_the_role() {
+    kit::scenario!(game in Night 2 where
+        vet: Veteran,
+        _townie: Detective,
+        _mafioso: Mafioso
+    );
+    assert_eq!(vet.ability_charges(Ability::Alert), 3);
+
+    assert!(vet.set_night_selection_single(vet));
+    game.next_phase();
+    assert_eq!(vet.ability_charges(Ability::Alert), 2);
+
+    vet.set_role(RoleState::Doctor(Doctor::default()));
+    assert_eq!(vet.ability_charges(Ability::Alert), 0);
+    assert_eq!(vet.ability_charges(Ability::SelfHeal), 1);
+
+    vet.set_role(RoleState::Veteran(Veteran::default()));
+    assert_eq!(vet.ability_charges(Ability::Alert), 3);
+}
+
+#[test]
+fn jailor_executing_town_loses_all_executions() {
+    kit::scenario!(game in Dusk 2 where
+        jailor: Jailor,
+        townie: Detective,
+        _mafioso: Mafioso
+    );
+    assert!(jailor.day_target(townie));
+    game.next_phase();
+
+    assert!(jailor.set_night_selection_single(townie));
+    game.next_phase();
+
+    assert!(!townie.alive());
+    assert_eq!(jailor.ability_charges(Ability::Execute), 0);
+}
+
+#[test]
+fn counterfeiter_backup_gets_remaining_forges() {
+    kit::scenario!(game in Night 2 where
+        cf: Counterfeiter,
+        backup: Framer,
+        townie: Detective,
+        vigi: Vigilante,
+        _jester: Jester
+    );
+    let RoleState::Counterfeiter(mut counterfeiter) = cf.role_state().clone() else {unreachable!()};
+    counterfeiter.action = CounterfeiterAction::Forge;
+    cf.set_role_state(RoleState::Counterfeiter(counterfeiter));
+
+    assert!(cf.set_night_selection_single(townie));
+    game.next_phase();
+    assert_eq!(cf.ability_charges(Ability::Forge), 2);
+
+    assert!(cf.day_target(backup));
+    game.skip_to(Night, 3);
+    assert!(vigi.set_night_selection_single(cf));
+    game.next_phase();
+
+    assert!(!cf.alive());
+    assert_eq!(backup.role(), Role::Counterfeiter);
+    assert_eq!(backup.ability_charges(Ability::Forge), 2);
+}
+
+#[test]
+fn night_trace_records_attacks_roleblocks_and_redirects() {
+    kit::scenario!(game in Night 2 where
+        mafioso: Mafioso,
+        townie: Detective,
+        esc: Escort,
+        bg: Bodyguard,
+        idle: Doctor
+    );
+    game.settings.rules.trace_night_actions = true;
+
+    assert!(mafioso.set_night_selection_single(townie));
+    assert!(esc.set_night_selection_single(townie));
+    assert!(bg.set_night_selection_single(townie));
+    game.next_phase();
+
+    let nights = game.night_trace().nights();
+    assert_eq!(nights.len(), 1);
+    let events: Vec<&TraceEvent> = nights[0].priorities.iter().flat_map(|priority| &priority.events).collect();
+    let night_actions: Vec<(Priority, PlayerReference)> = nights[0].priorities.iter()
+        .flat_map(|traced| traced.events.iter().filter_map(|event| match event {
+            TraceEvent::NightAction { actor, .. } => Some((traced.priority, *actor)),
+            _ => None
+        }))
+        .collect();
+
+    assert_eq!(night_actions, vec![
+        (Priority::Roleblock, esc.player_ref()),
+        (Priority::Bodyguard, bg.player_ref()),
+        (Priority::Kill, mafioso.player_ref()),
+        (Priority::Kill, bg.player_ref()),
+        (Priority::Investigative, bg.player_ref()),
+    ]);
+    assert!(!night_actions.iter().any(|(_, actor)| *actor == townie.player_ref() || *actor == idle.player_ref()));
+    assert!(events.contains(&&TraceEvent::Roleblock { player: townie.player_ref(), immune: false }));
+    assert!(events.contains(&&TraceEvent::VisitsChanged { player: mafioso.player_ref(), visits: vec![Visit::new(bg.player_ref(), true)] }));
+    assert!(events.iter().any(|event| matches!(event,
+        TraceEvent::Attack { attacker: Some(attacker), target, succeeded: true, .. } if *attacker == bg.player_ref() && *target == mafioso.player_ref()
+    )));
+}
+
+#[test]
+fn night_trace_is_off_by_default() {
+    kit::scenario!(game in Night 2 where
+        mafioso: Mafioso,
+        townie: Detective,
+        _jester: Jester
+    );
+
+    assert!(mafioso.set_night_selection_single(townie));
+    game.next_phase();
+
+    assert!(game.night_trace().nights().is_empty());
+}
+
+#[test]
+fn lovers_chat_at_night_only_when_the_host_turns_it_on() {
+    kit::scenario!(game in Night 1 where
+        townie: Detective,
+        mafioso: Mafioso,
+        doctor: Doctor
+    );
+    LoveLinked::add_love_link(&mut game, townie.player_ref(), mafioso.player_ref());
+
+    assert!(!townie.player_ref().get_current_send_chat_groups(&game).contains(&ChatGroup::Lovers));
+
+    game.settings.rules.team_chats.0.insert(TeamChat::Lovers);
+    assert!(townie.player_ref().get_current_send_chat_groups(&game).contains(&ChatGroup::Lovers));
+    assert!(mafioso.player_ref().get_current_send_chat_groups(&game).contains(&ChatGroup::Mafia));
+
+    townie.send_message("Hello!");
+    let message = ChatMessageVariant::Normal {
+        message_sender: MessageSender::Player { player: townie.index() },
+        text: "Hello!".to_string()
+    };
+    assert_contains!(mafioso.get_messages(), message.clone());
+    assert_not_contains!(doctor.get_messages(), message);
+}
+
+#[test]
+fn lovers_only_chat_with_who_they_are_linked_to() {
+    kit::scenario!(game in Night 1 where
+        a1: Detective,
+        a2: Doctor,
+        b1: Lookout,
+        b2: Escort,
+        _mafioso: Mafioso
+    );
+    LoveLinked::add_love_link(&mut game, a1.player_ref(), a2.player_ref());
+    LoveLinked::add_love_link(&mut game, b1.player_ref(), b2.player_ref());
+    game.settings.rules.team_chats.0.insert(TeamChat::Lovers);
+
+    a1.send_message("Hello a2!");
+    b1.send_message("Hello b2!");
+    let a_message = ChatMessageVariant::Normal {
+        message_sender: MessageSender::Player { player: a1.index() },
+        text: "Hello a2!".to_string()
+    };
+    let b_message = ChatMessageVariant::Normal {
+        message_sender: MessageSender::Player { player: b1.index() },
+        text: "Hello b2!".to_string()
+    };
+
+    assert_contains!(a2.get_messages(), a_message.clone());
+    assert_contains!(b2.get_messages(), b_message.clone());
+    assert_not_contains!(b1.get_messages(), a_message.clone());
+    assert_not_contains!(b2.get_messages(), a_message);
+    assert_not_contains!(a1.get_messages(), b_message.clone());
+    assert_not_contains!(a2.get_messages(), b_message);
+}
+
+#[test]
+fn players_in_two_team_chats_send_to_both_once() {
+    kit::scenario!(game in Night 1 where
+        townie: Detective,
+        mafioso: Mafioso,
+        mortician: Mortician,
+        doctor: Doctor
+    );
+    LoveLinked::add_love_link(&mut game, townie.player_ref(), mafioso.player_ref());
+    LoveLinked::add_love_link(&mut game, mortician.player_ref(), mafioso.player_ref());
+    game.settings.rules.team_chats.0.insert(TeamChat::Lovers);
+
+    assert!(mafioso.player_ref().get_current_send_chat_groups(&game).contains(&ChatGroup::Mafia));
+    assert!(mafioso.player_ref().get_current_send_chat_groups(&game).contains(&ChatGroup::Lovers));
+
+    mafioso.send_message("Hello!");
+    let message = ChatMessageVariant::Normal {
+        message_sender: MessageSender::Player { player: mafioso.index() },
+        text: "Hello!".to_string()
+    };
+    assert_eq!(mortician.get_messages().iter().filter(|m| **m == message).count(), 1);
+    assert_eq!(mafioso.get_messages().iter().filter(|m| **m == message).count(), 1);
+    assert_contains!(townie.get_messages(), message.clone());
+    assert_not_contains!(doctor.get_messages(), message);
+
+    townie.send_message("Hi!");
+    let reply = ChatMessageVariant::Normal {
+        message_sender: MessageSender::Player { player: townie.index() },
+        text: "Hi!".to_string()
+    };
+    assert_contains!(mafioso.get_messages(), reply);
+
+    game.on_client_message(mafioso.index(), ToServerPacket::Target { player_index_list: vec![townie.index()] });
+    let targeted = ChatMessageVariant::Targeted { targeter: mafioso.index(), targets: vec![townie.index()] };
+    assert_contains!(mortician.get_messages(), targeted.clone());
+    assert_not_contains!(townie.get_messages(), targeted);
+}
+
+#[test]
+fn host_can_turn_off_mafia_chat_and_give_fiends_one() {
+    kit::scenario!(game in Night 1 where
+        mafioso: Mafioso,
+        _mortician: Mortician,
+        arso: Arsonist,
+        _ojo: Ojo
+    );
+    game.settings.rules.team_chats.0 = vec![TeamChat::Fiends].into_iter().collect();
+
+    assert!(!mafioso.player_ref().get_current_send_chat_groups(&game).contains(&ChatGroup::Mafia));
+    assert!(!mafioso.player_ref().get_current_receive_chat_groups(&game).contains(&ChatGroup::Mafia));
+    assert!(arso.player_ref().get_current_send_chat_groups(&game).contains(&ChatGroup::Fiends));
+    assert!(!ChatGroup::Fiends.all_players_in_group(&game).contains(&mafioso.player_ref()));
+    assert_eq!(ChatGroup::Fiends.all_players_in_group(&game).len(), 2);
+}
@@ -0,0 +1,152 @@
+use std::collections::HashSet;
+
+use mafia_server::game::{
+    role::Role,
+    role_list::{Faction, RoleList, RoleOutline, RoleOutlineOption, RoleOutlineOptionRoles},
+    role_list_analysis::RoleListAnalysis,
+    role_list_generation::RoleListConstraint,
+    role_options::{RoleOptions, RoleOptionsMap},
+    RejectStartReason
+};
+use rand::{rngs::StdRng, SeedableRng};
+use vec1::Vec1;
+
+fn outline(options: Vec<(Role, u8)>) -> RoleOutline {
+    RoleOutline::RoleOutlineOptions {
+        options: Vec1::try_from_vec(
+            options.into_iter().map(|(role, weight)| RoleOutlineOption { roles: RoleOutlineOptionRoles::Role { role }, weight }).collect()
+        ).unwrap(),
+        modifiers: Vec::new()
+    }
+}
+
+fn all_roles() -> HashSet<Role> {
+    Role::values().into_iter().collect()
+}
+
+#[test]
+fn weighted_options_are_picked_more_often() {
+    let role_list = RoleList(vec![outline(vec![(Role::Mayor, 9), (Role::Jester, 1)])]);
+
+    let mayors = (0..200).filter(|seed| {
+        let roles = role_list.create_random_roles(&all_roles(), &[], &RoleOptionsMap::default(), &mut StdRng::seed_from_u64(*seed)).unwrap();
+        roles == [Role::Mayor]
+    }).count();
+
+    assert!(mayors > 150, "Mayor was only picked {mayors} times out of 200");
+}
+
+#[test]
+fn role_list_constraints_are_followed() {
+    let role_list = RoleList(vec![
+        outline(vec![(Role::Detective, 1), (Role::Lookout, 1)]),
+        outline(vec![(Role::Detective, 1), (Role::Lookout, 1), (Role::Doctor, 1)]),
+        outline(vec![(Role::Vigilante, 1), (Role::Jester, 1)]),
+    ]);
+    let constraints = [
+        RoleListConstraint::AtMost { roles: vec![Role::Detective, Role::Lookout], count: 1 },
+        RoleListConstraint::Requires { role: Role::Vigilante, required_role: Role::Mayor },
+    ];
+
+    for seed in 0..50 {
+        let roles = role_list.create_random_roles(&all_roles(), &constraints, &RoleOptionsMap::default(), &mut StdRng::seed_from_u64(seed)).unwrap();
+        assert_eq!(roles[1], Role::Doctor);
+        assert_eq!(roles[2], Role::Jester);
+    }
+}
+
+#[test]
+fn search_that_runs_out_of_steps_names_no_outline() {
+    // Eight outlines share seven roles that can each only be picked once, so every way of filling the first seven is tried
+    let roles = [Role::Detective, Role::Lookout, Role::Doctor, Role::Tracker, Role::Psychic, Role::Philosopher, Role::Snoop];
+    let role_list = RoleList(vec![outline(roles.iter().map(|role| (*role, 1)).collect()); 8]);
+    let constraints: Vec<RoleListConstraint> = roles.iter()
+        .map(|role| RoleListConstraint::AtMost { roles: vec![*role], count: 1 })
+        .collect();
+
+    assert_eq!(
+        role_list.create_random_roles(&all_roles(), &constraints, &RoleOptionsMap::default(), &mut StdRng::seed_from_u64(0)),
+        Err(RejectStartReason::RoleListTooComplex)
+    );
+}
+
+#[test]
+fn analysis_of_a_slow_role_list_stops_early() {
+    let roles = [Role::Detective, Role::Lookout, Role::Doctor, Role::Tracker, Role::Psychic, Role::Philosopher, Role::Snoop];
+    let role_list = RoleList(vec![outline(roles.iter().map(|role| (*role, 1)).collect()); 8]);
+    let constraints: Vec<RoleListConstraint> = roles.iter()
+        .map(|role| RoleListConstraint::AtMost { roles: vec![*role], count: 1 })
+        .collect();
+
+    let analysis = RoleListAnalysis::new(&role_list, &all_roles(), &constraints, &RoleOptionsMap::default(), RoleListAnalysis::DEFAULT_SAMPLES, &mut StdRng::seed_from_u64(0));
+    assert!(analysis.samples > 0);
+    assert!(analysis.samples < RoleListAnalysis::DEFAULT_SAMPLES);
+    assert_eq!(analysis.failure_chance, 1.0);
+}
+
+#[test]
+fn unsatisfiable_outline_is_named() {
+    let role_list = RoleList(vec![
+        RoleOutline::new_exact(Role::Mayor),
+        RoleOutline::new_exact(Role::Detective),
+        RoleOutline::new_exact(Role::Vigilante),
+    ]);
+    let mut rng = StdRng::seed_from_u64(0);
+
+    let constraints = [RoleListConstraint::Requires { role: Role::Vigilante, required_role: Role::Doctor }];
+    assert_eq!(
+        role_list.create_random_roles(&all_roles(), &constraints, &RoleOptionsMap::default(), &mut rng),
+        Err(RejectStartReason::RoleOutlineCannotBeSatisfied { outline: 2 })
+    );
+
+    let enabled_roles = all_roles().into_iter().filter(|role| *role != Role::Detective).collect();
+    assert_eq!(
+        role_list.create_random_roles(&enabled_roles, &[], &RoleOptionsMap::default(), &mut rng),
+        Err(RejectStartReason::RoleOutlineCannotBeSatisfied { outline: 1 })
+    );
+}
+
+#[test]
+fn analysis_reports_role_and_faction_chances() {
+    let role_list = RoleList(vec![
+        RoleOutline::new_exact(Role::Godfather),
+        outline(vec![(Role::Detective, 1), (Role::Lookout, 1)]),
+        RoleOutline::new_exact(Role::Doctor),
+    ]);
+    let analysis = RoleListAnalysis::new(&role_list, &all_roles(), &[], &RoleOptionsMap::default(), 400, &mut StdRng::seed_from_u64(0));
+
+    assert_eq!(analysis.failure_chance, 0.0);
+    assert_eq!(analysis.game_ends_instantly_chance, 0.0);
+    assert_eq!(analysis.faction_distributions[&Faction::Mafia][1], 1.0);
+    assert_eq!(analysis.faction_distributions[&Faction::Town][2], 1.0);
+    assert_eq!(analysis.role_chances[&Role::Godfather], 1.0);
+    assert!((0.4..0.6).contains(&analysis.role_chances[&Role::Detective]));
+    assert!(!analysis.role_chances.contains_key(&Role::Mayor));
+    assert!(analysis.impossible_outlines.is_empty());
+}
+
+#[test]
+fn analysis_finds_broken_role_lists() {
+    let town_only = RoleList(vec![RoleOutline::new_exact(Role::Detective), RoleOutline::new_exact(Role::Lookout)]);
+    let analysis = RoleListAnalysis::new(&town_only, &all_roles(), &[], &RoleOptionsMap::default(), 10, &mut StdRng::seed_from_u64(0));
+    assert_eq!(analysis.game_ends_instantly_chance, 1.0);
+
+    let enabled_roles = all_roles().into_iter().filter(|role| *role != Role::Lookout).collect();
+    let analysis = RoleListAnalysis::new(&town_only, &enabled_roles, &[], &RoleOptionsMap::default(), 10, &mut StdRng::seed_from_u64(0));
+    assert_eq!(analysis.failure_chance, 1.0);
+    assert_eq!(analysis.impossible_outlines, vec![1]);
+}
+
+#[test]
+fn maximum_count_can_be_changed() {
+    let role_list = RoleList(vec![RoleOutline::new_exact(Role::Mayor), RoleOutline::new_exact(Role::Mayor)]);
+    let mut rng = StdRng::seed_from_u64(0);
+
+    assert!(role_list.create_random_roles(&all_roles(), &[], &RoleOptionsMap::default(), &mut rng).is_err());
+
+    let role_options = RoleOptionsMap([(Role::Mayor, RoleOptions { maximum_count: Some(2), ..Default::default() })].into_iter().collect());
+    assert_eq!(
+        role_list.create_random_roles(&all_roles(), &[], &role_options, &mut rng),
+        Ok(vec![Role::Mayor, Role::Mayor])
+    );
+}
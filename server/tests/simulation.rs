@@ -0,0 +1,64 @@
+use mafia_server::game::{
+    preset::Preset,
+    resolution_state::ResolutionState,
+    role::Role,
+    role_list::{RoleList, RoleOutline},
+    settings::{GameRules, Settings},
+    simulation::{SimulatedGameResult, Simulation}
+};
+use rand::{rngs::StdRng, SeedableRng};
+
+#[test]
+fn classic_games_end_without_failures() {
+    let preset = Preset::from_json(include_str!("../resources/presets/classic.json")).unwrap();
+
+    let report = Simulation::new(preset.settings).run(50, &mut StdRng::seed_from_u64(0));
+
+    assert_eq!(report.games, 50);
+    assert!(report.failures.is_empty(), "{:?}", report.failures);
+    assert_eq!(report.ended_games() + report.reached_max_day, 50);
+    assert!(report.average_days() >= 1.0);
+
+    let total: f32 = ResolutionState::all().iter().map(|resolution| report.win_rate(resolution)).sum();
+    assert!((total - 1.0).abs() < 0.001);
+}
+
+#[test]
+fn same_seed_plays_same_game() {
+    let preset = Preset::from_json(include_str!("../resources/presets/classic.json")).unwrap();
+    let simulation = Simulation::new(preset.settings);
+
+    assert_eq!(format!("{:?}", simulation.play(7)), format!("{:?}", simulation.play(7)));
+}
+
+#[test]
+fn unplayable_settings_are_failures() {
+    let settings = Settings {
+        role_list: RoleList(vec![RoleOutline::new_exact(Role::Mayor)]),
+        enabled_roles: [Role::Jester].into_iter().collect(),
+        ..Settings::default()
+    };
+
+    let report = Simulation::new(settings).run(3, &mut StdRng::seed_from_u64(0));
+
+    assert_eq!(report.failures.len(), 3);
+    assert!(report.failures.iter().all(|failure| matches!(failure.result, SimulatedGameResult::CouldNotStart { .. })));
+}
+
+#[test]
+fn games_start_like_lobby_games() {
+    let settings = Settings {
+        role_list: RoleList(vec![RoleOutline::new_exact(Role::Detective); 4]),
+        enabled_roles: [Role::Detective].into_iter().collect(),
+        ..Settings::default()
+    };
+    let report = Simulation::new(settings.clone()).run(2, &mut StdRng::seed_from_u64(0));
+    assert_eq!(report.ended_games(), 0, "Games that are over before they start shouldn't count as wins");
+    assert_eq!(report.failures.len(), 2);
+
+    let preset = Preset::from_json(include_str!("../resources/presets/classic.json")).unwrap();
+    let settings = Settings { rules: GameRules { trials_per_day: 0, ..GameRules::default() }, ..preset.settings };
+    let report = Simulation::new(settings).run(2, &mut StdRng::seed_from_u64(0));
+    assert!(report.failures.iter().all(|failure| matches!(failure.result, SimulatedGameResult::CouldNotStart { .. })));
+    assert_eq!(report.failures.len(), 2);
+}
@@ -0,0 +1,113 @@
+use mafia_server::game::{
+    grave::Grave,
+    player::PlayerReference,
+    resolution_state::ResolutionState,
+    role::Role,
+    role_list::{Faction, RoleList, RoleOutline},
+    settings::Settings,
+    test::mock_game,
+    win_condition::WinCondition,
+    Game
+};
+
+fn game_with_roles(roles: &[Role]) -> Game {
+    let settings = Settings {
+        role_list: RoleList(roles.iter().map(|role| RoleOutline::new_exact(*role)).collect()),
+        enabled_roles: Role::values().into_iter().collect(),
+        seed: Some(0),
+        ..Default::default()
+    };
+    mock_game(settings, roles.len()).expect("Game should be created")
+}
+
+fn player_with_role(game: &Game, role: Role) -> PlayerReference {
+    PlayerReference::all_players(game).find(|player| player.role(game) == role).expect("Someone should have the role")
+}
+
+fn faction_resolution_state(faction: Faction) -> ResolutionState {
+    match faction {
+        Faction::Mafia => ResolutionState::Mafia,
+        Faction::Cult => ResolutionState::Cult,
+        Faction::Town => ResolutionState::Town,
+        Faction::Fiends => ResolutionState::Fiends,
+        Faction::Neutral => unreachable!(),
+    }
+}
+
+/// What every role should declare. Every neutral role has to be listed here
+fn expected_win_condition(role: Role) -> WinCondition {
+    match role {
+        Role::Minion | Role::Scarecrow => WinCondition::wins_with(vec![
+            ResolutionState::Mafia,
+            ResolutionState::Cult,
+            ResolutionState::Fiends,
+            ResolutionState::Death,
+            ResolutionState::Politician
+        ]),
+        Role::Politician => WinCondition::wins_with(vec![ResolutionState::Politician]),
+        Role::Wildcard | Role::TrueWildcard => WinCondition::Undecided,
+        Role::Jester | Role::RabbleRouser | Role::Doomsayer | Role::Death | Role::Martyr => WinCondition::RoleStateWon,
+        _ if role.faction() == Faction::Neutral => panic!("{role:?} has no expected win condition"),
+        _ => WinCondition::wins_with(vec![faction_resolution_state(role.faction())]),
+    }
+}
+
+#[test]
+fn every_role_declares_its_win_condition() {
+    for role in Role::values() {
+        let mut game = game_with_roles(&[role, Role::Detective, Role::Detective]);
+
+        // Some roles turn into other roles as soon as they're created
+        let player = game.roles_to_players.iter().find(|(starting_role, _)| *starting_role == role).unwrap().1;
+        player.set_role_state(&mut game, role.default_state());
+
+        assert_eq!(player.win_condition(&game), expected_win_condition(role), "{role:?} has the wrong win condition");
+        assert_eq!(player.keeps_game_running(&game), role.faction() != Faction::Neutral, "{role:?} is wrong about keeping the game running");
+    }
+}
+
+#[test]
+fn game_over_matrix() {
+    let cases: &[(&[Role], Option<ResolutionState>)] = &[
+        (&[Role::Detective, Role::Mafioso], None),
+        (&[Role::Detective, Role::Doctor], Some(ResolutionState::Town)),
+        (&[Role::Mafioso, Role::Consort], Some(ResolutionState::Mafia)),
+        (&[Role::Mafioso, Role::Minion], Some(ResolutionState::Mafia)),
+        (&[Role::Arsonist, Role::Werewolf], Some(ResolutionState::Fiends)),
+        (&[Role::Arsonist, Role::Mafioso], None),
+        (&[Role::Apostle, Role::Zealot], Some(ResolutionState::Cult)),
+        (&[Role::Apostle, Role::Detective], None),
+        (&[Role::Detective, Role::Jester], Some(ResolutionState::Town)),
+        (&[Role::Jester, Role::Doomsayer], Some(ResolutionState::Draw)),
+        (&[Role::Wildcard, Role::TrueWildcard], None),
+    ];
+
+    for (roles, expected) in cases {
+        let game = game_with_roles(roles);
+        assert_eq!(ResolutionState::game_is_over(&game), *expected, "{roles:?} should end in {expected:?}");
+    }
+}
+
+#[test]
+fn won_matches_win_condition() {
+    let game = game_with_roles(&[Role::Detective, Role::Doctor, Role::Jester, Role::Wildcard]);
+
+    assert!(player_with_role(&game, Role::Detective).get_won_game(&game));
+    assert!(!player_with_role(&game, Role::Jester).get_won_game(&game));
+    assert!(player_with_role(&game, Role::Wildcard).get_won_game(&game));
+}
+
+#[test]
+fn alive_at_end_wins_however_the_game_ends() {
+    let mut game = game_with_roles(&[Role::Detective, Role::Doctor, Role::Jester]);
+    let alive = player_with_role(&game, Role::Detective);
+    let dead = player_with_role(&game, Role::Jester);
+    let grave = Grave::from_player_lynch(&game, dead);
+    dead.die(&mut game, grave);
+
+    for resolution_state in ResolutionState::all() {
+        assert!(WinCondition::AliveAtEnd.can_win_with(&resolution_state));
+        assert!(WinCondition::AliveAtEnd.won(&game, alive, &resolution_state));
+        assert!(!WinCondition::AliveAtEnd.won(&game, dead, &resolution_state));
+    }
+}